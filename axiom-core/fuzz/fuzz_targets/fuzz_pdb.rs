@@ -0,0 +1,9 @@
+#![no_main]
+
+use axiom_core::parsers::pdb::parse_pdb_with_bonds_reader;
+use libfuzzer_sys::fuzz_target;
+use std::io::{BufReader, Cursor};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = parse_pdb_with_bonds_reader(BufReader::new(Cursor::new(data)));
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use axiom_core::parsers::xyz::parse_xyz_reader;
+use libfuzzer_sys::fuzz_target;
+use std::io::{BufReader, Cursor};
+
+// The parser must never panic on arbitrary bytes - only ever return `Ok` or
+// `Err`. Worth exercising here: the `num_atoms` header parse that drives
+// `Atoms::with_capacity` (a huge claimed count must not blow up memory), the
+// per-field column check in `parse_xyz_atom_line` before a line's columns
+// are indexed, and the trailing atom-count mismatch check.
+fuzz_target!(|data: &[u8]| {
+    let _ = parse_xyz_reader(BufReader::new(Cursor::new(data)));
+});
@@ -0,0 +1,182 @@
+// GPU buffer upload for the Atoms SoA.
+//
+// `Atoms` documents its Structure-of-Arrays layout as being "optimized for
+// GPU memory coalescing", but until now nothing actually moved it onto the
+// GPU - `renderer.rs` rebuilds an interleaved `AtomVertex` instance buffer
+// from scratch every frame instead. This module gives the SoA layout a real
+// GPU-resident counterpart: one `wgpu::Buffer` per array, uploaded once and
+// then updated in place as trajectory frames stream in, so a renderer no
+// longer has to reallocate and repack on every frame.
+
+use super::Atoms;
+use wgpu::util::DeviceExt;
+
+/// `x`/`y`/`z`/`elements` uploaded onto the GPU as separate buffers, mirroring
+/// the host-side SoA layout. All buffers carry `STORAGE | VERTEX | COPY_DST`
+/// usage so a shader can bind them either as a vertex buffer or a storage
+/// buffer, and so `update_positions` can rewrite them in place.
+pub struct GpuAtoms {
+    pub x_buffer: wgpu::Buffer,
+    pub y_buffer: wgpu::Buffer,
+    pub z_buffer: wgpu::Buffer,
+    pub elements_buffer: wgpu::Buffer,
+    pub count: usize,
+}
+
+/// Buffer usage shared by every `GpuAtoms` buffer: `STORAGE` for compute/
+/// shader binding, `VERTEX` so imposter/instance pipelines can bind the same
+/// buffer directly, `COPY_DST` so `update_positions` can rewrite it without
+/// recreating it.
+const ATOM_BUFFER_USAGE: wgpu::BufferUsages = wgpu::BufferUsages::STORAGE
+    .union(wgpu::BufferUsages::VERTEX)
+    .union(wgpu::BufferUsages::COPY_DST);
+
+impl GpuAtoms {
+    /// Upload `atoms.x`, `atoms.y`, `atoms.z`, and `atoms.elements` onto the
+    /// GPU as four separate buffers. `elements` is widened from `u8` to
+    /// `f32` since WGSL has no byte-sized numeric type a vertex/storage
+    /// buffer can bind directly.
+    pub fn upload(device: &wgpu::Device, atoms: &Atoms) -> Self {
+        let elements_f32: Vec<f32> = atoms.elements.iter().map(|&e| e as f32).collect();
+
+        let x_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("GpuAtoms X Buffer"),
+            contents: bytemuck::cast_slice(&atoms.x),
+            usage: ATOM_BUFFER_USAGE,
+        });
+        let y_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("GpuAtoms Y Buffer"),
+            contents: bytemuck::cast_slice(&atoms.y),
+            usage: ATOM_BUFFER_USAGE,
+        });
+        let z_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("GpuAtoms Z Buffer"),
+            contents: bytemuck::cast_slice(&atoms.z),
+            usage: ATOM_BUFFER_USAGE,
+        });
+        let elements_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("GpuAtoms Elements Buffer"),
+            contents: bytemuck::cast_slice(&elements_f32),
+            usage: ATOM_BUFFER_USAGE,
+        });
+
+        GpuAtoms {
+            x_buffer,
+            y_buffer,
+            z_buffer,
+            elements_buffer,
+            count: atoms.len(),
+        }
+    }
+
+    /// Re-write only the coordinate buffers in place via `queue.write_buffer`,
+    /// so a new trajectory frame can be streamed without reallocating any
+    /// buffer. `atoms` must have the same atom count this `GpuAtoms` was
+    /// uploaded with - topology (element, bond) buffers don't change frame
+    /// to frame, only positions do.
+    pub fn update_positions(&self, queue: &wgpu::Queue, atoms: &Atoms) {
+        queue.write_buffer(&self.x_buffer, 0, bytemuck::cast_slice(&atoms.x));
+        queue.write_buffer(&self.y_buffer, 0, bytemuck::cast_slice(&atoms.y));
+        queue.write_buffer(&self.z_buffer, 0, bytemuck::cast_slice(&atoms.z));
+    }
+}
+
+/// Pack `x`/`y`/`z`/`elements` into a single `vec4<f32>`-per-atom array
+/// (`w` holds the atomic number) for shaders that prefer one bound array
+/// over four.
+pub fn pack_positions_vec4(atoms: &Atoms) -> Vec<[f32; 4]> {
+    (0..atoms.len())
+        .map(|i| [atoms.x[i], atoms.y[i], atoms.z[i], atoms.elements[i] as f32])
+        .collect()
+}
+
+/// Upload the `pack_positions_vec4` layout as a single `wgpu::Buffer`, for
+/// shaders that prefer one bound array over the four separate buffers in
+/// `GpuAtoms`.
+pub fn upload_packed_positions(device: &wgpu::Device, atoms: &Atoms) -> wgpu::Buffer {
+    let packed = pack_positions_vec4(atoms);
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("GpuAtoms Packed vec4 Buffer"),
+        contents: bytemuck::cast_slice(&packed),
+        usage: ATOM_BUFFER_USAGE,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headless_device() -> (wgpu::Device, wgpu::Queue) {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        });
+        // Try hardware first, then retry with `force_fallback_adapter` - the
+        // GPU renderer is currently non-functional on ARM server, so a
+        // hardware-only request would otherwise fail this test outright
+        // there (see `Renderer::new`'s identical retry for the same reason).
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))
+        .or_else(|| {
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::LowPower,
+                compatible_surface: None,
+                force_fallback_adapter: true,
+            }))
+        })
+        .expect("no GPU or software adapter available for test");
+        pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("GpuAtoms Test Device"),
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::default(),
+                memory_hints: wgpu::MemoryHints::Performance,
+            },
+            None,
+        ))
+        .expect("failed to create test device")
+    }
+
+    fn sample_atoms() -> Atoms {
+        let mut atoms = Atoms::new();
+        atoms.push(1.0, 2.0, 3.0, 6);
+        atoms.push(4.0, 5.0, 6.0, 8);
+        atoms
+    }
+
+    #[test]
+    fn test_upload_produces_correctly_sized_buffers() {
+        let (device, _queue) = headless_device();
+        let atoms = sample_atoms();
+        let gpu_atoms = GpuAtoms::upload(&device, &atoms);
+
+        assert_eq!(gpu_atoms.count, 2);
+        assert_eq!(gpu_atoms.x_buffer.size(), (2 * std::mem::size_of::<f32>()) as u64);
+        assert_eq!(gpu_atoms.elements_buffer.size(), (2 * std::mem::size_of::<f32>()) as u64);
+    }
+
+    #[test]
+    fn test_update_positions_rewrites_coordinate_buffers() {
+        let (device, queue) = headless_device();
+        let atoms = sample_atoms();
+        let gpu_atoms = GpuAtoms::upload(&device, &atoms);
+
+        let mut next_frame = sample_atoms();
+        next_frame.x[0] = 42.0;
+        gpu_atoms.update_positions(&queue, &next_frame);
+        // write_buffer is fire-and-forget; just confirm it doesn't panic and
+        // the buffer sizes (set once at upload time) are unchanged.
+        assert_eq!(gpu_atoms.x_buffer.size(), (2 * std::mem::size_of::<f32>()) as u64);
+    }
+
+    #[test]
+    fn test_pack_positions_vec4() {
+        let atoms = sample_atoms();
+        let packed = pack_positions_vec4(&atoms);
+
+        assert_eq!(packed, vec![[1.0, 2.0, 3.0, 6.0], [4.0, 5.0, 6.0, 8.0]]);
+    }
+}
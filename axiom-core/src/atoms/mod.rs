@@ -1,5 +1,10 @@
 // Structure of Arrays (SoA) for GPU-optimized atomic data
+use crate::bonds::build_adjacency;
+use crate::errors::{AxiomError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+pub mod gpu;
 
 /// Structure of Arrays for atomic data
 ///
@@ -33,6 +38,29 @@ pub struct Atoms {
     pub chain_ids: Option<Vec<String>>,
     /// Optional: Residue indices
     pub residue_indices: Option<Vec<u32>>,
+    /// Optional: Atom names (e.g., "CA", "N", "OXT")
+    pub atom_names: Option<Vec<String>>,
+    /// Optional: Crystallographic occupancies (PDB columns 55-60)
+    pub occupancies: Option<Vec<f32>>,
+    /// Optional: Temperature (B) factors (PDB columns 61-66)
+    pub b_factors: Option<Vec<f32>>,
+    /// Optional: Alternate location indicators (PDB column 17); `' '` means
+    /// "no alternate location"
+    pub alt_locs: Option<Vec<char>>,
+    /// Optional: Formal charges (PDB columns 79-80)
+    pub formal_charges: Option<Vec<i8>>,
+    /// Optional: Per-atom velocities as read from the file (GRO columns
+    /// 45-68: nm/ps, stored without unit conversion)
+    pub velocities: Option<Vec<[f32; 3]>>,
+    /// Optional: Simulation box vectors (Angstroms), e.g. from a GRO file's
+    /// trailing box-vectors line. Distinct from `periodic_box`, which is the
+    /// orthogonalization matrix used for minimum-image distances.
+    pub box_vectors: Option<[[f32; 3]; 3]>,
+    /// Optional: Periodic box for minimum-image distance calculations
+    pub periodic_box: Option<PeriodicBox>,
+    /// Whether periodic-boundary (minimum-image) distances are active;
+    /// only meaningful when `periodic_box` is set - see `set_periodic`
+    periodic: bool,
 }
 
 impl Atoms {
@@ -49,6 +77,15 @@ impl Atoms {
             residue_names: None,
             chain_ids: None,
             residue_indices: None,
+            atom_names: None,
+            occupancies: None,
+            b_factors: None,
+            alt_locs: None,
+            formal_charges: None,
+            velocities: None,
+            box_vectors: None,
+            periodic_box: None,
+            periodic: false,
         }
     }
 
@@ -65,6 +102,15 @@ impl Atoms {
             residue_names: None,
             chain_ids: None,
             residue_indices: None,
+            atom_names: None,
+            occupancies: None,
+            b_factors: None,
+            alt_locs: None,
+            formal_charges: None,
+            velocities: None,
+            box_vectors: None,
+            periodic_box: None,
+            periodic: false,
         }
     }
 
@@ -112,6 +158,15 @@ impl Atoms {
         self.residue_names = None;
         self.chain_ids = None;
         self.residue_indices = None;
+        self.atom_names = None;
+        self.occupancies = None;
+        self.b_factors = None;
+        self.alt_locs = None;
+        self.formal_charges = None;
+        self.velocities = None;
+        self.box_vectors = None;
+        self.periodic_box = None;
+        self.periodic = false;
     }
 
     /// Reserve capacity for additional atoms
@@ -121,6 +176,228 @@ impl Atoms {
         self.z.reserve(additional);
         self.elements.reserve(additional);
     }
+
+    /// Set the triclinic periodic box used for minimum-image distance
+    /// calculations (fractional -> Cartesian orthogonalization matrix).
+    /// Periodicity becomes active immediately unless the matrix is singular.
+    pub fn set_periodic_box(&mut self, matrix: [[f32; 3]; 3]) {
+        self.periodic_box = PeriodicBox::from_matrix(matrix);
+        self.periodic = self.periodic_box.is_some();
+    }
+
+    /// Toggle periodic-boundary (minimum-image) distance calculations on or
+    /// off. Has no effect - stays open-boundary - if no periodic box has
+    /// been set via `set_periodic_box`.
+    pub fn set_periodic(&mut self, periodic: bool) {
+        self.periodic = periodic && self.periodic_box.is_some();
+    }
+
+    /// Whether periodic-boundary (minimum-image) distances are currently
+    /// active for `Within` selections
+    pub fn is_periodic(&self) -> bool {
+        self.periodic
+    }
+
+    /// Replicate the structure by integer lattice translations, producing
+    /// an `nx` x `ny` x `nz` supercell. Requires a periodic box (see
+    /// `set_periodic_box`) - there is no lattice to translate along
+    /// otherwise. All per-atom metadata is copied onto every replica; the
+    /// returned structure's periodic box is scaled to the supercell's
+    /// dimensions.
+    pub fn expand_supercell(&self, nx: u32, ny: u32, nz: u32) -> Result<Atoms> {
+        let pbox = self.periodic_box.ok_or_else(|| {
+            AxiomError::InvalidFormat(
+                "expand_supercell requires a periodic box (call set_periodic_box first)".to_string(),
+            )
+        })?;
+
+        if nx == 0 || ny == 0 || nz == 0 {
+            return Err(AxiomError::InvalidFormat(
+                "expand_supercell replication counts must each be at least 1".to_string(),
+            ));
+        }
+
+        let mut expanded = Atoms::with_capacity(self.len() * (nx * ny * nz) as usize);
+
+        for iz in 0..nz {
+            for iy in 0..ny {
+                for ix in 0..nx {
+                    for i in 0..self.len() {
+                        let mut frac = pbox.cartesian_to_fractional([self.x[i], self.y[i], self.z[i]]);
+                        frac[0] += ix as f32;
+                        frac[1] += iy as f32;
+                        frac[2] += iz as f32;
+                        let cart = pbox.fractional_to_cartesian(frac);
+
+                        expanded.push(cart[0], cart[1], cart[2], self.elements[i]);
+                        copy_atom_metadata(self, &mut expanded, i);
+                    }
+                }
+            }
+        }
+
+        let m = pbox.matrix;
+        expanded.set_periodic_box([
+            [m[0][0] * nx as f32, m[0][1] * ny as f32, m[0][2] * nz as f32],
+            [m[1][0] * nx as f32, m[1][1] * ny as f32, m[1][2] * nz as f32],
+            [m[2][0] * nx as f32, m[2][1] * ny as f32, m[2][2] * nz as f32],
+        ]);
+
+        Ok(expanded)
+    }
+
+    /// Collapse alternate-location (`altLoc`) conformers down to one atom
+    /// per site, the way standard PDB readers do: atoms sharing the same
+    /// chain/residue index/atom name are one site, and only the
+    /// highest-occupancy entry is kept for each (first occurrence breaks
+    /// ties), unless `prefer` names a specific altLoc letter to keep
+    /// instead wherever that letter is present at the site. Atom order is
+    /// preserved. Requires `alt_locs`, `atom_names`, `residue_indices`, and
+    /// `chain_ids` to all be present - without them there is no way to tell
+    /// which atoms are alternates of the same site, so the structure is
+    /// returned unchanged.
+    pub fn collapse_alt_locs(&self, prefer: Option<char>) -> Atoms {
+        let (alt_locs, atom_names, residue_indices, chain_ids) = match (
+            &self.alt_locs,
+            &self.atom_names,
+            &self.residue_indices,
+            &self.chain_ids,
+        ) {
+            (Some(a), Some(n), Some(r), Some(c)) => (a, n, r, c),
+            _ => return self.clone(),
+        };
+
+        let occupancy_of = |i: usize| -> f32 {
+            self.occupancies.as_ref().map(|occ| occ[i]).unwrap_or(1.0)
+        };
+
+        let mut best_index: HashMap<(String, u32, String), usize> = HashMap::new();
+
+        for i in 0..self.len() {
+            let key = (chain_ids[i].clone(), residue_indices[i], atom_names[i].clone());
+
+            best_index
+                .entry(key)
+                .and_modify(|existing| {
+                    let replace = match prefer {
+                        Some(p) if alt_locs[*existing] == p => false,
+                        Some(p) if alt_locs[i] == p => true,
+                        _ => occupancy_of(i) > occupancy_of(*existing),
+                    };
+                    if replace {
+                        *existing = i;
+                    }
+                })
+                .or_insert(i);
+        }
+
+        let mut keep_indices: Vec<usize> = best_index.into_values().collect();
+        keep_indices.sort_unstable();
+
+        let mut collapsed = Atoms::with_capacity(keep_indices.len());
+        for &i in &keep_indices {
+            collapsed.push(self.x[i], self.y[i], self.z[i], self.elements[i]);
+            copy_atom_metadata(self, &mut collapsed, i);
+        }
+        collapsed
+    }
+
+    /// Split this structure into its disconnected molecular fragments,
+    /// using `bonds`' adjacency (BFS over connected components) rather
+    /// than distance - so callers can isolate a ligand from solvent, drop
+    /// counter-ions, or otherwise separate what `compute_bonds`/a
+    /// topology parser already told us is unconnected. An atom with no
+    /// bonds at all becomes its own single-atom fragment. Each fragment is
+    /// a fresh `Atoms`/`Bonds` pair with indices re-based to 0 and all
+    /// per-atom metadata carried over (`copy_atom_metadata`); atoms within
+    /// a fragment keep their relative order from `self`, and fragments are
+    /// returned in order of their lowest original atom index.
+    pub fn separate(&self, bonds: &Bonds) -> Vec<(Atoms, Bonds)> {
+        let adjacency = build_adjacency(self, bonds);
+        let mut component_of = vec![usize::MAX; self.len()];
+        let mut num_components = 0;
+
+        for start in 0..self.len() {
+            if component_of[start] != usize::MAX {
+                continue;
+            }
+
+            let mut queue = VecDeque::new();
+            queue.push_back(start);
+            component_of[start] = num_components;
+
+            while let Some(current) = queue.pop_front() {
+                for &(neighbor, _order) in &adjacency[current] {
+                    if component_of[neighbor] == usize::MAX {
+                        component_of[neighbor] = num_components;
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+
+            num_components += 1;
+        }
+
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); num_components];
+        for (atom_index, &component) in component_of.iter().enumerate() {
+            members[component].push(atom_index);
+        }
+
+        let mut old_to_new = vec![0u32; self.len()];
+        let fragment_atoms: Vec<Atoms> = members
+            .iter()
+            .map(|indices| {
+                let mut fragment = Atoms::with_capacity(indices.len());
+                for (new_index, &old_index) in indices.iter().enumerate() {
+                    fragment.push(self.x[old_index], self.y[old_index], self.z[old_index], self.elements[old_index]);
+                    copy_atom_metadata(self, &mut fragment, old_index);
+                    old_to_new[old_index] = new_index as u32;
+                }
+                fragment
+            })
+            .collect();
+
+        let mut fragment_bonds: Vec<Bonds> = (0..num_components).map(|_| Bonds::new()).collect();
+        for i in 0..bonds.len() {
+            let a = bonds.atom1[i] as usize;
+            let b = bonds.atom2[i] as usize;
+            let component = component_of[a]; // a and b are always in the same component
+            fragment_bonds[component].push(old_to_new[a], old_to_new[b], bonds.order[i]);
+        }
+
+        fragment_atoms.into_iter().zip(fragment_bonds).collect()
+    }
+
+    /// The fragment (see `separate`) with the most atoms - typically the
+    /// macromolecule once solvent and counter-ions have been split into
+    /// their own small fragments. `None` for an empty structure.
+    pub fn largest_fragment(&self, bonds: &Bonds) -> Option<(Atoms, Bonds)> {
+        self.separate(bonds).into_iter().max_by_key(|(atoms, _)| atoms.len())
+    }
+}
+
+/// Append atom `index`'s optional metadata from `src` onto `dest`, for every
+/// per-atom `Option<Vec<_>>` field - used by `expand_supercell` so each
+/// supercell replica keeps the original atoms' charges, names, residues, etc.
+fn copy_atom_metadata(src: &Atoms, dest: &mut Atoms, index: usize) {
+    fn append<T: Clone>(dest: &mut Option<Vec<T>>, src: &Option<Vec<T>>, index: usize) {
+        if let Some(src_vec) = src {
+            dest.get_or_insert_with(Vec::new).push(src_vec[index].clone());
+        }
+    }
+
+    append(&mut dest.charges, &src.charges, index);
+    append(&mut dest.atom_types, &src.atom_types, index);
+    append(&mut dest.molecule_ids, &src.molecule_ids, index);
+    append(&mut dest.residue_names, &src.residue_names, index);
+    append(&mut dest.chain_ids, &src.chain_ids, index);
+    append(&mut dest.residue_indices, &src.residue_indices, index);
+    append(&mut dest.atom_names, &src.atom_names, index);
+    append(&mut dest.occupancies, &src.occupancies, index);
+    append(&mut dest.b_factors, &src.b_factors, index);
+    append(&mut dest.alt_locs, &src.alt_locs, index);
+    append(&mut dest.formal_charges, &src.formal_charges, index);
+    append(&mut dest.velocities, &src.velocities, index);
 }
 
 impl Default for Atoms {
@@ -129,6 +406,103 @@ impl Default for Atoms {
     }
 }
 
+/// Triclinic periodic box used for minimum-image distance calculations: the
+/// orthogonalization matrix (fractional -> Cartesian, as built from a
+/// crystallographic a/b/c/alpha/beta/gamma cell) and its inverse.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PeriodicBox {
+    /// Cartesian = matrix * fractional
+    pub matrix: [[f32; 3]; 3],
+    /// fractional = inverse * Cartesian
+    pub inverse: [[f32; 3]; 3],
+}
+
+impl PeriodicBox {
+    /// Build a periodic box from an orthogonalization matrix, inverting it.
+    /// Returns `None` for a singular (zero-volume) cell.
+    pub fn from_matrix(matrix: [[f32; 3]; 3]) -> Option<Self> {
+        invert_3x3(&matrix).map(|inverse| PeriodicBox { matrix, inverse })
+    }
+
+    fn transform(m: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+        [
+            m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+            m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+            m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+        ]
+    }
+
+    /// Convert a Cartesian point to fractional coordinates
+    pub fn cartesian_to_fractional(&self, cart: [f32; 3]) -> [f32; 3] {
+        Self::transform(&self.inverse, cart)
+    }
+
+    /// Convert a fractional point to Cartesian coordinates
+    pub fn fractional_to_cartesian(&self, fract: [f32; 3]) -> [f32; 3] {
+        Self::transform(&self.matrix, fract)
+    }
+
+    /// Minimum-image displacement from `pos_ref` to `pos_i`: the shortest
+    /// vector between them over all periodic images of the box.
+    pub fn minimum_image(&self, pos_i: [f32; 3], pos_ref: [f32; 3]) -> [f32; 3] {
+        let d = [pos_i[0] - pos_ref[0], pos_i[1] - pos_ref[1], pos_i[2] - pos_ref[2]];
+        let mut f = self.cartesian_to_fractional(d);
+        for c in f.iter_mut() {
+            *c -= c.round();
+        }
+        self.fractional_to_cartesian(f)
+    }
+
+    /// Recover the crystallographic cell lengths (Angstroms) and angles
+    /// (degrees) that produced this box, for formats that describe the cell
+    /// as `a, b, c, alpha, beta, gamma` (e.g. PDB `CRYST1`) rather than as a
+    /// raw matrix.
+    pub fn lengths_angles(&self) -> (f32, f32, f32, f32, f32, f32) {
+        let a = [self.matrix[0][0], self.matrix[1][0], self.matrix[2][0]];
+        let b = [self.matrix[0][1], self.matrix[1][1], self.matrix[2][1]];
+        let c = [self.matrix[0][2], self.matrix[1][2], self.matrix[2][2]];
+
+        let norm = |v: [f32; 3]| (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+        let dot = |u: [f32; 3], v: [f32; 3]| u[0] * v[0] + u[1] * v[1] + u[2] * v[2];
+        let angle = |u: [f32; 3], v: [f32; 3]| {
+            (dot(u, v) / (norm(u) * norm(v))).clamp(-1.0, 1.0).acos().to_degrees()
+        };
+
+        (norm(a), norm(b), norm(c), angle(b, c), angle(a, c), angle(a, b))
+    }
+}
+
+/// Invert a 3x3 matrix via the adjugate/cofactor method. Returns `None` if
+/// the determinant is ~0 (degenerate cell).
+fn invert_3x3(m: &[[f32; 3]; 3]) -> Option<[[f32; 3]; 3]> {
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+
+    if det.abs() < 1e-10 {
+        return None;
+    }
+
+    let inv_det = 1.0 / det;
+    Some([
+        [
+            (m[1][1] * m[2][2] - m[1][2] * m[2][1]) * inv_det,
+            (m[0][2] * m[2][1] - m[0][1] * m[2][2]) * inv_det,
+            (m[0][1] * m[1][2] - m[0][2] * m[1][1]) * inv_det,
+        ],
+        [
+            (m[1][2] * m[2][0] - m[1][0] * m[2][2]) * inv_det,
+            (m[0][0] * m[2][2] - m[0][2] * m[2][0]) * inv_det,
+            (m[0][2] * m[1][0] - m[0][0] * m[1][2]) * inv_det,
+        ],
+        [
+            (m[1][0] * m[2][1] - m[1][1] * m[2][0]) * inv_det,
+            (m[0][1] * m[2][0] - m[0][0] * m[2][1]) * inv_det,
+            (m[0][0] * m[1][1] - m[0][1] * m[1][0]) * inv_det,
+        ],
+    ])
+}
+
 /// Bond data (pairs of atom indices)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bonds {
@@ -192,6 +566,10 @@ pub struct UnitCell {
     /// Cell matrix: [[a_x, b_x, c_x],
     ///               [a_y, b_y, c_y],
     ///               [a_z, b_z, c_z]]
+    /// i.e. each row is a Cartesian component and each column a lattice
+    /// vector - the same column-major convention `PeriodicBox::transform`
+    /// and the CIF/LAMMPS parsers' hand-built cell matrices use, so
+    /// `matrix` can be handed straight to `Atoms::set_periodic_box`.
     pub matrix: [[f32; 3]; 3],
 }
 
@@ -199,7 +577,11 @@ impl UnitCell {
     /// Create unit cell from cell vectors
     pub fn from_vectors(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> Self {
         UnitCell {
-            matrix: [a, b, c],
+            matrix: [
+                [a[0], b[0], c[0]],
+                [a[1], b[1], c[1]],
+                [a[2], b[2], c[2]],
+            ],
         }
     }
 
@@ -227,18 +609,19 @@ impl UnitCell {
 
         UnitCell {
             matrix: [
-                [a, 0.0, 0.0],
-                [b * cos_gamma, b * sin_gamma, 0.0],
-                [c_x, c_y, c_z],
+                [a, b * cos_gamma, c_x],
+                [0.0, b * sin_gamma, c_y],
+                [0.0, 0.0, c_z],
             ],
         }
     }
 
     /// Get cell volume
     pub fn volume(&self) -> f32 {
-        let a = self.matrix[0];
-        let b = self.matrix[1];
-        let c = self.matrix[2];
+        // a, b, c are the matrix's columns, not its rows (see `matrix`'s doc).
+        let a = [self.matrix[0][0], self.matrix[1][0], self.matrix[2][0]];
+        let b = [self.matrix[0][1], self.matrix[1][1], self.matrix[2][1]];
+        let c = [self.matrix[0][2], self.matrix[1][2], self.matrix[2][2]];
 
         // Volume = |a · (b × c)|
         let b_cross_c = [
@@ -289,4 +672,211 @@ mod tests {
         // Cubic cell: volume = a³
         assert!((volume - 1000.0).abs() < 0.1);
     }
+
+    fn cubic_box(edge: f32) -> PeriodicBox {
+        PeriodicBox::from_matrix([
+            [edge, 0.0, 0.0],
+            [0.0, edge, 0.0],
+            [0.0, 0.0, edge],
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_periodic_box_minimum_image_wraps_across_boundary() {
+        let pbox = cubic_box(10.0);
+
+        // Two atoms near opposite faces of the box are actually 1Å apart
+        // through the periodic image, not 9Å through the box interior.
+        let pos_i = [9.5, 0.0, 0.0];
+        let pos_ref = [0.5, 0.0, 0.0];
+        let d = pbox.minimum_image(pos_i, pos_ref);
+        let dist = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+        assert!((dist - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_periodic_box_singular_matrix_returns_none() {
+        assert!(PeriodicBox::from_matrix([[0.0; 3]; 3]).is_none());
+    }
+
+    #[test]
+    fn test_unit_cell_from_vectors_sheared_round_trips_through_fractional() {
+        // A sheared (non-orthogonal) cell: b has a nonzero x-component, so a
+        // row/column transpose bug in `from_vectors` would only show up here
+        // - every pre-existing test cell is orthogonal (all off-diagonals
+        // zero), which makes the two conventions indistinguishable.
+        let a = [10.0, 0.0, 0.0];
+        let b = [2.0, 8.0, 0.0];
+        let c = [0.0, 0.0, 6.0];
+        let cell = UnitCell::from_vectors(a, b, c);
+        let pbox = PeriodicBox::from_matrix(cell.matrix).unwrap();
+
+        let cart_a = pbox.fractional_to_cartesian([1.0, 0.0, 0.0]);
+        let cart_b = pbox.fractional_to_cartesian([0.0, 1.0, 0.0]);
+        let cart_c = pbox.fractional_to_cartesian([0.0, 0.0, 1.0]);
+
+        for (got, want) in [(cart_a, a), (cart_b, b), (cart_c, c)] {
+            for axis in 0..3 {
+                assert!((got[axis] - want[axis]).abs() < 1e-4, "got {:?}, want {:?}", got, want);
+            }
+        }
+
+        // And back: b's own fractional coordinates are (0, 1, 0).
+        let frac_b = pbox.cartesian_to_fractional(b);
+        assert!((frac_b[0] - 0.0).abs() < 1e-4);
+        assert!((frac_b[1] - 1.0).abs() < 1e-4);
+        assert!((frac_b[2] - 0.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_periodic_box_lengths_angles_cubic() {
+        let pbox = cubic_box(10.0);
+        let (a, b, c, alpha, beta, gamma) = pbox.lengths_angles();
+        assert!((a - 10.0).abs() < 1e-4);
+        assert!((b - 10.0).abs() < 1e-4);
+        assert!((c - 10.0).abs() < 1e-4);
+        assert!((alpha - 90.0).abs() < 1e-3);
+        assert!((beta - 90.0).abs() < 1e-3);
+        assert!((gamma - 90.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_expand_supercell_without_box_errors() {
+        let atoms = Atoms::new();
+        assert!(atoms.expand_supercell(2, 1, 1).is_err());
+    }
+
+    #[test]
+    fn test_expand_supercell_replicates_atoms_and_box() {
+        let mut atoms = Atoms::new();
+        atoms.set_periodic_box([[10.0, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]]);
+        atoms.push(1.0, 1.0, 1.0, 6);
+        atoms.atom_names = Some(vec!["C1".to_string()]);
+
+        let super_cell = atoms.expand_supercell(2, 1, 1).unwrap();
+
+        assert_eq!(super_cell.len(), 2);
+        assert_eq!(super_cell.position(0), Some([1.0, 1.0, 1.0]));
+        assert_eq!(super_cell.position(1), Some([11.0, 1.0, 1.0]));
+        assert_eq!(super_cell.atom_names, Some(vec!["C1".to_string(), "C1".to_string()]));
+
+        let pbox = super_cell.periodic_box.unwrap();
+        assert!((pbox.matrix[0][0] - 20.0).abs() < 1e-4);
+        assert!((pbox.matrix[1][1] - 10.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_atoms_set_periodic_toggle() {
+        let mut atoms = Atoms::new();
+        assert!(!atoms.is_periodic());
+
+        atoms.set_periodic(true);
+        assert!(!atoms.is_periodic(), "toggling on without a box must stay open-boundary");
+
+        atoms.set_periodic_box([[10.0, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]]);
+        assert!(atoms.is_periodic());
+
+        atoms.set_periodic(false);
+        assert!(!atoms.is_periodic());
+    }
+
+    #[test]
+    fn test_collapse_alt_locs_keeps_highest_occupancy() {
+        let mut atoms = Atoms::new();
+        atoms.push(1.0, 0.0, 0.0, 6);
+        atoms.push(1.1, 0.0, 0.0, 6);
+        atoms.chain_ids = Some(vec!["A".to_string(), "A".to_string()]);
+        atoms.residue_indices = Some(vec![1, 1]);
+        atoms.atom_names = Some(vec!["CA".to_string(), "CA".to_string()]);
+        atoms.alt_locs = Some(vec!['A', 'B']);
+        atoms.occupancies = Some(vec![0.3, 0.7]);
+
+        let collapsed = atoms.collapse_alt_locs(None);
+
+        assert_eq!(collapsed.len(), 1);
+        assert_eq!(collapsed.position(0), Some([1.1, 0.0, 0.0]));
+        assert_eq!(collapsed.alt_locs, Some(vec!['B']));
+    }
+
+    #[test]
+    fn test_collapse_alt_locs_prefer_overrides_occupancy() {
+        let mut atoms = Atoms::new();
+        atoms.push(1.0, 0.0, 0.0, 6);
+        atoms.push(1.1, 0.0, 0.0, 6);
+        atoms.chain_ids = Some(vec!["A".to_string(), "A".to_string()]);
+        atoms.residue_indices = Some(vec![1, 1]);
+        atoms.atom_names = Some(vec!["CA".to_string(), "CA".to_string()]);
+        atoms.alt_locs = Some(vec!['A', 'B']);
+        atoms.occupancies = Some(vec![0.3, 0.7]);
+
+        let collapsed = atoms.collapse_alt_locs(Some('A'));
+
+        assert_eq!(collapsed.len(), 1);
+        assert_eq!(collapsed.position(0), Some([1.0, 0.0, 0.0]));
+        assert_eq!(collapsed.alt_locs, Some(vec!['A']));
+    }
+
+    #[test]
+    fn test_collapse_alt_locs_passthrough_without_metadata() {
+        let mut atoms = Atoms::new();
+        atoms.push(1.0, 0.0, 0.0, 6);
+        atoms.push(1.1, 0.0, 0.0, 6);
+
+        let collapsed = atoms.collapse_alt_locs(None);
+
+        assert_eq!(collapsed.len(), 2);
+    }
+
+    #[test]
+    fn test_separate_splits_ligand_from_solvent() {
+        // 0-1: a bonded pair (the "ligand"); 2, 3: two unbonded waters'
+        // oxygens standing in for solvent, each its own fragment.
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 6);
+        atoms.push(1.0, 0.0, 0.0, 6);
+        atoms.push(5.0, 0.0, 0.0, 8);
+        atoms.push(9.0, 0.0, 0.0, 8);
+        atoms.atom_names = Some(vec!["C1".into(), "C2".into(), "OW".into(), "OW".into()]);
+
+        let mut bonds = Bonds::new();
+        bonds.push(0, 1, 1);
+
+        let fragments = atoms.separate(&bonds);
+
+        assert_eq!(fragments.len(), 3);
+        let (ligand, ligand_bonds) = &fragments[0];
+        assert_eq!(ligand.len(), 2);
+        assert_eq!(ligand_bonds.len(), 1);
+        assert_eq!(ligand_bonds.get(0), Some((0, 1, 1)));
+        assert_eq!(ligand.atom_names, Some(vec!["C1".to_string(), "C2".to_string()]));
+
+        assert_eq!(fragments[1].0.len(), 1);
+        assert_eq!(fragments[1].1.len(), 0);
+        assert_eq!(fragments[2].0.len(), 1);
+        assert_eq!(fragments[2].1.len(), 0);
+    }
+
+    #[test]
+    fn test_largest_fragment_picks_the_macromolecule() {
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 6);
+        atoms.push(1.0, 0.0, 0.0, 6);
+        atoms.push(2.0, 0.0, 0.0, 6);
+        atoms.push(9.0, 0.0, 0.0, 17); // lone chloride ion
+
+        let mut bonds = Bonds::new();
+        bonds.push(0, 1, 1);
+        bonds.push(1, 2, 1);
+
+        let (largest, largest_bonds) = atoms.largest_fragment(&bonds).unwrap();
+        assert_eq!(largest.len(), 3);
+        assert_eq!(largest_bonds.len(), 2);
+    }
+
+    #[test]
+    fn test_largest_fragment_empty_structure() {
+        let atoms = Atoms::new();
+        let bonds = Bonds::new();
+        assert!(atoms.largest_fragment(&bonds).is_none());
+    }
 }
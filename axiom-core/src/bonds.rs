@@ -2,7 +2,8 @@
 //
 // Computes bonds between atoms based on distances and covalent radii
 
-use crate::atoms::{Atoms, Bonds};
+use crate::atoms::{Atoms, Bonds, PeriodicBox, UnitCell};
+use std::collections::{HashMap, HashSet};
 
 /// Covalent radii for elements (in Angstroms)
 /// Source: Cordero et al. (2008) "Covalent radii revisited"
@@ -65,6 +66,64 @@ fn covalent_radius(element: u8) -> f32 {
     }
 }
 
+/// Maximum simultaneous bonds ("valence") per element, used by
+/// `compute_bonds_valence` to prune distance-only candidate bonds down to a
+/// chemically plausible connectivity. Main-group values follow typical
+/// bonding capacity (H=1, O=2 from its two lone pairs, N=4 allowing the
+/// protonated/ammonium case, C=4, hypervalent P/S=6); everything else
+/// defaults to 6, generous enough for the octahedral coordination common
+/// among transition metals without being unbounded.
+const MAX_BONDS: [u8; 119] = [
+    0, // 0: placeholder
+    1, // 1: H
+    0, // 2: He
+    1, // 3: Li
+    2, // 4: Be
+    4, // 5: B
+    4, // 6: C
+    4, // 7: N
+    2, // 8: O
+    1, // 9: F
+    0, // 10: Ne
+    1, // 11: Na
+    2, // 12: Mg
+    6, // 13: Al
+    6, // 14: Si
+    6, // 15: P
+    6, // 16: S
+    1, // 17: Cl
+    0, // 18: Ar
+    1, // 19: K
+    2, // 20: Ca
+    // Fill in more as needed, for now use 6 as default (generous for
+    // transition-metal coordination numbers).
+    6, 6, 6, 6, 6, 6, 6, 6, 6, 6, // 21-30
+    4, 6, 6, 6, 1, 0, // 31-36
+    1, 2, 6, 6, 6, 6, // 37-42
+    6, 6, 6, 6, 6, 6, // 43-48
+    6, 6, 6, 6, 1, 0, // 49-54
+    1, 2, 6, 6, 6, 6, // 55-60
+    6, 6, 6, 6, 6, 6, // 61-66
+    6, 6, 6, 6, 6, 6, // 67-72
+    6, 6, 6, 6, 6, 6, // 73-78
+    6, 6, 6, 6, 6, 6, // 79-84
+    1, 0, 1, 2, 6, 6, // 85-90
+    6, 6, 6, 6, 6, 6, // 91-96
+    6, 6, 6, 6, 6, 6, // 97-102
+    6, 6, 6, 6, 6, 6, // 103-108
+    6, 6, 6, 6, 6, 6, // 109-114
+    6, 6, 6, 6, // 115-118
+];
+
+/// Get the maximum simultaneous bond count ("valence") for an element.
+fn max_bonds(element: u8) -> u8 {
+    if (element as usize) < MAX_BONDS.len() {
+        MAX_BONDS[element as usize]
+    } else {
+        6 // Default for unknown elements
+    }
+}
+
 /// Compute bonds between atoms based on distances
 ///
 /// A bond is created if the distance between two atoms is less than
@@ -77,51 +136,494 @@ fn covalent_radius(element: u8) -> f32 {
 ///
 /// # Returns
 /// A Bonds structure containing all detected bonds
+///
+/// Thin wrapper over the cell-list implementation below - kept as its own
+/// function so the signature (and therefore every call site) stays
+/// unchanged while the actual neighbor search scales near-linearly instead
+/// of the old O(n^2) all-pairs scan. `Octree::query_radius` now offers the
+/// same fixed-radius search (see `octree.rs`), but the flat uniform grid
+/// here is already near-linear and avoids a tree build and per-atom
+/// descent, so this is left on the cell list rather than switched over.
 pub fn compute_bonds(atoms: &Atoms, tolerance: f32, max_distance: f32) -> Bonds {
+    compute_bonds_cell_list(atoms, tolerance, max_distance)
+}
+
+/// Cell-list (uniform grid) candidate bond search: bins atoms into cells
+/// sized to the same `max_distance` cutoff the brute-force scan already
+/// quick-rejects on, then for each atom only tests candidates in its own
+/// cell plus the 26 neighbor cells. Cell size must be >= the distance
+/// cutoff actually used below (`max_distance`, clamped away from zero) -
+/// otherwise a bonded pair further apart than one cell could fall outside
+/// the 3x3x3 neighbor block and be missed entirely.
+///
+/// Returns `(atom1, atom2, distance)` triples sorted by `(atom1, atom2)`,
+/// so both `compute_bonds_cell_list` (which ignores the distance) and
+/// `compute_bonds_valence` (which prunes by it) see the same ordering
+/// `compute_bonds`'s previous brute-force loop produced (ascending `i`,
+/// then ascending `j`) - grid bucket traversal order has no such guarantee
+/// on its own.
+fn collect_candidate_bonds(atoms: &Atoms, tolerance: f32, max_distance: f32) -> Vec<(u32, u32, f32)> {
+    let n = atoms.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // Clamp away from zero/negative so degenerate configs can't divide by
+    // zero or produce a single infinitely-large cell.
+    let cell_size = max_distance.max(1e-3);
+
+    let mut min = [f32::INFINITY; 3];
+    for i in 0..n {
+        min[0] = min[0].min(atoms.x[i]);
+        min[1] = min[1].min(atoms.y[i]);
+        min[2] = min[2].min(atoms.z[i]);
+    }
+
+    let cell_of = |i: usize| -> (i32, i32, i32) {
+        (
+            ((atoms.x[i] - min[0]) / cell_size).floor() as i32,
+            ((atoms.y[i] - min[1]) / cell_size).floor() as i32,
+            ((atoms.z[i] - min[2]) / cell_size).floor() as i32,
+        )
+    };
+
+    let mut grid: HashMap<(i32, i32, i32), Vec<u32>> = HashMap::new();
+    for i in 0..n {
+        grid.entry(cell_of(i)).or_default().push(i as u32);
+    }
+
+    let mut pairs: Vec<(u32, u32, f32)> = Vec::new();
+
+    for i in 0..n {
+        let (cx, cy, cz) = cell_of(i);
+        let x1 = atoms.x[i];
+        let y1 = atoms.y[i];
+        let z1 = atoms.z[i];
+        let r1 = covalent_radius(atoms.elements[i]);
+
+        for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let neighbor_cell = (cx + dx, cy + dy, cz + dz);
+                    let candidates = match grid.get(&neighbor_cell) {
+                        Some(candidates) => candidates,
+                        None => continue,
+                    };
+
+                    for &j_u32 in candidates {
+                        let j = j_u32 as usize;
+                        if j <= i {
+                            // Each unordered pair is only counted from the
+                            // lower-indexed atom's scan, matching the
+                            // brute-force loop's `for j in (i+1)..n`.
+                            continue;
+                        }
+
+                        let x2 = atoms.x[j];
+                        let y2 = atoms.y[j];
+                        let z2 = atoms.z[j];
+                        let r2 = covalent_radius(atoms.elements[j]);
+
+                        let dx = x2 - x1;
+                        let dy = y2 - y1;
+                        let dz = z2 - z1;
+                        let dist_sq = dx * dx + dy * dy + dz * dz;
+
+                        if dist_sq > max_distance * max_distance {
+                            continue;
+                        }
+
+                        let dist = dist_sq.sqrt();
+                        let bond_threshold = (r1 + r2) * tolerance;
+
+                        if dist < bond_threshold {
+                            pairs.push((i as u32, j as u32, dist));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pairs.sort_unstable_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+    pairs
+}
+
+fn compute_bonds_cell_list(atoms: &Atoms, tolerance: f32, max_distance: f32) -> Bonds {
+    let pairs = collect_candidate_bonds(atoms, tolerance, max_distance);
+    let mut bonds = Bonds::new();
+    bonds.atom1.reserve(pairs.len());
+    bonds.atom2.reserve(pairs.len());
+    bonds.order.reserve(pairs.len());
+
+    for (a, b, _) in pairs {
+        bonds.atom1.push(a);
+        bonds.atom2.push(b);
+        bonds.order.push(1); // Default to single bond
+    }
+
+    bonds
+}
+
+/// Distance-only bonding (`compute_bonds`) can produce chemically impossible
+/// structures - a hydrogen with three bonds, an overcrowded metal center.
+/// This computes the same distance-only candidate bonds, then prunes: for
+/// any atom whose degree exceeds `max_bonds(element)`, repeatedly drops its
+/// longest remaining candidate bond until it's within its valence limit.
+/// Atoms are processed in index order and the degree count is updated after
+/// every drop, so a bond removed to satisfy one atom's valence is also
+/// reflected in its partner's count - an atom that only looked hypervalent
+/// because of a bond another atom already shed won't over-prune.
+pub fn compute_bonds_valence(atoms: &Atoms, tolerance: f32, max_distance: f32) -> Bonds {
+    let candidates = collect_candidate_bonds(atoms, tolerance, max_distance);
     let n = atoms.len();
+
+    let mut degree = vec![0u32; n];
+    for &(a, b, _) in &candidates {
+        degree[a as usize] += 1;
+        degree[b as usize] += 1;
+    }
+
+    let mut kept = vec![true; candidates.len()];
+    for atom_idx in 0..n {
+        let limit = max_bonds(atoms.elements[atom_idx]) as u32;
+        while degree[atom_idx] > limit {
+            // Find the longest remaining candidate bond incident to this atom.
+            let longest = candidates
+                .iter()
+                .enumerate()
+                .filter(|(ci, (a, b, _))| kept[*ci] && (*a == atom_idx as u32 || *b == atom_idx as u32))
+                .max_by(|(_, (_, _, d1)), (_, (_, _, d2))| d1.partial_cmp(d2).unwrap());
+
+            match longest {
+                Some((ci, &(a, b, _))) => {
+                    kept[ci] = false;
+                    degree[a as usize] -= 1;
+                    degree[b as usize] -= 1;
+                }
+                None => break, // no candidate bonds left to drop
+            }
+        }
+    }
+
+    let mut bonds = Bonds::new();
+    for (ci, &(a, b, _)) in candidates.iter().enumerate() {
+        if kept[ci] {
+            bonds.atom1.push(a);
+            bonds.atom2.push(b);
+            bonds.order.push(1);
+        }
+    }
+
+    bonds
+}
+
+/// Typical single/double/triple bond lengths (Angstroms), for the element
+/// pairs common enough in organic/inorganic structures that distinguishing
+/// multiple bonds by distance alone is worthwhile: C-C, C-N, C-O, N-O.
+/// Source: standard organic chemistry bond-length tables (e.g. ethane/
+/// ethene/ethyne C-C at 1.54/1.34/1.20 A). Pairs not listed here have no
+/// well-established double/triple bond length to compare against, so
+/// `classify_bond_order` leaves them at the default single-bond order.
+fn expected_bond_lengths(elem_a: u8, elem_b: u8) -> Option<(f32, f32, f32)> {
+    let (lo, hi) = if elem_a <= elem_b { (elem_a, elem_b) } else { (elem_b, elem_a) };
+    match (lo, hi) {
+        (6, 6) => Some((1.54, 1.34, 1.20)), // C-C: ethane / ethene / ethyne
+        (6, 7) => Some((1.47, 1.29, 1.16)), // C-N: amine / imine / nitrile
+        (6, 8) => Some((1.43, 1.23, 1.13)), // C-O: alcohol/ether / carbonyl / rare C#O
+        (7, 8) => Some((1.40, 1.21, 1.06)), // N-O: hydroxylamine / nitroso / nitrosonium
+        _ => None,
+    }
+}
+
+/// Classify a candidate bond's order from its measured distance: whichever
+/// of the expected single/double/triple lengths for this element pair is
+/// closest, or the default single bond if the pair has none tabulated.
+fn classify_bond_order(elem_a: u8, elem_b: u8, dist: f32) -> u8 {
+    match expected_bond_lengths(elem_a, elem_b) {
+        Some((single, double, triple)) => {
+            let d_single = (dist - single).abs();
+            let d_double = (dist - double).abs();
+            let d_triple = (dist - triple).abs();
+            if d_triple <= d_double && d_triple <= d_single {
+                3
+            } else if d_double <= d_single {
+                2
+            } else {
+                1
+            }
+        }
+        None => 1,
+    }
+}
+
+/// Same distance-only candidate bonds as `compute_bonds`, but each bond's
+/// `order` is classified from its measured distance (via
+/// `classify_bond_order`) instead of being hard-coded to 1.
+pub fn compute_bonds_with_orders(atoms: &Atoms, tolerance: f32, max_distance: f32) -> Bonds {
+    let pairs = collect_candidate_bonds(atoms, tolerance, max_distance);
+    let mut bonds = Bonds::new();
+    bonds.atom1.reserve(pairs.len());
+    bonds.atom2.reserve(pairs.len());
+    bonds.order.reserve(pairs.len());
+
+    for (a, b, dist) in pairs {
+        let order = classify_bond_order(atoms.elements[a as usize], atoms.elements[b as usize], dist);
+        bonds.atom1.push(a);
+        bonds.atom2.push(b);
+        bonds.order.push(order);
+    }
+
+    bonds
+}
+
+/// Additive slack (Angstroms) added to the sum of two covalent radii by
+/// `perceive`. Looser than `compute_bonds`'s multiplicative tolerance since
+/// `perceive` is meant to fill in connectivity for formats (XYZ, GRO, ...)
+/// that carry no bonding hints at all, across a much wider range of element
+/// pairs and bond lengths than the crystallographic default is tuned for.
+const PERCEPTION_TOLERANCE: f32 = 0.45;
+
+/// Largest tabulated covalent radius, used to size `perceive`'s cell-list
+/// bins so that even the largest bondable pair in the table falls within
+/// the 3x3x3 neighbor block searched around each atom.
+fn max_covalent_radius() -> f32 {
+    COVALENT_RADII.iter().cloned().fold(0.0, f32::max)
+}
+
+/// Distance-based bond perception for formats (XYZ, GRO, ...) that carry
+/// coordinates but no connectivity of their own. Bins atoms into a uniform
+/// spatial grid (cell list) sized to the largest possible bonding cutoff, so
+/// each atom only tests candidates in its own cell and the 26 neighboring
+/// cells - O(N) instead of the O(N^2) all-pairs scan.
+///
+/// Two atoms are bonded when their separation is below
+/// `r_cov(e1) + r_cov(e2) + tolerance` (tolerance ~= 0.45 A by default).
+/// Atoms with unknown element (0) never bond. When `cell` is supplied,
+/// separations are measured with the minimum-image convention so bonds wrap
+/// across periodic boundaries. Each pair is emitted once with `order = 1`;
+/// bond order classification is `compute_bonds_with_orders`'s job, not this
+/// one.
+pub fn perceive(atoms: &Atoms, cell: Option<&UnitCell>) -> Bonds {
     let mut bonds = Bonds::new();
+    if atoms.is_empty() {
+        return bonds;
+    }
+
+    let cutoff = 2.0 * max_covalent_radius() + PERCEPTION_TOLERANCE;
+    match cell.and_then(|c| PeriodicBox::from_matrix(c.matrix)) {
+        Some(pbox) => perceive_periodic(atoms, &pbox, cutoff, &mut bonds),
+        None => perceive_aperiodic(atoms, cutoff, &mut bonds),
+    }
+    bonds
+}
+
+/// Non-periodic half of `perceive`: bins atoms by raw Cartesian position into
+/// cells of edge `cutoff`, same scheme as `collect_candidate_bonds`.
+fn perceive_aperiodic(atoms: &Atoms, cutoff: f32, bonds: &mut Bonds) {
+    let n = atoms.len();
+    let cell_size = cutoff.max(1e-3);
+
+    let mut min = [f32::INFINITY; 3];
+    for i in 0..n {
+        min[0] = min[0].min(atoms.x[i]);
+        min[1] = min[1].min(atoms.y[i]);
+        min[2] = min[2].min(atoms.z[i]);
+    }
+
+    let cell_of = |i: usize| -> (i32, i32, i32) {
+        (
+            ((atoms.x[i] - min[0]) / cell_size).floor() as i32,
+            ((atoms.y[i] - min[1]) / cell_size).floor() as i32,
+            ((atoms.z[i] - min[2]) / cell_size).floor() as i32,
+        )
+    };
+
+    let mut grid: HashMap<(i32, i32, i32), Vec<u32>> = HashMap::new();
+    for i in 0..n {
+        grid.entry(cell_of(i)).or_default().push(i as u32);
+    }
+
+    for i in 0..n {
+        let elem_i = atoms.elements[i];
+        if elem_i == 0 {
+            continue;
+        }
+        let r1 = covalent_radius(elem_i);
+        let (cx, cy, cz) = cell_of(i);
+
+        for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let candidates = match grid.get(&(cx + dx, cy + dy, cz + dz)) {
+                        Some(candidates) => candidates,
+                        None => continue,
+                    };
+
+                    for &j_u32 in candidates {
+                        let j = j_u32 as usize;
+                        if j <= i {
+                            continue;
+                        }
+                        let elem_j = atoms.elements[j];
+                        if elem_j == 0 {
+                            continue;
+                        }
+
+                        let ddx = atoms.x[j] - atoms.x[i];
+                        let ddy = atoms.y[j] - atoms.y[i];
+                        let ddz = atoms.z[j] - atoms.z[i];
+                        let dist = (ddx * ddx + ddy * ddy + ddz * ddz).sqrt();
+
+                        let r2 = covalent_radius(elem_j);
+                        if dist < r1 + r2 + PERCEPTION_TOLERANCE {
+                            bonds.atom1.push(i as u32);
+                            bonds.atom2.push(j as u32);
+                            bonds.order.push(1);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Periodic half of `perceive`: bins atoms by fractional coordinate (wrapped
+/// into the primary cell) into a grid of `nx * ny * nz` cells along the
+/// lattice directions, wrapping neighbor cell indices modulo the grid
+/// dimensions so pairs across a periodic face are still found. Falls back to
+/// a single cell along any direction shorter than `3 * cutoff`, so the
+/// 3x3x3 neighbor search degrades to an exhaustive scan along that axis
+/// instead of silently missing pairs.
+fn perceive_periodic(atoms: &Atoms, pbox: &PeriodicBox, cutoff: f32, bonds: &mut Bonds) {
+    let n = atoms.len();
+    let cell_size = cutoff.max(1e-3);
+    let (len_a, len_b, len_c, ..) = pbox.lengths_angles();
+
+    let grid_dim = |len: f32| -> i32 {
+        let raw = (len / cell_size).floor() as i32;
+        if raw >= 3 {
+            raw
+        } else {
+            1
+        }
+    };
+    let (nx, ny, nz) = (grid_dim(len_a), grid_dim(len_b), grid_dim(len_c));
+
+    let cell_of = |i: usize| -> (i32, i32, i32) {
+        let pos = [atoms.x[i], atoms.y[i], atoms.z[i]];
+        let f = pbox.cartesian_to_fractional(pos);
+        let wrap = |c: f32| c - c.floor();
+        (
+            ((wrap(f[0]) * nx as f32) as i32).min(nx - 1),
+            ((wrap(f[1]) * ny as f32) as i32).min(ny - 1),
+            ((wrap(f[2]) * nz as f32) as i32).min(nz - 1),
+        )
+    };
+
+    let mut grid: HashMap<(i32, i32, i32), Vec<u32>> = HashMap::new();
+    for i in 0..n {
+        grid.entry(cell_of(i)).or_default().push(i as u32);
+    }
+
+    for i in 0..n {
+        let elem_i = atoms.elements[i];
+        if elem_i == 0 {
+            continue;
+        }
+        let r1 = covalent_radius(elem_i);
+        let pos_i = [atoms.x[i], atoms.y[i], atoms.z[i]];
+        let (cx, cy, cz) = cell_of(i);
+
+        // When a grid dimension collapsed to 1 cell (box shorter than
+        // `3 * cutoff` along that axis), several `dx`/`dy`/`dz` offsets wrap
+        // to the same neighbor cell - dedup before visiting so that cell's
+        // candidates aren't rescanned (and the same bond re-emitted) once
+        // per offset that lands on it.
+        let mut visited_cells: HashSet<(i32, i32, i32)> = HashSet::with_capacity(27);
+
+        for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let neighbor_cell = (
+                        (cx + dx).rem_euclid(nx),
+                        (cy + dy).rem_euclid(ny),
+                        (cz + dz).rem_euclid(nz),
+                    );
+                    if !visited_cells.insert(neighbor_cell) {
+                        continue;
+                    }
+                    let candidates = match grid.get(&neighbor_cell) {
+                        Some(candidates) => candidates,
+                        None => continue,
+                    };
+
+                    for &j_u32 in candidates {
+                        let j = j_u32 as usize;
+                        if j <= i {
+                            continue;
+                        }
+                        let elem_j = atoms.elements[j];
+                        if elem_j == 0 {
+                            continue;
+                        }
+
+                        let pos_j = [atoms.x[j], atoms.y[j], atoms.z[j]];
+                        let delta = pbox.minimum_image(pos_j, pos_i);
+                        let dist = (delta[0] * delta[0] + delta[1] * delta[1] + delta[2] * delta[2]).sqrt();
 
-    // Estimate capacity (rough heuristic: ~2-3 bonds per atom on average)
+                        let r2 = covalent_radius(elem_j);
+                        if dist < r1 + r2 + PERCEPTION_TOLERANCE {
+                            bonds.atom1.push(i as u32);
+                            bonds.atom2.push(j as u32);
+                            bonds.order.push(1);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The original O(n^2) all-pairs scan, kept test-only as the reference
+/// implementation `test_cell_list_matches_brute_force` checks the cell-list
+/// path against.
+#[cfg(test)]
+fn compute_bonds_brute_force(atoms: &Atoms, tolerance: f32, max_distance: f32) -> Bonds {
+    let n = atoms.len();
+    let mut bonds = Bonds::new();
     bonds.atom1.reserve(n * 2);
     bonds.atom2.reserve(n * 2);
     bonds.order.reserve(n * 2);
 
-    // Simple O(n^2) algorithm
-    // TODO: Optimize with spatial hashing or cell lists for large systems
     for i in 0..n {
         let x1 = atoms.x[i];
         let y1 = atoms.y[i];
         let z1 = atoms.z[i];
-        let elem1 = atoms.elements[i];
-        let r1 = covalent_radius(elem1);
+        let r1 = covalent_radius(atoms.elements[i]);
 
         for j in (i + 1)..n {
             let x2 = atoms.x[j];
             let y2 = atoms.y[j];
             let z2 = atoms.z[j];
-            let elem2 = atoms.elements[j];
-            let r2 = covalent_radius(elem2);
+            let r2 = covalent_radius(atoms.elements[j]);
 
-            // Compute distance
             let dx = x2 - x1;
             let dy = y2 - y1;
             let dz = z2 - z1;
             let dist_sq = dx * dx + dy * dy + dz * dz;
 
-            // Quick rejection if too far
             if dist_sq > max_distance * max_distance {
                 continue;
             }
 
             let dist = dist_sq.sqrt();
-
-            // Bond threshold: sum of covalent radii * tolerance
             let bond_threshold = (r1 + r2) * tolerance;
 
             if dist < bond_threshold {
                 bonds.atom1.push(i as u32);
                 bonds.atom2.push(j as u32);
-                bonds.order.push(1); // Default to single bond
+                bonds.order.push(1);
             }
         }
     }
@@ -136,6 +638,22 @@ pub fn compute_bonds_default(atoms: &Atoms) -> Bonds {
     compute_bonds(atoms, 1.2, 3.0)
 }
 
+/// Build a per-atom adjacency list (neighbor index, bond order) from a flat
+/// `Bonds` list, since `Bonds` itself carries no per-atom connectivity.
+/// Shared by callers elsewhere in the crate (functional-group perception,
+/// substructure search) that need to walk the bond graph atom-by-atom.
+pub(crate) fn build_adjacency(atoms: &Atoms, bonds: &Bonds) -> Vec<Vec<(usize, u8)>> {
+    let mut adjacency = vec![Vec::new(); atoms.len()];
+    for i in 0..bonds.len() {
+        let a = bonds.atom1[i] as usize;
+        let b = bonds.atom2[i] as usize;
+        let order = bonds.order[i];
+        adjacency[a].push((b, order));
+        adjacency[b].push((a, order));
+    }
+    adjacency
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -201,4 +719,161 @@ mod tests {
             .any(|(&a1, &a2)| (a1 == 0 && a2 == 1) || (a1 == 1 && a2 == 0));
         assert!(has_cc_bond);
     }
+
+    #[test]
+    fn test_cell_list_matches_brute_force() {
+        // ~10k atoms on a regular grid spaced just under the bond threshold,
+        // so every atom bonds to its immediate neighbors - dense enough to
+        // exercise many occupied cells and many bonds spanning cell edges.
+        let mut atoms = Atoms::new();
+        let side = 22; // 22^3 = 10648 atoms
+        let spacing = 1.3; // just inside the C-C bond threshold (1.2 * 0.76 * 2 = 1.824)
+        for xi in 0..side {
+            for yi in 0..side {
+                for zi in 0..side {
+                    atoms.push(xi as f32 * spacing, yi as f32 * spacing, zi as f32 * spacing, 6); // C
+                }
+            }
+        }
+
+        let cell_list = compute_bonds(&atoms, 1.2, 3.0);
+        let brute_force = compute_bonds_brute_force(&atoms, 1.2, 3.0);
+
+        assert_eq!(cell_list.len(), brute_force.len());
+        assert_eq!(cell_list.atom1, brute_force.atom1);
+        assert_eq!(cell_list.atom2, brute_force.atom2);
+        assert_eq!(cell_list.order, brute_force.order);
+    }
+
+    #[test]
+    fn test_compute_bonds_valence_prunes_hypervalent_atom() {
+        // A hydrogen (max_bonds = 1) surrounded by four oxygens, all within
+        // distance-only bonding range at different distances - distance-only
+        // bonding should hang all four off the hydrogen, while valence
+        // pruning should keep only the closest and drop the three longer
+        // (and therefore weaker) candidate bonds.
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 1);    // H, index 0
+        atoms.push(0.9, 0.0, 0.0, 8);    // O, index 1 - closest
+        atoms.push(0.0, 0.95, 0.0, 8);   // O, index 2
+        atoms.push(0.0, 0.0, 1.0, 8);    // O, index 3 - farthest
+        atoms.push(-0.98, 0.0, 0.0, 8);  // O, index 4
+
+        let permissive = compute_bonds(&atoms, 1.2, 3.0);
+        let h_degree_permissive = permissive.atom1.iter().chain(permissive.atom2.iter())
+            .filter(|&&idx| idx == 0)
+            .count();
+        assert_eq!(h_degree_permissive, 4, "distance-only bonding should make H hypervalent here");
+
+        let valence = compute_bonds_valence(&atoms, 1.2, 3.0);
+        let h_bonds: Vec<(u32, u32)> = valence.atom1.iter().zip(valence.atom2.iter())
+            .filter(|(&a, &b)| a == 0 || b == 0)
+            .map(|(&a, &b)| (a, b))
+            .collect();
+
+        assert_eq!(h_bonds.len(), 1, "valence pruning should leave H with exactly one bond");
+        assert_eq!(h_bonds[0], (0, 1), "the closest oxygen (index 1) should be the bond that survives");
+    }
+
+    #[test]
+    fn test_classify_bond_order_ethane_cc_single() {
+        // Ethane-like C-C distance (~1.54 Å) should classify as a single bond.
+        assert_eq!(classify_bond_order(6, 6, 1.54), 1);
+    }
+
+    #[test]
+    fn test_classify_bond_order_ethene_cc_double() {
+        // Ethene-like C-C distance (~1.33 Å) should classify as a double bond.
+        assert_eq!(classify_bond_order(6, 6, 1.33), 2);
+    }
+
+    #[test]
+    fn test_classify_bond_order_carbonyl_co_double() {
+        // Carbonyl C=O distance (~1.23 Å) should classify as a double bond.
+        assert_eq!(classify_bond_order(6, 8, 1.23), 2);
+    }
+
+    #[test]
+    fn test_perceive_water_aperiodic() {
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 8);     // O
+        atoms.push(0.96, 0.0, 0.0, 1);    // H
+        atoms.push(-0.24, 0.93, 0.0, 1);  // H
+
+        let bonds = perceive(&atoms, None);
+
+        assert_eq!(bonds.len(), 2);
+        assert_eq!(bonds.atom1[0], 0);
+        assert_eq!(bonds.atom2[0], 1);
+        assert_eq!(bonds.atom1[1], 0);
+        assert_eq!(bonds.atom2[1], 2);
+        assert_eq!(bonds.order[0], 1);
+        assert_eq!(bonds.order[1], 1);
+    }
+
+    #[test]
+    fn test_perceive_skips_unknown_element() {
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 0);  // unknown element
+        atoms.push(0.9, 0.0, 0.0, 1);  // H, within bonding range of a real atom
+
+        let bonds = perceive(&atoms, None);
+
+        assert_eq!(bonds.len(), 0, "pairs involving unknown element 0 should never bond");
+    }
+
+    #[test]
+    fn test_perceive_wraps_bond_across_periodic_boundary() {
+        // Two atoms near opposite faces of a 10 A cubic box, separated by
+        // ~9.9 A directly but only ~0.9 A through the periodic image.
+        let mut atoms = Atoms::new();
+        atoms.push(0.05, 5.0, 5.0, 6);  // C near x=0 face
+        atoms.push(9.95, 5.0, 5.0, 6);  // C near x=10 face
+
+        let cell = UnitCell::from_vectors([10.0, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]);
+
+        assert_eq!(perceive(&atoms, None).len(), 0, "these atoms are far apart without periodicity");
+
+        let bonds = perceive(&atoms, Some(&cell));
+        assert_eq!(bonds.len(), 1, "minimum-image convention should find the wraparound bond");
+        assert_eq!((bonds.atom1[0], bonds.atom2[0]), (0, 1));
+    }
+
+    #[test]
+    fn test_perceive_empty_atoms() {
+        let atoms = Atoms::new();
+        assert_eq!(perceive(&atoms, None).len(), 0);
+    }
+
+    #[test]
+    fn test_compute_bonds_with_orders_pins_classifications() {
+        // Three independent molecules placed far apart so no cross-molecule
+        // candidate bonds appear: ethane (C-C single), an ethene-like C-C
+        // pair (double), and a carbonyl-like C=O pair (double).
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 6);    // 0: ethane C1
+        atoms.push(1.54, 0.0, 0.0, 6);   // 1: ethane C2
+
+        atoms.push(20.0, 0.0, 0.0, 6);   // 2: ethene-like C1
+        atoms.push(21.33, 0.0, 0.0, 6);  // 3: ethene-like C2
+
+        atoms.push(40.0, 0.0, 0.0, 6);   // 4: carbonyl C
+        atoms.push(41.23, 0.0, 0.0, 8);  // 5: carbonyl O
+
+        // `compute_bonds_with_orders`'s tolerance is multiplicative (see
+        // `compute_bonds_default`'s 1.2), not the additive `PERCEPTION_TOLERANCE`
+        // used by `perceive` - 1.2 comfortably covers all three pairs here.
+        let bonds = compute_bonds_with_orders(&atoms, 1.2, 2.0);
+
+        let order_of = |a: u32, b: u32| -> u8 {
+            bonds.atom1.iter().zip(bonds.atom2.iter()).zip(bonds.order.iter())
+                .find(|((&x, &y), _)| (x == a && y == b) || (x == b && y == a))
+                .map(|(_, &order)| order)
+                .expect("expected bond not found")
+        };
+
+        assert_eq!(order_of(0, 1), 1, "ethane C-C should classify as single");
+        assert_eq!(order_of(2, 3), 2, "ethene-like C-C should classify as double");
+        assert_eq!(order_of(4, 5), 2, "carbonyl C=O should classify as double");
+    }
 }
@@ -0,0 +1,443 @@
+// Functional-group perception: classifies atoms into chemically meaningful
+// groups by local bond-graph pattern matching (element + neighbor-element
+// multiset + bond order), for use by the `group <name>` selection keyword.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::atoms::{Atoms, Bonds};
+use crate::bonds::build_adjacency;
+
+const H: u8 = 1;
+const C: u8 = 6;
+const N: u8 = 7;
+const O: u8 = 8;
+const P: u8 = 15;
+const S: u8 = 16;
+
+fn add_member(groups: &mut HashMap<String, HashSet<usize>>, name: &str, idx: usize) {
+    groups.entry(name.to_string()).or_default().insert(idx);
+}
+
+/// Classify every atom in `atoms` into the functional groups it participates
+/// in, given the bond graph `bonds`. Returns a map from canonical group name
+/// (e.g. `"carboxyl"`, `"hydroxyl"`, `"amine_primary"`) to the set of member
+/// atom indices. An atom can belong to more than one group, e.g. an amide
+/// carbon is reported under both `"carbonyl"` and `"amide"`.
+pub fn classify_functional_groups(atoms: &Atoms, bonds: &Bonds) -> HashMap<String, HashSet<usize>> {
+    let adjacency = build_adjacency(atoms, bonds);
+    let mut groups: HashMap<String, HashSet<usize>> = HashMap::new();
+
+    for atom_idx in 0..atoms.len() {
+        match atoms.elements[atom_idx] {
+            C => classify_carbon(atoms, &adjacency, atom_idx, &mut groups),
+            N => classify_nitrogen(atoms, &adjacency, atom_idx, &mut groups),
+            O => classify_oxygen(atoms, &adjacency, atom_idx, &mut groups),
+            S => classify_sulfur(atoms, &adjacency, atom_idx, &mut groups),
+            P => classify_phosphorus(atoms, &adjacency, atom_idx, &mut groups),
+            _ => {}
+        }
+    }
+
+    classify_aromatic_rings(atoms, &adjacency, &mut groups);
+
+    groups
+}
+
+/// Carbon-centered groups: carbonyl, carboxyl/carboxylate, ester, amide.
+fn classify_carbon(
+    atoms: &Atoms,
+    adjacency: &[Vec<(usize, u8)>],
+    c_idx: usize,
+    groups: &mut HashMap<String, HashSet<usize>>,
+) {
+    let neighbors = &adjacency[c_idx];
+
+    let double_oxygens: Vec<usize> = neighbors.iter()
+        .filter(|&&(n, order)| atoms.elements[n] == O && order == 2)
+        .map(|&(n, _)| n)
+        .collect();
+    let single_oxygens: Vec<usize> = neighbors.iter()
+        .filter(|&&(n, order)| atoms.elements[n] == O && order == 1)
+        .map(|&(n, _)| n)
+        .collect();
+
+    // Lone C=O with no other oxygen attached: a plain carbonyl (ketone/aldehyde).
+    if double_oxygens.len() == 1 && single_oxygens.is_empty() {
+        add_member(groups, "carbonyl", c_idx);
+        add_member(groups, "carbonyl", double_oxygens[0]);
+    }
+
+    // C(=O)-O-: carboxylic acid (-OH), ester (-OR), or carboxylate (-O-).
+    if double_oxygens.len() == 1 && single_oxygens.len() == 1 {
+        let carbonyl_o = double_oxygens[0];
+        let hydroxyl_o = single_oxygens[0];
+        let o_neighbors = &adjacency[hydroxyl_o];
+        let has_h = o_neighbors.iter().any(|&(n, _)| atoms.elements[n] == H);
+        let other_carbon = o_neighbors.iter().any(|&(n, _)| n != c_idx && atoms.elements[n] == C);
+
+        let name = if has_h {
+            "carboxyl"
+        } else if other_carbon {
+            "ester"
+        } else {
+            "carboxylate"
+        };
+        add_member(groups, name, c_idx);
+        add_member(groups, name, carbonyl_o);
+        add_member(groups, name, hydroxyl_o);
+    }
+
+    // Resonance-delocalized carboxylate, where both C-O distances classify
+    // as double bonds rather than one single / one double.
+    if double_oxygens.len() == 2 && single_oxygens.is_empty() {
+        add_member(groups, "carboxylate", c_idx);
+        add_member(groups, "carboxylate", double_oxygens[0]);
+        add_member(groups, "carboxylate", double_oxygens[1]);
+    }
+
+    // Amide: a carbonyl carbon also singly bonded to a nitrogen.
+    if let Some(&carbonyl_o) = double_oxygens.first() {
+        for &(n, order) in neighbors {
+            if order == 1 && atoms.elements[n] == N {
+                add_member(groups, "amide", c_idx);
+                add_member(groups, "amide", carbonyl_o);
+                add_member(groups, "amide", n);
+            }
+        }
+    }
+}
+
+/// Amines, split by how many carbons the nitrogen is bonded to. Amide
+/// nitrogens (bonded to a carbonyl carbon) are excluded, since they are
+/// reported under `"amide"` instead.
+fn classify_nitrogen(
+    atoms: &Atoms,
+    adjacency: &[Vec<(usize, u8)>],
+    n_idx: usize,
+    groups: &mut HashMap<String, HashSet<usize>>,
+) {
+    let neighbors = &adjacency[n_idx];
+
+    // Only sp3-style amines: no double/triple bonds off this nitrogen.
+    if neighbors.iter().any(|&(_, order)| order != 1) {
+        return;
+    }
+
+    let is_amide_nitrogen = neighbors.iter().any(|&(n, _)| {
+        atoms.elements[n] == C
+            && adjacency[n].iter().any(|&(o, order)| atoms.elements[o] == O && order == 2)
+    });
+    if is_amide_nitrogen {
+        return;
+    }
+
+    let carbon_count = neighbors.iter().filter(|&&(n, _)| atoms.elements[n] == C).count();
+    match carbon_count {
+        1 => add_member(groups, "amine_primary", n_idx),
+        2 => add_member(groups, "amine_secondary", n_idx),
+        3 => add_member(groups, "amine_tertiary", n_idx),
+        _ => return,
+    }
+    add_member(groups, "amine", n_idx);
+}
+
+/// Hydroxyl oxygens: exactly one H and one C neighbor, both single bonds,
+/// and that carbon isn't a carbonyl carbon (which would make this the acid
+/// -OH of a carboxyl group instead of an alcohol).
+fn classify_oxygen(
+    atoms: &Atoms,
+    adjacency: &[Vec<(usize, u8)>],
+    o_idx: usize,
+    groups: &mut HashMap<String, HashSet<usize>>,
+) {
+    let neighbors = &adjacency[o_idx];
+    if neighbors.len() != 2 || neighbors.iter().any(|&(_, order)| order != 1) {
+        return;
+    }
+
+    let has_h = neighbors.iter().any(|&(n, _)| atoms.elements[n] == H);
+    let carbon = neighbors.iter().find(|&&(n, _)| atoms.elements[n] == C).map(|&(n, _)| n);
+
+    if let (true, Some(c_idx)) = (has_h, carbon) {
+        let carbon_is_carbonyl = adjacency[c_idx].iter()
+            .any(|&(n, order)| atoms.elements[n] == O && order == 2);
+        if !carbon_is_carbonyl {
+            add_member(groups, "hydroxyl", o_idx);
+        }
+    }
+}
+
+/// Thiols: sulfur bonded to exactly one H and one C, both single bonds.
+fn classify_sulfur(
+    atoms: &Atoms,
+    adjacency: &[Vec<(usize, u8)>],
+    s_idx: usize,
+    groups: &mut HashMap<String, HashSet<usize>>,
+) {
+    let neighbors = &adjacency[s_idx];
+    let is_thiol = neighbors.len() == 2
+        && neighbors.iter().all(|&(_, order)| order == 1)
+        && neighbors.iter().any(|&(n, _)| atoms.elements[n] == H)
+        && neighbors.iter().any(|&(n, _)| atoms.elements[n] == C);
+
+    if is_thiol {
+        add_member(groups, "thiol", s_idx);
+    }
+}
+
+/// Phosphates: phosphorus bonded to four oxygens, at least one via a
+/// double bond (the PO4 core of a phosphate ester or free phosphate).
+fn classify_phosphorus(
+    atoms: &Atoms,
+    adjacency: &[Vec<(usize, u8)>],
+    p_idx: usize,
+    groups: &mut HashMap<String, HashSet<usize>>,
+) {
+    let neighbors = &adjacency[p_idx];
+    let oxygens: Vec<usize> = neighbors.iter()
+        .filter(|&&(n, _)| atoms.elements[n] == O)
+        .map(|&(n, _)| n)
+        .collect();
+    let has_double_bond_oxygen = neighbors.iter()
+        .any(|&(n, order)| atoms.elements[n] == O && order == 2);
+
+    if oxygens.len() >= 4 && has_double_bond_oxygen {
+        add_member(groups, "phosphate", p_idx);
+        for &o_idx in &oxygens {
+            add_member(groups, "phosphate", o_idx);
+        }
+    }
+}
+
+/// Aromatic rings: 6-membered all-carbon cycles whose bond orders match one
+/// of the patterns `is_aromatic_ring` accepts.
+fn classify_aromatic_rings(
+    atoms: &Atoms,
+    adjacency: &[Vec<(usize, u8)>],
+    groups: &mut HashMap<String, HashSet<usize>>,
+) {
+    let mut ring_members = HashSet::new();
+
+    for start in 0..atoms.len() {
+        if atoms.elements[start] != C {
+            continue;
+        }
+        if let Some(ring) = find_six_membered_aromatic_ring(atoms, adjacency, start) {
+            ring_members.extend(ring);
+        }
+    }
+
+    if !ring_members.is_empty() {
+        groups.entry("aromatic_ring".to_string()).or_default().extend(ring_members);
+    }
+}
+
+fn find_six_membered_aromatic_ring(
+    atoms: &Atoms,
+    adjacency: &[Vec<(usize, u8)>],
+    start: usize,
+) -> Option<Vec<usize>> {
+    let mut path = vec![start];
+    let mut visited = HashSet::new();
+    visited.insert(start);
+    dfs_ring(atoms, adjacency, start, start, &mut path, &mut visited)
+}
+
+/// Tripos bond order for an explicit aromatic bond ("ar" in a MOL2 file) -
+/// duplicated from `parsers::mol2::AROMATIC_BOND_ORDER` rather than shared,
+/// since this module works purely in terms of the bond-order convention on
+/// `Bonds`/adjacency, not that parser's types.
+const AROMATIC_BOND_ORDER: u8 = 4;
+
+fn dfs_ring(
+    atoms: &Atoms,
+    adjacency: &[Vec<(usize, u8)>],
+    start: usize,
+    current: usize,
+    path: &mut Vec<usize>,
+    visited: &mut HashSet<usize>,
+) -> Option<Vec<usize>> {
+    if path.len() == 6 {
+        let closing_order = adjacency[current].iter()
+            .find(|&&(n, _)| n == start)
+            .map(|&(_, order)| order);
+
+        return match closing_order {
+            Some(order) if is_aromatic_ring(adjacency, path, order) => Some(path.clone()),
+            _ => None,
+        };
+    }
+
+    for &(next, order) in &adjacency[current] {
+        if atoms.elements[next] != C
+            || visited.contains(&next)
+            || !matches!(order, 1 | 2 | AROMATIC_BOND_ORDER)
+        {
+            continue;
+        }
+
+        path.push(next);
+        visited.insert(next);
+        if let Some(ring) = dfs_ring(atoms, adjacency, start, next, path, visited) {
+            return Some(ring);
+        }
+        path.pop();
+        visited.remove(&next);
+    }
+
+    None
+}
+
+/// A ring reads as aromatic under any of three bond-order patterns real
+/// inputs actually produce (wrapping around the ring, with `closing_order`
+/// as the last-to-first bond):
+/// - every bond explicitly marked aromatic (`AROMATIC_BOND_ORDER`, from a
+///   MOL2 `ar` bond type);
+/// - every bond uniformly double: a real aromatic C-C length (~1.39 Å) is
+///   closer to the tabulated double-bond length than single for every bond
+///   in the ring, so `classify_bond_order` assigns order 2 across the board
+///   rather than alternating;
+/// - a strict Kekulé single/double alternation, for inputs that do carry
+///   distinct per-bond orders.
+fn is_aromatic_ring(adjacency: &[Vec<(usize, u8)>], path: &[usize], closing_order: u8) -> bool {
+    let mut orders = Vec::with_capacity(path.len());
+    for i in 0..path.len() - 1 {
+        let (a, b) = (path[i], path[i + 1]);
+        match adjacency[a].iter().find(|&&(n, _)| n == b) {
+            Some(&(_, order)) => orders.push(order),
+            None => return false,
+        }
+    }
+    orders.push(closing_order);
+
+    orders.iter().all(|&o| o == AROMATIC_BOND_ORDER)
+        || orders.iter().all(|&o| o == 2)
+        || (orders.iter().all(|&o| o == 1 || o == 2)
+            && orders.windows(2).all(|w| w[0] != w[1])
+            && orders[0] != orders[orders.len() - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atoms::Atoms;
+    use crate::bonds::compute_bonds_with_orders;
+
+    fn acetic_acid() -> (Atoms, Bonds) {
+        // CH3-C(=O)-OH, roughly planar, bond lengths close to their
+        // canonical values so `compute_bonds_with_orders` classifies orders
+        // the way a real acetic acid structure would.
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 6);    // 0: CH3 carbon
+        atoms.push(1.54, 0.0, 0.0, 6);   // 1: carboxyl carbon
+        atoms.push(2.155, 1.065, 0.0, 8);  // 2: carbonyl O (C=O, ~1.23 Å)
+        atoms.push(2.22, -1.178, 0.0, 8);  // 3: hydroxyl O (C-O, ~1.36 Å)
+        atoms.push(3.19, -1.178, 0.0, 1);  // 4: acid H (O-H, ~0.97 Å)
+
+        let bonds = compute_bonds_with_orders(&atoms, 1.2, 2.0);
+        (atoms, bonds)
+    }
+
+    fn ethanol() -> (Atoms, Bonds) {
+        // CH3-CH2-OH
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 6);    // 0: CH3 carbon
+        atoms.push(1.54, 0.0, 0.0, 6);   // 1: CH2 carbon
+        atoms.push(2.43, 1.25, 0.0, 8);  // 2: hydroxyl O
+        atoms.push(3.40, 1.25, 0.0, 1);  // 3: hydroxyl H
+
+        let bonds = compute_bonds_with_orders(&atoms, 1.2, 2.0);
+        (atoms, bonds)
+    }
+
+    #[test]
+    fn test_acetic_acid_carboxyl() {
+        let (atoms, bonds) = acetic_acid();
+        let groups = classify_functional_groups(&atoms, &bonds);
+
+        let carboxyl = groups.get("carboxyl").expect("expected a carboxyl group");
+        assert!(carboxyl.contains(&1), "carboxyl carbon should be classified");
+        assert!(carboxyl.contains(&2), "carbonyl oxygen should be classified");
+        assert!(carboxyl.contains(&3), "hydroxyl oxygen should be classified");
+
+        // The acid -OH should not also be reported as a plain alcohol hydroxyl.
+        assert!(groups.get("hydroxyl").map_or(true, |h| !h.contains(&3)));
+    }
+
+    #[test]
+    fn test_ethanol_hydroxyl() {
+        let (atoms, bonds) = ethanol();
+        let groups = classify_functional_groups(&atoms, &bonds);
+
+        let hydroxyl = groups.get("hydroxyl").expect("expected a hydroxyl group");
+        assert!(hydroxyl.contains(&2), "the ethanol oxygen should be classified as hydroxyl");
+        assert!(!groups.contains_key("carboxyl"), "ethanol has no carboxyl group");
+    }
+
+    fn benzene_ring() -> (Atoms, Bonds) {
+        // A planar carbon hexagon with the real aromatic C-C bond length
+        // (~1.39 Å), which `compute_bonds_with_orders` classifies as order 2
+        // for every ring bond (1.39 Å sits closer to the tabulated C-C
+        // double-bond length than single) rather than alternating 1/2.
+        let mut atoms = Atoms::new();
+        let radius: f64 = 1.39 / (2.0 * (std::f64::consts::PI / 6.0).sin());
+        for i in 0..6 {
+            let theta = std::f64::consts::PI / 3.0 * i as f64;
+            atoms.push((radius * theta.cos()) as f32, (radius * theta.sin()) as f32, 0.0, 6);
+        }
+
+        let bonds = compute_bonds_with_orders(&atoms, 1.2, 2.0);
+        (atoms, bonds)
+    }
+
+    #[test]
+    fn test_benzene_ring_from_geometry_is_aromatic() {
+        let (atoms, bonds) = benzene_ring();
+        let groups = classify_functional_groups(&atoms, &bonds);
+
+        let ring = groups
+            .get("aromatic_ring")
+            .expect("a geometric benzene hexagon should be detected as aromatic");
+        for i in 0..6 {
+            assert!(ring.contains(&i), "atom {i} should be part of the aromatic ring");
+        }
+    }
+
+    #[test]
+    fn test_benzene_ring_from_mol2_ar_bonds_is_aromatic() {
+        use crate::parsers::mol2::parse_mol2_reader;
+        use std::io::Cursor;
+
+        let mol2 = "\
+@<TRIPOS>MOLECULE
+benzene
+ 6 6 1 0 0
+SMALL
+GASTEIGER
+
+@<TRIPOS>ATOM
+      1 C1         1.2131    0.7000    0.0000 C.ar    1  BEN1       -0.0620
+      2 C2         1.2131   -0.7000    0.0000 C.ar    1  BEN1       -0.0620
+      3 C3         0.0000   -1.4000    0.0000 C.ar    1  BEN1       -0.0620
+      4 C4        -1.2131   -0.7000    0.0000 C.ar    1  BEN1       -0.0620
+      5 C5        -1.2131    0.7000    0.0000 C.ar    1  BEN1       -0.0620
+      6 C6         0.0000    1.4000    0.0000 C.ar    1  BEN1       -0.0620
+@<TRIPOS>BOND
+     1    1    2   ar
+     2    2    3   ar
+     3    3    4   ar
+     4    4    5   ar
+     5    5    6   ar
+     6    6    1   ar
+";
+        let (atoms, bonds) = parse_mol2_reader(Cursor::new(mol2)).unwrap();
+        let groups = classify_functional_groups(&atoms, &bonds);
+
+        let ring = groups
+            .get("aromatic_ring")
+            .expect("explicit MOL2 'ar' bonds should be detected as aromatic");
+        for i in 0..6 {
+            assert!(ring.contains(&i), "atom {i} should be part of the aromatic ring");
+        }
+    }
+}
@@ -0,0 +1,6 @@
+// Chemistry-aware structure analysis, built on top of the connectivity
+// (`Bonds`) and geometry (`Atoms`) that the core data model already carries.
+
+pub mod functional_groups;
+
+pub use functional_groups::classify_functional_groups;
@@ -0,0 +1,79 @@
+// Toggleable debug visualization flags for the CPU renderer, modeled on
+// WebRender's debug flag bitmask. Each flag gates one overlay drawn directly
+// into the rendered `RgbaImage`, replacing the ad-hoc unconditional
+// `eprintln!`/`/tmp/axiom_*.log` diagnostics that used to run on every frame.
+
+use std::ops::{BitOr, BitOrAssign, BitAnd};
+
+/// A combinable set of renderer debug overlays. Build one with `|`, e.g.
+/// `DebugFlags::SHOW_BBOX | DebugFlags::PERF_OVERLAY`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct DebugFlags(u32);
+
+impl DebugFlags {
+    /// No overlays active (the default).
+    pub const NONE: DebugFlags = DebugFlags(0);
+    /// Draw wireframe boxes for every octree leaf node.
+    pub const SHOW_OCTREE_BOXES: DebugFlags = DebugFlags(1 << 0);
+    /// Outline atoms that survived frustum culling with a thin ring.
+    pub const SHOW_FRUSTUM_CULLING: DebugFlags = DebugFlags(1 << 1);
+    /// Tint each atom by its LOD level instead of its element color.
+    pub const COLOR_BY_LOD: DebugFlags = DebugFlags(1 << 2);
+    /// Replace atom shading with a grayscale view-space depth visualization.
+    pub const SHOW_DEPTH_BUFFER: DebugFlags = DebugFlags(1 << 3);
+    /// Draw a wireframe box around the full scene's bounding box.
+    pub const SHOW_BBOX: DebugFlags = DebugFlags(1 << 4);
+    /// Blit a performance panel (FPS / render-efficiency bars) sourced from `PerfSummary`.
+    pub const PERF_OVERLAY: DebugFlags = DebugFlags(1 << 5);
+    /// Emit the legacy `eprintln!`/`/tmp/axiom_*.log` diagnostic traces.
+    pub const VERBOSE_LOGGING: DebugFlags = DebugFlags(1 << 6);
+
+    /// Check whether every bit in `flag` is set.
+    pub fn contains(&self, flag: DebugFlags) -> bool {
+        self.0 & flag.0 == flag.0
+    }
+
+    /// True if no flags are set.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for DebugFlags {
+    type Output = DebugFlags;
+    fn bitor(self, rhs: DebugFlags) -> DebugFlags {
+        DebugFlags(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for DebugFlags {
+    fn bitor_assign(&mut self, rhs: DebugFlags) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for DebugFlags {
+    type Output = DebugFlags;
+    fn bitand(self, rhs: DebugFlags) -> DebugFlags {
+        DebugFlags(self.0 & rhs.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains_and_combination() {
+        let flags = DebugFlags::SHOW_BBOX | DebugFlags::PERF_OVERLAY;
+        assert!(flags.contains(DebugFlags::SHOW_BBOX));
+        assert!(flags.contains(DebugFlags::PERF_OVERLAY));
+        assert!(!flags.contains(DebugFlags::SHOW_OCTREE_BOXES));
+    }
+
+    #[test]
+    fn test_none_is_empty() {
+        assert!(DebugFlags::NONE.is_empty());
+        assert!(!DebugFlags::SHOW_BBOX.is_empty());
+    }
+}
@@ -0,0 +1,230 @@
+// The periodic table - single source of truth for symbol <-> atomic number
+// mapping, shared by every parser/selection module that previously carried
+// its own partial copy (the CIF parser stopped around zinc and silently
+// mapped unknowns to 0; the selection evaluator stopped at krypton and
+// errored instead, missing common inorganic/organometallic elements).
+//
+// Covers every naturally occurring element, H (1) through U (92).
+
+/// Element symbols indexed by atomic number - 1 (`ELEMENTS[0]` is H, element 1).
+const ELEMENTS: [&str; 92] = [
+    "H", "HE", "LI", "BE", "B", "C", "N", "O", "F", "NE", "NA", "MG", "AL", "SI", "P", "S", "CL",
+    "AR", "K", "CA", "SC", "TI", "V", "CR", "MN", "FE", "CO", "NI", "CU", "ZN", "GA", "GE", "AS",
+    "SE", "BR", "KR", "RB", "SR", "Y", "ZR", "NB", "MO", "TC", "RU", "RH", "PD", "AG", "CD", "IN",
+    "SN", "SB", "TE", "I", "XE", "CS", "BA", "LA", "CE", "PR", "ND", "PM", "SM", "EU", "GD", "TB",
+    "DY", "HO", "ER", "TM", "YB", "LU", "HF", "TA", "W", "RE", "OS", "IR", "PT", "AU", "HG", "TL",
+    "PB", "BI", "PO", "AT", "RN", "FR", "RA", "AC", "TH", "PA", "U",
+];
+
+/// Convert an element symbol to its atomic number, case-insensitively.
+/// Only accepts one- or two-character candidates (real element symbols are
+/// never longer), and for two characters tries the full two-letter symbol
+/// before falling back to the first letter alone - so "CL"/"Cl" resolve to
+/// chlorine (17) and "CO" resolves to cobalt (27), not carbon (6) with a
+/// dangling second letter.
+pub fn symbol_to_atomic_number(symbol: &str) -> Option<u8> {
+    let upper: String = symbol.trim().chars().map(|c| c.to_ascii_uppercase()).collect();
+
+    match upper.len() {
+        1 => ELEMENTS.iter().position(|&s| s == upper.as_str()).map(|pos| pos as u8 + 1),
+        2 => ELEMENTS
+            .iter()
+            .position(|&s| s == upper.as_str())
+            .or_else(|| ELEMENTS.iter().position(|&s| s == &upper[..1]))
+            .map(|pos| pos as u8 + 1),
+        _ => None,
+    }
+}
+
+/// Convert an atomic number back to its element symbol (uppercase, e.g.
+/// "FE"). Returns `None` for 0 or any number past uranium (92).
+pub fn atomic_number_to_symbol(atomic_number: u8) -> Option<&'static str> {
+    ELEMENTS.get(atomic_number.checked_sub(1)? as usize).copied()
+}
+
+/// Two-letter ion/metal symbols safe to recognize in formats (like GRO)
+/// that carry no PDB-style column convention to tell a two-letter element
+/// apart from a two-letter organic atom name. Deliberately excludes
+/// symbols that collide with common biomolecular naming - "CA" (alpha
+/// carbon), "HG" (gamma hydrogen), "HE" (epsilon hydrogen) - which are
+/// element symbols too (calcium, mercury, helium) but are ambiguous
+/// without column information.
+const UNAMBIGUOUS_TWO_LETTER_IONS: [&str; 6] = ["CL", "BR", "FE", "ZN", "MG", "MN"];
+
+/// Infer an element symbol from an atom-name token that carries no
+/// PDB-style column convention (e.g. a GRO atom name, already trimmed of
+/// field padding). A leading digit is the hydrogen-numbering convention
+/// (e.g. "1HB2") and is skipped; the remaining two-letter prefix is only
+/// trusted as an element when it's in the unambiguous ion allow-list
+/// above, otherwise the first letter is the element. `symbol_to_atomic_number`
+/// is the single source of truth for whether a candidate is a real symbol.
+pub fn element_from_atom_name_token(token: &str) -> Option<u8> {
+    let token = token.trim();
+    if token.is_empty() {
+        return None;
+    }
+
+    let start = if token.as_bytes()[0].is_ascii_digit() { 1 } else { 0 };
+    let rest = token.get(start..)?;
+    if rest.is_empty() {
+        return None;
+    }
+
+    if rest.len() >= 2 {
+        let prefix = rest[..2].to_ascii_uppercase();
+        if UNAMBIGUOUS_TWO_LETTER_IONS.contains(&prefix.as_str()) {
+            return symbol_to_atomic_number(&prefix);
+        }
+    }
+
+    symbol_to_atomic_number(&rest[0..1])
+}
+
+/// Standard atomic weights (g/mol), indexed by atomic number - 1, aligned
+/// with `ELEMENTS`. Used by `atomic_mass` for selection queries like
+/// `mass > 12` (a field `Atoms` has no room to store per-atom, since it's
+/// fully determined by the element).
+const ATOMIC_MASSES: [f32; 92] = [
+    1.008, 4.003, 6.94, 9.012, 10.81, 12.011, 14.007, 15.999, 18.998, 20.180, // H-Ne
+    22.990, 24.305, 26.982, 28.085, 30.974, 32.06, 35.45, 39.948, 39.098, 40.078, // Na-Ca
+    44.956, 47.867, 50.942, 51.996, 54.938, 55.845, 58.933, 58.693, 63.546, 65.38, // Sc-Zn
+    69.723, 72.630, 74.922, 78.971, 79.904, 83.798, 85.468, 87.62, 88.906, 91.224, // Ga-Zr
+    92.906, 95.95, 98.0, 101.07, 102.906, 106.42, 107.868, 112.414, 114.818, 118.710, // Nb-Sn
+    121.760, 127.60, 126.904, 131.293, 132.905, 137.327, 138.905, 140.116, 140.908, 144.242, // Sb-Nd
+    145.0, 150.36, 151.964, 157.25, 158.925, 162.500, 164.930, 167.259, 168.934, 173.045, // Pm-Yb
+    174.967, 178.49, 180.948, 183.84, 186.207, 190.23, 192.217, 195.084, 196.967, 200.592, // Lu-Hg
+    204.38, 207.2, 208.980, 209.0, 210.0, 222.0, 223.0, 226.0, 227.0, 232.038, // Tl-Th
+    231.036, 238.029, // Pa-U
+];
+
+/// Standard atomic weight (g/mol) for an atomic number; 0.0 for unknown
+/// element (0) or anything past uranium (92).
+pub fn atomic_mass(atomic_number: u8) -> f32 {
+    match atomic_number.checked_sub(1) {
+        Some(idx) => ATOMIC_MASSES.get(idx as usize).copied().unwrap_or(0.0),
+        None => 0.0,
+    }
+}
+
+/// Reverse lookup: the atomic number whose standard atomic weight is
+/// closest to `mass`, as long as that closest match is within `tolerance`
+/// amu. Used by force-field formats (LAMMPS, PSF) that only give a per-type
+/// mass and expect the caller to infer the element. Returns `None` if
+/// nothing in the table is within tolerance.
+pub fn atomic_number_from_mass(mass: f32, tolerance: f32) -> Option<u8> {
+    ATOMIC_MASSES
+        .iter()
+        .enumerate()
+        .map(|(idx, &candidate)| (idx as u8 + 1, (candidate - mass).abs()))
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+        .filter(|&(_, diff)| diff <= tolerance)
+        .map(|(atomic_number, _)| atomic_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atomic_mass_common_elements() {
+        assert_eq!(atomic_mass(1), 1.008);
+        assert_eq!(atomic_mass(6), 12.011);
+        assert_eq!(atomic_mass(8), 15.999);
+        assert_eq!(atomic_mass(26), 55.845);
+    }
+
+    #[test]
+    fn test_atomic_mass_unknown_element() {
+        assert_eq!(atomic_mass(0), 0.0);
+        assert_eq!(atomic_mass(255), 0.0);
+    }
+
+    #[test]
+    fn test_atomic_number_from_mass_common_elements() {
+        assert_eq!(atomic_number_from_mass(12.011, 0.5), Some(6)); // carbon
+        assert_eq!(atomic_number_from_mass(15.999, 0.5), Some(8)); // oxygen
+        assert_eq!(atomic_number_from_mass(1.0, 0.5), Some(1)); // hydrogen
+    }
+
+    #[test]
+    fn test_atomic_number_from_mass_outside_tolerance_is_none() {
+        // Nothing in the table is within 0.5 amu of a 50.0-amu bead.
+        assert_eq!(atomic_number_from_mass(50.0, 0.5), None);
+    }
+
+    #[test]
+    fn test_symbol_to_atomic_number_common_elements() {
+        assert_eq!(symbol_to_atomic_number("H"), Some(1));
+        assert_eq!(symbol_to_atomic_number("C"), Some(6));
+        assert_eq!(symbol_to_atomic_number("O"), Some(8));
+        assert_eq!(symbol_to_atomic_number("Fe"), Some(26));
+        assert_eq!(symbol_to_atomic_number("ca"), Some(20)); // case insensitivity
+    }
+
+    #[test]
+    fn test_symbol_to_atomic_number_covers_previously_missing_elements() {
+        // Missing from the old CIF table
+        assert_eq!(symbol_to_atomic_number("Sc"), Some(21));
+        assert_eq!(symbol_to_atomic_number("V"), Some(23));
+        assert_eq!(symbol_to_atomic_number("Cr"), Some(24));
+        assert_eq!(symbol_to_atomic_number("Mn"), Some(25));
+        assert_eq!(symbol_to_atomic_number("Co"), Some(27));
+        assert_eq!(symbol_to_atomic_number("Ni"), Some(28));
+        assert_eq!(symbol_to_atomic_number("Ga"), Some(31));
+
+        // Missing from the old selection evaluator table (stopped at Kr)
+        assert_eq!(symbol_to_atomic_number("Ag"), Some(47));
+        assert_eq!(symbol_to_atomic_number("Au"), Some(79));
+        assert_eq!(symbol_to_atomic_number("U"), Some(92));
+    }
+
+    #[test]
+    fn test_symbol_to_atomic_number_unknown_returns_none() {
+        assert_eq!(symbol_to_atomic_number("Xx"), None);
+        assert_eq!(symbol_to_atomic_number(""), None);
+    }
+
+    #[test]
+    fn test_atomic_number_to_symbol_round_trips() {
+        assert_eq!(atomic_number_to_symbol(1), Some("H"));
+        assert_eq!(atomic_number_to_symbol(26), Some("FE"));
+        assert_eq!(atomic_number_to_symbol(92), Some("U"));
+        assert_eq!(atomic_number_to_symbol(0), None);
+        assert_eq!(atomic_number_to_symbol(255), None);
+    }
+
+    #[test]
+    fn test_two_letter_matched_before_single_letter() {
+        // "Cl" must resolve to chlorine (17), not carbon (6)
+        assert_eq!(symbol_to_atomic_number("Cl"), Some(17));
+        // "Co" must resolve to cobalt (27), not carbon (6)
+        assert_eq!(symbol_to_atomic_number("Co"), Some(27));
+    }
+
+    #[test]
+    fn test_element_from_atom_name_token_unambiguous_ions() {
+        assert_eq!(element_from_atom_name_token("CL"), Some(17));
+        assert_eq!(element_from_atom_name_token("MG"), Some(12));
+        assert_eq!(element_from_atom_name_token("BR"), Some(35));
+    }
+
+    #[test]
+    fn test_element_from_atom_name_token_ambiguous_falls_back_to_first_letter() {
+        // "CA" is ambiguous without column info (alpha carbon vs calcium);
+        // the allow-list keeps it resolving to carbon, not calcium.
+        assert_eq!(element_from_atom_name_token("CA"), Some(6));
+        assert_eq!(element_from_atom_name_token("HG"), Some(1));
+    }
+
+    #[test]
+    fn test_element_from_atom_name_token_skips_leading_digit() {
+        assert_eq!(element_from_atom_name_token("1HB2"), Some(1));
+        assert_eq!(element_from_atom_name_token("2HG1"), Some(1));
+    }
+
+    #[test]
+    fn test_element_from_atom_name_token_empty() {
+        assert_eq!(element_from_atom_name_token(""), None);
+        assert_eq!(element_from_atom_name_token("9"), None);
+    }
+}
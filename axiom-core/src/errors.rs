@@ -29,6 +29,9 @@ pub enum AxiomError {
 
     #[error("Selection error: {0}")]
     SelectionError(String),
+
+    #[error("{0}")]
+    SelectionSyntaxError(crate::selection::parser::SelectionDiagnostic),
 }
 
 pub type Result<T> = std::result::Result<T, AxiomError>;
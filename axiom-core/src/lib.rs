@@ -3,23 +3,49 @@
 
 pub mod atoms;
 pub mod bonds;
+pub mod elements;     // Single authoritative periodic table (symbol <-> atomic number)
 pub mod colors;
 pub mod errors;
 pub mod parsers;
+pub mod writers;     // Structure/coordinate writers (GRO, PDB, G96) - the inverse of parsers
 pub mod renderer;      // GPU renderer (wgpu) - currently non-functional on ARM server
 pub mod renderer_cpu;  // CPU renderer (software rasterization) - ACTIVE
 pub mod selection;     // Semantic selection parser for agent-native queries
 pub mod octree;        // Spatial indexing for large structures
 pub mod lod;           // Level of Detail rendering system
+pub mod light_clusters; // Clustered (froxel) point-light assignment for cheap multi-light shading
+pub mod debug_flags;   // Toggleable renderer debug overlays (octree/bbox/LOD/depth/perf)
 pub mod perf_metrics;  // Performance tracking and monitoring
+pub mod trajectory;    // Multi-frame trajectory support (multi-MODEL PDB, multi-frame GRO)
+pub mod chemistry;     // Functional-group perception over the Atoms + Bonds graph
+pub mod substructure;  // VF2 subgraph isomorphism ("needle in haystack") over the bond graph
+#[cfg(feature = "wasm")]
+pub mod wasm;          // wasm-bindgen entry points so parsers can run client-side in a browser
 
 // Re-exports (use CPU renderer by default)
 pub use atoms::{Atoms, Bonds, UnitCell};
-pub use bonds::{compute_bonds, compute_bonds_default};
+pub use atoms::gpu::{GpuAtoms, pack_positions_vec4, upload_packed_positions};
+pub use bonds::{compute_bonds, compute_bonds_default, compute_bonds_valence, compute_bonds_with_orders, perceive};
+pub use elements::{symbol_to_atomic_number, atomic_number_to_symbol, atomic_mass};
 pub use colors::{element_to_cpk_color, element_to_vdw_radius, element_to_ball_stick_radius};
 pub use errors::{AxiomError, Result};
-pub use renderer_cpu::{Renderer, RendererConfig, BackgroundColor};  // CPU renderer is now default
-pub use selection::{select, parse_selection, evaluate_selection, SelectionAST};
+pub use renderer_cpu::{Renderer, RendererConfig, BackgroundColor, Projection, Light, ReconstructionFilter, TileRect};  // CPU renderer is now default
+pub use renderer::CameraController;  // Orbit camera controller for the windowed GPU viewer
+pub use renderer::GpuDiagnostics;  // wgpu validation/OOM diagnostics from the (ARM-non-functional) GPU renderer
+pub use selection::{
+    select, select_with_bonds, parse_selection, parse_selection_all, evaluate_selection, evaluate_selection_for_frame,
+    evaluate_selection_with_groups, evaluate_selection_with_bonds, evaluate_selection_with_context,
+    SelectionAST, SelectionDiagnostic,
+};
 pub use octree::{Octree, AABB, OctreeStats};
-pub use lod::{LODLevel, LODConfig, LODStats};
-pub use perf_metrics::{PerformanceTracker, PerfSummary, FrameMetrics};
+pub use lod::{LODLevel, LODConfig, LODStats, LODHysteresis};
+pub use light_clusters::LightClusters;
+pub use debug_flags::DebugFlags;
+pub use perf_metrics::{
+    PerformanceTracker, PerfSummary, FrameMetrics, MetricsLogger, Counter, CounterMode,
+    CounterDisplay, parse_counter_layout, WindowStats, Ratio,
+};
+pub use trajectory::{Trajectory, TrajectoryFrame, FrameReader};
+pub use writers::{write_gro, write_pdb, write_g96};
+pub use chemistry::classify_functional_groups;
+pub use substructure::{find_substructures, contains_substructure, WILDCARD_ELEMENT};
@@ -0,0 +1,271 @@
+// Clustered (froxel) light assignment, so shading hundreds of point lights
+// stays cheap.
+//
+// The view frustum is subdivided into a 3D grid: a fixed screen-space tile
+// grid crossed with depth slices distributed exponentially along view-space
+// Z, following Persson's "Practical Clustered Shading" (`z_slice = near *
+// (far/near)^(k/num_slices)`). Each point light is assigned to the tiles it
+// overlaps *per depth slice* rather than one conservative AABB over its
+// whole depth range, since a sphere's screen-space footprint shrinks away
+// from its equator.
+
+use crate::renderer_cpu::Light;
+
+/// Number of horizontal tiles in the cluster grid.
+pub const TILES_X: usize = 16;
+/// Number of vertical tiles in the cluster grid.
+pub const TILES_Y: usize = 9;
+/// Number of exponential depth slices in the cluster grid.
+pub const NUM_SLICES: usize = 16;
+
+/// Per-cluster lists of point-light indices (into the `lights` slice passed to `build`).
+pub struct LightClusters {
+    near: f32,
+    far: f32,
+    width: f32,
+    height: f32,
+    clusters: Vec<Vec<usize>>, // len == TILES_X * TILES_Y * NUM_SLICES
+}
+
+impl LightClusters {
+    /// View-space depth of the boundary between slice `k-1` and slice `k`.
+    fn slice_depth(near: f32, far: f32, k: usize) -> f32 {
+        near * (far / near).powf(k as f32 / NUM_SLICES as f32)
+    }
+
+    /// Inverse of `slice_depth`: which slice a given view-space depth falls into.
+    fn slice_for_depth(near: f32, far: f32, depth: f32) -> usize {
+        let d = depth.clamp(near, far);
+        let k = NUM_SLICES as f32 * (d / near).ln() / (far / near).ln();
+        (k.floor() as usize).min(NUM_SLICES - 1)
+    }
+
+    fn cluster_index(tx: usize, ty: usize, slice: usize) -> usize {
+        (slice * TILES_Y + ty) * TILES_X + tx
+    }
+
+    /// Build per-cluster point-light index lists.
+    ///
+    /// `view`/`proj` are the same column-major matrices used to project
+    /// atoms (`Renderer::build_view_matrix`/`build_projection_matrix`), so a
+    /// light's world-space radius maps to the same screen-space footprint
+    /// atoms use, under whichever projection (perspective or orthographic)
+    /// is active.
+    pub fn build(
+        lights: &[Light],
+        view: [[f32; 4]; 4],
+        proj: [[f32; 4]; 4],
+        near: f32,
+        far: f32,
+        width: f32,
+        height: f32,
+    ) -> Self {
+        let mut clusters = vec![Vec::new(); TILES_X * TILES_Y * NUM_SLICES];
+
+        let project = |view_pos: [f32; 3]| -> Option<[f32; 2]> {
+            let clip_x = proj[0][0] * view_pos[0] + proj[1][0] * view_pos[1] + proj[2][0] * view_pos[2] + proj[3][0];
+            let clip_y = proj[0][1] * view_pos[0] + proj[1][1] * view_pos[1] + proj[2][1] * view_pos[2] + proj[3][1];
+            let clip_w = proj[0][3] * view_pos[0] + proj[1][3] * view_pos[1] + proj[2][3] * view_pos[2] + proj[3][3];
+            if clip_w.abs() < 1e-6 {
+                return None;
+            }
+            Some([
+                (clip_x / clip_w + 1.0) * 0.5 * width,
+                (1.0 - clip_y / clip_w) * 0.5 * height,
+            ])
+        };
+
+        for (light_idx, light) in lights.iter().enumerate() {
+            let (position, radius) = match light {
+                Light::Point { position, radius, .. } => (*position, *radius),
+                Light::Directional { .. } => continue, // lights everything - not clustered
+            };
+
+            let view_pos = [
+                view[0][0] * position[0] + view[1][0] * position[1] + view[2][0] * position[2] + view[3][0],
+                view[0][1] * position[0] + view[1][1] * position[1] + view[2][1] * position[2] + view[3][1],
+                view[0][2] * position[0] + view[1][2] * position[1] + view[2][2] * position[2] + view[3][2],
+            ];
+            let view_depth = -view_pos[2];
+
+            let light_near = (view_depth - radius).max(near);
+            let light_far = (view_depth + radius).min(far);
+            if light_far <= near || light_near >= far {
+                continue; // entirely outside the frustum's depth range
+            }
+
+            let slice_start = Self::slice_for_depth(near, far, light_near);
+            let slice_end = Self::slice_for_depth(near, far, light_far);
+
+            for slice in slice_start..=slice_end {
+                // Iterative sphere refinement: recompute the footprint at
+                // this slice's depth instead of reusing one AABB for the
+                // whole range the light spans.
+                let slice_depth = Self::slice_depth(near, far, slice).clamp(near, far);
+                let dz = slice_depth - view_depth;
+                let cross_section_sq = radius * radius - dz * dz;
+                if cross_section_sq <= 0.0 {
+                    continue; // sphere doesn't reach this slice
+                }
+                let cross_radius = cross_section_sq.sqrt();
+
+                let center_view = [view_pos[0], view_pos[1], -slice_depth];
+                let edge_view = [view_pos[0] + cross_radius, view_pos[1], -slice_depth];
+
+                let center_screen = match project(center_view) {
+                    Some(s) => s,
+                    None => continue,
+                };
+                let edge_screen = match project(edge_view) {
+                    Some(s) => s,
+                    None => continue,
+                };
+                let screen_radius = (edge_screen[0] - center_screen[0]).abs();
+
+                let min_tx = ((center_screen[0] - screen_radius) / width * TILES_X as f32)
+                    .floor()
+                    .max(0.0) as usize;
+                let max_tx = (((center_screen[0] + screen_radius) / width * TILES_X as f32).floor().max(0.0)
+                    as usize)
+                    .min(TILES_X - 1);
+                let min_ty = ((center_screen[1] - screen_radius) / height * TILES_Y as f32)
+                    .floor()
+                    .max(0.0) as usize;
+                let max_ty = (((center_screen[1] + screen_radius) / height * TILES_Y as f32).floor().max(0.0)
+                    as usize)
+                    .min(TILES_Y - 1);
+
+                let min_tx = min_tx.min(TILES_X - 1);
+                let min_ty = min_ty.min(TILES_Y - 1);
+
+                for ty in min_ty..=max_ty.max(min_ty) {
+                    for tx in min_tx..=max_tx.max(min_tx) {
+                        clusters[Self::cluster_index(tx, ty, slice)].push(light_idx);
+                    }
+                }
+            }
+        }
+
+        Self { near, far, width, height, clusters }
+    }
+
+    /// Look up the point-light indices affecting a screen position at a given view-space depth.
+    pub fn lights_at(&self, screen_x: f32, screen_y: f32, view_depth: f32) -> &[usize] {
+        let tx = ((screen_x / self.width * TILES_X as f32).max(0.0) as usize).min(TILES_X - 1);
+        let ty = ((screen_y / self.height * TILES_Y as f32).max(0.0) as usize).min(TILES_Y - 1);
+        let slice = Self::slice_for_depth(self.near, self.far, view_depth);
+        &self.clusters[Self::cluster_index(tx, ty, slice)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity_view() -> [[f32; 4]; 4] {
+        [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]
+    }
+
+    fn perspective_proj(width: f32, height: f32, near: f32, far: f32) -> [[f32; 4]; 4] {
+        let aspect = width / height;
+        let fov_y = 45.0_f32.to_radians();
+        let f = 1.0 / (fov_y / 2.0).tan();
+        [
+            [f / aspect, 0.0, 0.0, 0.0],
+            [0.0, f, 0.0, 0.0],
+            [0.0, 0.0, (far + near) / (near - far), -1.0],
+            [0.0, 0.0, (2.0 * far * near) / (near - far), 0.0],
+        ]
+    }
+
+    #[test]
+    fn test_point_light_assigned_near_its_own_cluster() {
+        let near = 0.1;
+        let far = 1000.0;
+        let width = 1920.0;
+        let height = 1080.0;
+
+        // A point light sitting directly in front of the camera (-Z in view space).
+        let lights = vec![Light::Point {
+            position: [0.0, 0.0, -20.0],
+            color: [1.0, 1.0, 1.0],
+            intensity: 1.0,
+            radius: 5.0,
+        }];
+
+        let clusters = LightClusters::build(
+            &lights,
+            identity_view(),
+            perspective_proj(width, height, near, far),
+            near,
+            far,
+            width,
+            height,
+        );
+
+        // The screen center, at the light's own depth, should see this light.
+        let hits = clusters.lights_at(width / 2.0, height / 2.0, 20.0);
+        assert_eq!(hits, &[0]);
+    }
+
+    #[test]
+    fn test_point_light_absent_far_outside_its_radius() {
+        let near = 0.1;
+        let far = 1000.0;
+        let width = 1920.0;
+        let height = 1080.0;
+
+        let lights = vec![Light::Point {
+            position: [0.0, 0.0, -20.0],
+            color: [1.0, 1.0, 1.0],
+            intensity: 1.0,
+            radius: 5.0,
+        }];
+
+        let clusters = LightClusters::build(
+            &lights,
+            identity_view(),
+            perspective_proj(width, height, near, far),
+            near,
+            far,
+            width,
+            height,
+        );
+
+        // Far beyond the light's depth range, nothing should be assigned.
+        let hits = clusters.lights_at(width / 2.0, height / 2.0, 500.0);
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn test_directional_lights_are_never_clustered() {
+        let near = 0.1;
+        let far = 1000.0;
+        let width = 1920.0;
+        let height = 1080.0;
+
+        let lights = vec![Light::Directional {
+            direction: [0.0, 0.0, 1.0],
+            color: [1.0, 1.0, 1.0],
+            intensity: 1.0,
+        }];
+
+        let clusters = LightClusters::build(
+            &lights,
+            identity_view(),
+            perspective_proj(width, height, near, far),
+            near,
+            far,
+            width,
+            height,
+        );
+
+        let hits = clusters.lights_at(width / 2.0, height / 2.0, 20.0);
+        assert!(hits.is_empty());
+    }
+}
@@ -46,6 +46,18 @@ pub struct LODConfig {
     pub medium_threshold: f32,  // < this = Medium
     pub low_threshold: f32,     // < this = Low
     // >= low_threshold = Minimal
+    /// Projected-pixel-radius thresholds for `get_lod_level_screenspace`.
+    /// Unlike the world-space distance thresholds above, these stay stable
+    /// across camera zoom/FOV changes since they key off how large the atom
+    /// actually appears on screen.
+    pub high_px_threshold: f32,    // > this = High
+    pub medium_px_threshold: f32,  // > this = Medium
+    pub low_px_threshold: f32,     // > this = Low
+    // <= low_px_threshold = Minimal
+    /// Distance dead zone, on each side of `high_threshold`/`medium_threshold`/
+    /// `low_threshold`, that `get_lod_level_stable` requires a promotion or
+    /// demotion to clear before it takes effect - see `get_lod_level_stable`.
+    pub hysteresis_band: f32,
 }
 
 impl Default for LODConfig {
@@ -56,6 +68,11 @@ impl Default for LODConfig {
             medium_threshold: 60.0,   // Medium detail 30-60 units
             low_threshold: 100.0,     // Low detail 60-100 units
             // Minimal beyond 100 units
+            high_px_threshold: 12.0,   // High detail when the atom spans > 12px
+            medium_px_threshold: 5.0,  // Medium detail when > 5px
+            low_px_threshold: 1.5,     // Low detail when > 1.5px
+            // Minimal at or below 1.5px
+            hysteresis_band: 5.0,      // +/- 5 world units around each threshold
         }
     }
 }
@@ -78,6 +95,46 @@ impl LODConfig {
         }
     }
 
+    /// Determine LOD level from the atom's projected size on screen rather
+    /// than raw world-space distance, so the same structure doesn't pop
+    /// between LOD levels differently as the camera zooms or the FOV
+    /// changes, and a huge atom up close still downgrades once it's no
+    /// longer contributing detail per pixel.
+    ///
+    /// `projected_px` is the atom's projected pixel radius: the world
+    /// radius scaled by distance and by how many pixels one unit of
+    /// view-space height maps to at that distance, derived from the
+    /// standard perspective-projection pixel-per-unit factor
+    /// `viewport_height_px / (2 * tan(fov_y / 2))`.
+    pub fn get_lod_level_screenspace(
+        &self,
+        distance: f32,
+        atom_radius: f32,
+        fov_y_rad: f32,
+        viewport_height_px: f32,
+    ) -> LODLevel {
+        if !self.enabled {
+            return LODLevel::High;
+        }
+
+        if distance <= 0.0 {
+            return LODLevel::High;
+        }
+
+        let pixels_per_unit = viewport_height_px / (2.0 * (fov_y_rad * 0.5).tan());
+        let projected_px = atom_radius / distance * pixels_per_unit;
+
+        if projected_px > self.high_px_threshold {
+            LODLevel::High
+        } else if projected_px > self.medium_px_threshold {
+            LODLevel::Medium
+        } else if projected_px > self.low_px_threshold {
+            LODLevel::Low
+        } else {
+            LODLevel::Minimal
+        }
+    }
+
     /// Calculate distance from camera to atom
     pub fn calculate_distance(camera_pos: [f32; 3], atom_pos: [f32; 3]) -> f32 {
         let dx = atom_pos[0] - camera_pos[0];
@@ -85,6 +142,103 @@ impl LODConfig {
         let dz = atom_pos[2] - camera_pos[2];
         (dx * dx + dy * dy + dz * dz).sqrt()
     }
+
+    /// Rank levels by detail, most detailed first, so promotion/demotion can
+    /// be decided by comparing ranks rather than matching enum variants.
+    fn level_rank(level: LODLevel) -> u8 {
+        match level {
+            LODLevel::High => 0,
+            LODLevel::Medium => 1,
+            LODLevel::Low => 2,
+            LODLevel::Minimal => 3,
+        }
+    }
+
+    /// The distance threshold `level` must drop below (minus the hysteresis
+    /// band) to promote to the next more-detailed level, or `None` if
+    /// `level` is already the most detailed (`High`).
+    fn promotion_boundary(&self, level: LODLevel) -> Option<f32> {
+        match level {
+            LODLevel::High => None,
+            LODLevel::Medium => Some(self.high_threshold),
+            LODLevel::Low => Some(self.medium_threshold),
+            LODLevel::Minimal => Some(self.low_threshold),
+        }
+    }
+
+    /// The distance threshold `level` must rise above (plus the hysteresis
+    /// band) to demote to the next less-detailed level, or `None` if
+    /// `level` is already the least detailed (`Minimal`).
+    fn demotion_boundary(&self, level: LODLevel) -> Option<f32> {
+        match level {
+            LODLevel::High => Some(self.high_threshold),
+            LODLevel::Medium => Some(self.medium_threshold),
+            LODLevel::Low => Some(self.low_threshold),
+            LODLevel::Minimal => None,
+        }
+    }
+
+    /// Distance-based LOD selection with temporal hysteresis: computes the
+    /// raw level `get_lod_level` would pick, then only actually changes from
+    /// `prev` if the distance has moved comfortably past the relevant
+    /// threshold - below `threshold - hysteresis_band` to promote to more
+    /// detail, above `threshold + hysteresis_band` to demote to less.
+    /// Otherwise `prev` is kept, damping the frame-to-frame flicker an atom
+    /// sitting right at a threshold would otherwise show.
+    pub fn get_lod_level_stable(&self, distance: f32, prev: LODLevel) -> LODLevel {
+        if !self.enabled {
+            return LODLevel::High;
+        }
+
+        let raw = self.get_lod_level(distance);
+        if raw == prev {
+            return prev;
+        }
+
+        if Self::level_rank(raw) < Self::level_rank(prev) {
+            match self.promotion_boundary(prev) {
+                Some(boundary) if distance < boundary - self.hysteresis_band => raw,
+                _ => prev,
+            }
+        } else {
+            match self.demotion_boundary(prev) {
+                Some(boundary) if distance > boundary + self.hysteresis_band => raw,
+                _ => prev,
+            }
+        }
+    }
+}
+
+/// Per-atom memory of the last `LODLevel` chosen, consumed by
+/// `LODConfig::get_lod_level_stable` to damp frame-to-frame threshold
+/// flicker. Indexed by atom id (position in the `Atoms` arrays) rather than
+/// a map, since atom count and ordering stay fixed within a frame.
+#[derive(Debug, Clone, Default)]
+pub struct LODHysteresis {
+    levels: Vec<LODLevel>,
+}
+
+impl LODHysteresis {
+    /// Create hysteresis state for `atom_count` atoms, every atom starting
+    /// at `LODLevel::High` until its first `update` call.
+    pub fn new(atom_count: usize) -> Self {
+        Self { levels: vec![LODLevel::High; atom_count] }
+    }
+
+    /// Resize to match a changed atom count, preserving existing per-atom
+    /// state where indices still line up and defaulting new slots to High.
+    pub fn resize(&mut self, atom_count: usize) {
+        self.levels.resize(atom_count, LODLevel::High);
+    }
+
+    /// Compute the stable LOD level for `atom_id` at `distance` using
+    /// `config`'s hysteresis band, and remember the result for next call.
+    pub fn update(&mut self, config: &LODConfig, atom_id: usize, distance: f32) -> LODLevel {
+        let prev = self.levels[atom_id];
+        let level = config.get_lod_level_stable(distance, prev);
+        self.levels[atom_id] = level;
+        level
+    }
 }
 
 /// LOD statistics for performance monitoring
@@ -157,4 +311,81 @@ mod tests {
         assert_eq!(config.get_lod_level(10.0), LODLevel::High);
         assert_eq!(config.get_lod_level(1000.0), LODLevel::High);
     }
+
+    #[test]
+    fn test_lod_screenspace_selection() {
+        let config = LODConfig::default();
+        let fov_y_rad = std::f32::consts::FRAC_PI_2; // 90 degrees
+        let viewport_height_px = 1000.0;
+
+        // Large atom very close: big projected radius -> High
+        assert_eq!(
+            config.get_lod_level_screenspace(1.0, 1.0, fov_y_rad, viewport_height_px),
+            LODLevel::High
+        );
+
+        // Same atom, far away: tiny projected radius -> Minimal
+        assert_eq!(
+            config.get_lod_level_screenspace(2000.0, 1.0, fov_y_rad, viewport_height_px),
+            LODLevel::Minimal
+        );
+    }
+
+    #[test]
+    fn test_lod_screenspace_stable_across_zoom() {
+        // A huge near atom and a tiny distant one can project to the same
+        // pixel size - screenspace LOD should treat them identically, unlike
+        // world-space distance thresholds which would not.
+        let config = LODConfig::default();
+        let fov_y_rad = std::f32::consts::FRAC_PI_2;
+        let viewport_height_px = 1000.0;
+
+        let near = config.get_lod_level_screenspace(10.0, 1.0, fov_y_rad, viewport_height_px);
+        let far = config.get_lod_level_screenspace(100.0, 10.0, fov_y_rad, viewport_height_px);
+        assert_eq!(near, far);
+    }
+
+    #[test]
+    fn test_lod_screenspace_disabled() {
+        let config = LODConfig {
+            enabled: false,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.get_lod_level_screenspace(1.0, 1.0, std::f32::consts::FRAC_PI_2, 1000.0),
+            LODLevel::High
+        );
+    }
+
+    #[test]
+    fn test_lod_stable_damps_flicker_at_threshold() {
+        // high_threshold = 30.0, hysteresis_band = 5.0 by default: an atom
+        // hovering just past the raw threshold shouldn't demote yet.
+        let config = LODConfig::default();
+        assert_eq!(config.get_lod_level_stable(32.0, LODLevel::High), LODLevel::High);
+        // Comfortably past threshold + band -> demotes.
+        assert_eq!(config.get_lod_level_stable(36.0, LODLevel::High), LODLevel::Medium);
+    }
+
+    #[test]
+    fn test_lod_stable_promotes_once_past_band() {
+        let config = LODConfig::default();
+        // Just inside threshold but within the band -> stays Medium.
+        assert_eq!(config.get_lod_level_stable(28.0, LODLevel::Medium), LODLevel::Medium);
+        // Comfortably below threshold - band -> promotes to High.
+        assert_eq!(config.get_lod_level_stable(24.0, LODLevel::Medium), LODLevel::High);
+    }
+
+    #[test]
+    fn test_lod_hysteresis_tracks_per_atom_state() {
+        let config = LODConfig::default();
+        let mut hysteresis = LODHysteresis::new(2);
+
+        assert_eq!(hysteresis.update(&config, 0, 10.0), LODLevel::High);
+        assert_eq!(hysteresis.update(&config, 1, 150.0), LODLevel::Minimal);
+
+        // Atom 0 drifts just past high_threshold, but still within the band.
+        assert_eq!(hysteresis.update(&config, 0, 32.0), LODLevel::High);
+    }
 }
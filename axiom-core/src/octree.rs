@@ -2,6 +2,9 @@
 // Enables fast queries for frustum culling, LOD selection, and neighbor searches
 
 use crate::atoms::Atoms;
+use crate::colors::element_to_ball_stick_radius;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 
 /// Axis-aligned bounding box
 #[derive(Clone, Debug)]
@@ -58,6 +61,63 @@ impl AABB {
         true
     }
 
+    /// Check if ray (origin + normalized direction) intersects AABB (slab method)
+    pub fn intersects_ray(&self, origin: [f32; 3], direction: [f32; 3]) -> bool {
+        let mut t_min = f32::NEG_INFINITY;
+        let mut t_max = f32::INFINITY;
+
+        for axis in 0..3 {
+            if direction[axis].abs() < 1e-12 {
+                // Ray is parallel to this axis's slab - must already be inside it
+                if origin[axis] < self.min[axis] || origin[axis] > self.max[axis] {
+                    return false;
+                }
+            } else {
+                let inv_d = 1.0 / direction[axis];
+                let mut t0 = (self.min[axis] - origin[axis]) * inv_d;
+                let mut t1 = (self.max[axis] - origin[axis]) * inv_d;
+                if t0 > t1 {
+                    std::mem::swap(&mut t0, &mut t1);
+                }
+                t_min = t_min.max(t0);
+                t_max = t_max.min(t1);
+                if t_min > t_max {
+                    return false;
+                }
+            }
+        }
+
+        t_max >= 0.0 // box is not entirely behind the ray origin
+    }
+
+    /// Slab test returning the entry/exit ray parameters `(tmin, tmax)`
+    /// instead of a bool, so callers can sort children near-to-far and prune
+    /// subtrees that can't beat a hit already found. `inv_dir` is
+    /// `1.0 / direction` per axis, precomputed once per ray by the caller.
+    pub fn intersect_ray(&self, origin: [f32; 3], inv_dir: [f32; 3]) -> Option<(f32, f32)> {
+        let mut t_min = f32::NEG_INFINITY;
+        let mut t_max = f32::INFINITY;
+
+        for axis in 0..3 {
+            let mut t0 = (self.min[axis] - origin[axis]) * inv_dir[axis];
+            let mut t1 = (self.max[axis] - origin[axis]) * inv_dir[axis];
+            if t0 > t1 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            t_min = t_min.max(t0);
+            t_max = t_max.min(t1);
+            if t_min > t_max {
+                return None;
+            }
+        }
+
+        if t_max < 0.0 {
+            return None; // box is entirely behind the ray origin
+        }
+
+        Some((t_min, t_max))
+    }
+
     /// Get center of AABB
     pub fn center(&self) -> [f32; 3] {
         [
@@ -74,13 +134,251 @@ impl AABB {
         let dz = self.max[2] - self.min[2];
         dx.max(dy).max(dz)
     }
+
+    /// Surface area of the box, for `Octree::build_sah`'s split cost
+    /// (`SA(left) * count(left) + SA(right) * count(right)`); negative
+    /// extents (an inverted/degenerate box) are clamped to zero rather than
+    /// flipping the sign of a term they're multiplied into.
+    fn surface_area(&self) -> f32 {
+        let dx = (self.max[0] - self.min[0]).max(0.0);
+        let dy = (self.max[1] - self.min[1]).max(0.0);
+        let dz = (self.max[2] - self.min[2]).max(0.0);
+        2.0 * (dx * dy + dy * dz + dz * dx)
+    }
+
+    /// Squared distance from `point` to the closest point on this box: clamp
+    /// `point` into the box per axis and measure to the clamped point - the
+    /// same clamp-and-measure approach `intersects_sphere` uses, reused here
+    /// as the priority key for `query_knn`/`query_radius`'s best-first node
+    /// search (zero when `point` is inside the box).
+    fn min_dist_sq(&self, point: [f32; 3]) -> f32 {
+        let cx = point[0].clamp(self.min[0], self.max[0]);
+        let cy = point[1].clamp(self.min[1], self.max[1]);
+        let cz = point[2].clamp(self.min[2], self.max[2]);
+        let dx = point[0] - cx;
+        let dy = point[1] - cy;
+        let dz = point[2] - cz;
+        dx * dx + dy * dy + dz * dz
+    }
+}
+
+/// Struct-of-arrays box bounds for `cull_frustum_batch`'s branchless,
+/// auto-vectorizable frustum test: a flat, contiguous layout (rather than a
+/// `Vec<AABB>`) so the per-plane arithmetic below runs as straight-line f32
+/// loops over slices instead of per-node method calls on scattered structs.
+#[derive(Clone, Debug, Default)]
+pub struct AABBSoA {
+    pub min_x: Vec<f32>,
+    pub min_y: Vec<f32>,
+    pub min_z: Vec<f32>,
+    pub max_x: Vec<f32>,
+    pub max_y: Vec<f32>,
+    pub max_z: Vec<f32>,
+}
+
+impl AABBSoA {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build from a slice of `AABB`s (e.g. `Octree::leaf_bounds`).
+    pub fn from_bounds(bounds: &[AABB]) -> Self {
+        let mut soa = Self::new();
+        for b in bounds {
+            soa.push(b);
+        }
+        soa
+    }
+
+    pub fn push(&mut self, bounds: &AABB) {
+        self.min_x.push(bounds.min[0]);
+        self.min_y.push(bounds.min[1]);
+        self.min_z.push(bounds.min[2]);
+        self.max_x.push(bounds.max[0]);
+        self.max_y.push(bounds.max[1]);
+        self.max_z.push(bounds.max[2]);
+    }
+
+    pub fn len(&self) -> usize {
+        self.min_x.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min_x.is_empty()
+    }
+}
+
+/// Branchless select: a `1.0`/`0.0` mask multiply in place of an `if`, so a
+/// loop built out of this stays straight-line arithmetic the compiler can
+/// auto-vectorize instead of a per-element conditional branch.
+#[inline]
+fn select(cond: bool, a: f32, b: f32) -> f32 {
+    let mask = (cond as i32) as f32;
+    mask * a + (1.0 - mask) * b
+}
+
+/// Batched, branchless frustum test over a struct-of-arrays box layout -
+/// the SIMD-friendly counterpart to calling `AABB::intersects_frustum` once
+/// per node. For each of the 6 frustum planes, every box's "positive
+/// vertex" (the corner farthest along the plane normal) is picked with
+/// `select` instead of `intersects_frustum`'s per-axis `if`, and an
+/// "outside" flag accumulates per box with no early `return` inside the
+/// per-box work - keeping the inner loop straight-line f32 arithmetic over
+/// contiguous slices rather than branching per element.
+///
+/// Writes `true` into `out_mask[i]` for every box still inside the frustum
+/// after all 6 planes, `false` for any box found outside on any plane.
+/// `out_mask` must be at least `soa.len()` long; entries beyond that are
+/// left untouched.
+pub fn cull_frustum_batch(frustum_planes: &[[f32; 4]; 6], soa: &AABBSoA, out_mask: &mut [bool]) {
+    let n = soa.len();
+    let mut outside = vec![false; n];
+
+    for plane in frustum_planes {
+        let (a, b, c, d) = (plane[0], plane[1], plane[2], plane[3]);
+        let a_pos = a >= 0.0;
+        let b_pos = b >= 0.0;
+        let c_pos = c >= 0.0;
+
+        for i in 0..n {
+            let px = select(a_pos, soa.max_x[i], soa.min_x[i]);
+            let py = select(b_pos, soa.max_y[i], soa.min_y[i]);
+            let pz = select(c_pos, soa.max_z[i], soa.min_z[i]);
+            let signed_dist = a * px + b * py + c * pz + d;
+            outside[i] = outside[i] || signed_dist < 0.0;
+        }
+    }
+
+    for i in 0..n {
+        out_mask[i] = !outside[i];
+    }
+}
+
+/// Total-ordering wrapper around squared distances (always finite,
+/// non-negative) so they can key a `BinaryHeap`, which requires `Ord` and
+/// `f32` only implements `PartialOrd`. Compares via `partial_cmp().unwrap()`,
+/// consistent with how the rest of the crate orders floats (see
+/// `bonds.rs`/`elements.rs`).
+#[derive(Clone, Copy, PartialEq)]
+struct OrderedDist(f32);
+
+impl Eq for OrderedDist {}
+
+impl PartialOrd for OrderedDist {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedDist {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.partial_cmp(&other.0).unwrap()
+    }
+}
+
+/// Pairs an `OrderedDist` key with a node reference for `query_knn`/
+/// `query_radius`'s node heap. `OctreeNode` itself has no `Ord`/`Eq` (it owns
+/// a `Vec`/`Box<[OctreeNode]>`, for which a total order wouldn't mean
+/// anything), so ordering is keyed on `dist` alone and `node` just comes
+/// along for the ride.
+struct HeapNode<'a> {
+    dist: OrderedDist,
+    node: &'a OctreeNode,
+}
+
+impl PartialEq for HeapNode<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.dist == other.dist
+    }
+}
+
+impl Eq for HeapNode<'_> {}
+
+impl PartialOrd for HeapNode<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapNode<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.dist.cmp(&other.dist)
+    }
+}
+
+/// Ray-sphere intersection: solves `|o + t*d - center|^2 = r^2` for `t`
+/// (with `d` assumed normalized) and returns the smaller non-negative root,
+/// or `None` if the ray misses the sphere or the sphere is entirely behind it.
+fn ray_sphere_intersect(origin: [f32; 3], direction: [f32; 3], center: [f32; 3], radius: f32) -> Option<f32> {
+    let oc = [origin[0] - center[0], origin[1] - center[1], origin[2] - center[2]];
+    let b = oc[0] * direction[0] + oc[1] * direction[1] + oc[2] * direction[2];
+    let c = oc[0] * oc[0] + oc[1] * oc[1] + oc[2] * oc[2] - radius * radius;
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let sqrt_disc = discriminant.sqrt();
+    let t_near = -b - sqrt_disc;
+    let t_far = -b + sqrt_disc;
+    let t = if t_near >= 0.0 { t_near } else { t_far };
+
+    if t < 0.0 {
+        None
+    } else {
+        Some(t)
+    }
+}
+
+/// Coordinate of an atom along `axis` (0 = x, 1 = y, 2 = z), used by
+/// `OctreeNode::build_sah_node` to sort candidate split positions.
+fn atom_coord(atoms: &Atoms, idx: usize, axis: usize) -> f32 {
+    match axis {
+        0 => atoms.x[idx],
+        1 => atoms.y[idx],
+        _ => atoms.z[idx],
+    }
+}
+
+/// An atom's position as `[x, y, z]`.
+fn atom_pos(atoms: &Atoms, idx: usize) -> [f32; 3] {
+    [atoms.x[idx], atoms.y[idx], atoms.z[idx]]
+}
+
+/// Extend `bounds` to include `point`.
+fn union_point(mut bounds: AABB, point: [f32; 3]) -> AABB {
+    for axis in 0..3 {
+        bounds.min[axis] = bounds.min[axis].min(point[axis]);
+        bounds.max[axis] = bounds.max[axis].max(point[axis]);
+    }
+    bounds
+}
+
+/// Tight bounding box over `indices`' atoms, with `Octree::build`'s small
+/// margin so atoms sitting exactly on a face still test as contained.
+fn tight_bounds(atoms: &Atoms, indices: &[usize]) -> AABB {
+    let mut bounds = AABB::new([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]);
+    for &idx in indices {
+        bounds = union_point(bounds, atom_pos(atoms, idx));
+    }
+
+    let margin = 0.1;
+    for axis in 0..3 {
+        bounds.min[axis] -= margin;
+        bounds.max[axis] += margin;
+    }
+    bounds
 }
 
 /// Octree node for spatial partitioning
 pub struct OctreeNode {
     pub bounds: AABB,
     pub atom_indices: Vec<usize>,  // Atoms in this node (leaf nodes only)
-    pub children: Option<Box<[OctreeNode; 8]>>,  // 8 octants
+    // Variable arity: 8 octants under `Octree::build`'s fixed subdivision,
+    // 2 children under `Octree::build_sah`'s binary SAH splits. A boxed
+    // slice rather than a fixed-size array so both builders can share the
+    // same node type (and therefore the same query methods).
+    pub children: Option<Box<[OctreeNode]>>,
     pub depth: u32,
 }
 
@@ -148,16 +446,89 @@ impl OctreeNode {
             children.push(child);
         }
 
-        // Convert Vec to fixed-size array
-        self.children = Some(Box::new([
-            children.remove(0), children.remove(0), children.remove(0), children.remove(0),
-            children.remove(0), children.remove(0), children.remove(0), children.remove(0),
-        ]));
+        self.children = Some(children.into_boxed_slice());
 
         // Clear atom indices from internal node (only leaves store atoms)
         self.atom_indices.clear();
     }
 
+    /// Recursive SAH binary-split builder backing `Octree::build_sah`. Picks
+    /// whichever axis+position minimizes `SA(left)*count(left) +
+    /// SA(right)*count(right)` (the surface-area heuristic) by sorting
+    /// candidate split positions along each axis and sweeping prefix/suffix
+    /// bounding boxes in O(n) after the sort, falling back to a leaf once
+    /// the best split cost doesn't beat the no-split cost or the node is
+    /// already at `max_leaf` atoms or fewer.
+    fn build_sah_node(atoms: &Atoms, bounds: AABB, mut indices: Vec<usize>, depth: u32, max_leaf: usize) -> OctreeNode {
+        let n = indices.len();
+        if n <= max_leaf || n <= 1 {
+            return OctreeNode::new_leaf(bounds, indices, depth);
+        }
+
+        let no_split_cost = bounds.surface_area() * n as f32;
+        let mut best: Option<(usize, usize, f32)> = None; // (axis, split count, cost)
+
+        for axis in 0..3 {
+            indices.sort_by(|&a, &b| {
+                atom_coord(atoms, a, axis).partial_cmp(&atom_coord(atoms, b, axis)).unwrap()
+            });
+
+            // Prefix bounds (ascending) give the left child's box for a
+            // split right after each position; suffix surface areas
+            // (descending) give the right child's box for the same split.
+            let mut prefix_bounds: Vec<AABB> = Vec::with_capacity(n);
+            let mut running = AABB::new([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]);
+            for &idx in indices.iter() {
+                running = union_point(running, atom_pos(atoms, idx));
+                prefix_bounds.push(running.clone());
+            }
+
+            let mut suffix_sa = vec![0.0f32; n + 1];
+            let mut running = AABB::new([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]);
+            for i in (0..n).rev() {
+                running = union_point(running, atom_pos(atoms, indices[i]));
+                suffix_sa[i] = running.surface_area();
+            }
+
+            for split in 1..n {
+                let left_sa = prefix_bounds[split - 1].surface_area();
+                let right_sa = suffix_sa[split];
+                let cost = left_sa * split as f32 + right_sa * (n - split) as f32;
+                if best.map_or(true, |(_, _, best_cost)| cost < best_cost) {
+                    best = Some((axis, split, cost));
+                }
+            }
+        }
+
+        let (best_axis, best_split, best_cost) = match best {
+            Some(b) => b,
+            None => return OctreeNode::new_leaf(bounds, indices, depth),
+        };
+
+        if best_cost >= no_split_cost {
+            return OctreeNode::new_leaf(bounds, indices, depth);
+        }
+
+        indices.sort_by(|&a, &b| {
+            atom_coord(atoms, a, best_axis).partial_cmp(&atom_coord(atoms, b, best_axis)).unwrap()
+        });
+        let right_indices = indices.split_off(best_split);
+        let left_indices = indices;
+
+        let left_bounds = tight_bounds(atoms, &left_indices);
+        let right_bounds = tight_bounds(atoms, &right_indices);
+
+        let left_child = OctreeNode::build_sah_node(atoms, left_bounds, left_indices, depth + 1, max_leaf);
+        let right_child = OctreeNode::build_sah_node(atoms, right_bounds, right_indices, depth + 1, max_leaf);
+
+        OctreeNode {
+            bounds,
+            atom_indices: Vec::new(),
+            children: Some(vec![left_child, right_child].into_boxed_slice()),
+            depth,
+        }
+    }
+
     /// Query atoms within frustum (recursive)
     pub fn query_frustum(&self, frustum_planes: &[[f32; 4]; 6], result: &mut Vec<usize>) {
         // Check if node intersects frustum
@@ -192,6 +563,158 @@ impl OctreeNode {
         }
     }
 
+    /// Query atoms along a ray (for hover/click picking)
+    pub fn query_ray(&self, origin: [f32; 3], direction: [f32; 3], result: &mut Vec<usize>) {
+        if !self.bounds.intersects_ray(origin, direction) {
+            return;
+        }
+
+        if self.is_leaf() {
+            result.extend_from_slice(&self.atom_indices);
+        } else if let Some(ref children) = self.children {
+            for child in children.iter() {
+                child.query_ray(origin, direction, result);
+            }
+        }
+    }
+
+    /// Recursive near-to-far ray cast: visits children in order of their
+    /// entry `tmin` and prunes any child whose `tmin` is already past the
+    /// best hit found so far, instead of gathering every AABB candidate
+    /// up front like `query_ray` does. At leaves, ray-sphere tests each
+    /// atom (ball-stick radius) and keeps the smallest positive root.
+    fn query_ray_nearest(
+        &self,
+        origin: [f32; 3],
+        direction: [f32; 3],
+        inv_dir: [f32; 3],
+        atoms: &Atoms,
+        best: &mut Option<(usize, f32)>,
+    ) {
+        let tmin = match self.bounds.intersect_ray(origin, inv_dir) {
+            Some((tmin, _tmax)) => tmin,
+            None => return,
+        };
+        if let Some((_, best_t)) = best {
+            if tmin > *best_t {
+                return; // nothing in this subtree can beat the current hit
+            }
+        }
+
+        if self.is_leaf() {
+            for &atom_idx in &self.atom_indices {
+                let center = [atoms.x[atom_idx], atoms.y[atom_idx], atoms.z[atom_idx]];
+                let radius = element_to_ball_stick_radius(atoms.elements[atom_idx]);
+                if let Some(t) = ray_sphere_intersect(origin, direction, center, radius) {
+                    if best.map(|(_, best_t)| t < best_t).unwrap_or(true) {
+                        *best = Some((atom_idx, t));
+                    }
+                }
+            }
+        } else if let Some(ref children) = self.children {
+            let mut ordered: Vec<(f32, &OctreeNode)> = children
+                .iter()
+                .filter_map(|child| child.bounds.intersect_ray(origin, inv_dir).map(|(t, _)| (t, child)))
+                .collect();
+            ordered.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            for (_, child) in ordered {
+                child.query_ray_nearest(origin, direction, inv_dir, atoms, best);
+            }
+        }
+    }
+
+    /// Recompute this node's `AABB` bottom-up from the atoms actually
+    /// assigned to it, without touching topology (leaf membership, children
+    /// layout stay exactly as `subdivide` left them): a leaf's bounds become
+    /// the tight box over `atom_indices` (with `build`'s small margin so
+    /// atoms sitting on a face still test as contained), and an internal
+    /// node's bounds become the union of its (already-refit) children.
+    /// Returns the new bounds so each level folds its children's results
+    /// into its own.
+    fn refit_bounds(&mut self, atoms: &Atoms) -> AABB {
+        if self.is_leaf() {
+            if self.atom_indices.is_empty() {
+                // Nothing assigned to this leaf - keep its existing bounds
+                // rather than collapsing to a degenerate/inverted box.
+                return self.bounds.clone();
+            }
+
+            let mut min = [f32::INFINITY; 3];
+            let mut max = [f32::NEG_INFINITY; 3];
+            for &atom_idx in &self.atom_indices {
+                min[0] = min[0].min(atoms.x[atom_idx]);
+                min[1] = min[1].min(atoms.y[atom_idx]);
+                min[2] = min[2].min(atoms.z[atom_idx]);
+                max[0] = max[0].max(atoms.x[atom_idx]);
+                max[1] = max[1].max(atoms.y[atom_idx]);
+                max[2] = max[2].max(atoms.z[atom_idx]);
+            }
+
+            let margin = 0.1;
+            for axis in 0..3 {
+                min[axis] -= margin;
+                max[axis] += margin;
+            }
+
+            self.bounds = AABB::new(min, max);
+        } else if let Some(ref mut children) = self.children {
+            let mut min = [f32::INFINITY; 3];
+            let mut max = [f32::NEG_INFINITY; 3];
+            for child in children.iter_mut() {
+                let child_bounds = child.refit_bounds(atoms);
+                min[0] = min[0].min(child_bounds.min[0]);
+                min[1] = min[1].min(child_bounds.min[1]);
+                min[2] = min[2].min(child_bounds.min[2]);
+                max[0] = max[0].max(child_bounds.max[0]);
+                max[1] = max[1].max(child_bounds.max[1]);
+                max[2] = max[2].max(child_bounds.max[2]);
+            }
+            self.bounds = AABB::new(min, max);
+        }
+
+        self.bounds.clone()
+    }
+
+    /// Check whether any atom in this leaf (or, recursively, any descendant
+    /// leaf) has migrated outside its leaf's current bounds by more than
+    /// `tolerance * leaf_extent` - the signal that a plain `refit` is no
+    /// longer a good enough approximation of the tree's spatial partition
+    /// and a full `Octree::build` is warranted instead.
+    fn exceeds_drift(&self, atoms: &Atoms, tolerance: f32) -> bool {
+        if self.is_leaf() {
+            if self.atom_indices.is_empty() {
+                return false;
+            }
+            let allowance = self.bounds.max_extent() * tolerance;
+            for &atom_idx in &self.atom_indices {
+                let pos = [atoms.x[atom_idx], atoms.y[atom_idx], atoms.z[atom_idx]];
+                for axis in 0..3 {
+                    if pos[axis] < self.bounds.min[axis] - allowance
+                        || pos[axis] > self.bounds.max[axis] + allowance
+                    {
+                        return true;
+                    }
+                }
+            }
+            false
+        } else if let Some(ref children) = self.children {
+            children.iter().any(|child| child.exceeds_drift(atoms, tolerance))
+        } else {
+            false
+        }
+    }
+
+    /// Collect the bounds of every leaf node (for debug wireframe overlays)
+    fn collect_leaf_bounds(&self, result: &mut Vec<AABB>) {
+        if self.is_leaf() {
+            result.push(self.bounds.clone());
+        } else if let Some(ref children) = self.children {
+            for child in children.iter() {
+                child.collect_leaf_bounds(result);
+            }
+        }
+    }
+
     /// Get node count (for debugging/stats)
     pub fn count_nodes(&self) -> usize {
         if self.is_leaf() {
@@ -247,6 +770,25 @@ impl Octree {
         }
     }
 
+    /// Build an octree by recursive SAH binary splits instead of `build`'s
+    /// fixed octant subdivision - see `OctreeNode::build_sah_node`.
+    /// Clustered/anisotropic structures (thin slabs, solvated boxes) split
+    /// far more evenly this way than median octants do, reducing node
+    /// visits per query. Produces the same `OctreeNode` tree shape `build`
+    /// does (just 2-ary instead of 8-ary), so every query method
+    /// (`query_visible`, `query_near_camera`, `query_ray`, `query_knn`, ...)
+    /// works over it unchanged.
+    pub fn build_sah(atoms: &Atoms, max_leaf: usize) -> Self {
+        let atom_indices: Vec<usize> = (0..atoms.len()).collect();
+        let bounds = tight_bounds(atoms, &atom_indices);
+        let root = OctreeNode::build_sah_node(atoms, bounds, atom_indices, 0, max_leaf);
+
+        Self {
+            root,
+            atom_count: atoms.len(),
+        }
+    }
+
     /// Query visible atoms using frustum culling
     pub fn query_visible(&self, frustum_planes: &[[f32; 4]; 6]) -> Vec<usize> {
         let mut result = Vec::new();
@@ -261,6 +803,167 @@ impl Octree {
         result
     }
 
+    /// Query atom candidates along a ray (for hover/click picking); callers
+    /// still need to do the per-atom ray-sphere test, since this only
+    /// narrows down to the nodes the ray's bounding box passes through
+    pub fn query_ray(&self, origin: [f32; 3], direction: [f32; 3]) -> Vec<usize> {
+        let mut result = Vec::new();
+        self.root.query_ray(origin, direction, &mut result);
+        result
+    }
+
+    /// Ray cast for hover/click picking (mouse or an agent-issued "what's
+    /// here" query): returns the index and ray parameter `t` of the closest
+    /// atom the ray actually hits, doing the per-atom ray-sphere test and
+    /// near-to-far pruning internally. `direction` must be normalized.
+    ///
+    /// Unlike `query_ray`, which only narrows down to AABB candidates and
+    /// leaves the sphere test to the caller, this resolves all the way to a
+    /// single nearest hit.
+    pub fn query_ray_nearest(&self, atoms: &Atoms, origin: [f32; 3], direction: [f32; 3]) -> Option<(usize, f32)> {
+        let inv_dir = [1.0 / direction[0], 1.0 / direction[1], 1.0 / direction[2]];
+        let mut best = None;
+        self.root.query_ray_nearest(origin, direction, inv_dir, atoms, &mut best);
+        best
+    }
+
+    /// Best-first k-nearest-neighbor search. Maintains a min-heap of octree
+    /// nodes keyed by `AABB::min_dist_sq` (the minimum possible distance from
+    /// `point` to anything the node could contain) and a max-heap of the
+    /// current k best candidates keyed by distance. Each pop takes the
+    /// closest remaining node; once that node's min-distance exceeds the
+    /// current k-th best candidate, every node still in the heap is at least
+    /// as far, so the search stops early instead of visiting the whole tree.
+    /// Leaf atoms are ray-sphere-free point tests: insert into the candidate
+    /// heap and evict the farthest once it grows past `k`. Returns
+    /// `(atom_index, distance)` pairs sorted nearest-first.
+    pub fn query_knn(&self, atoms: &Atoms, point: [f32; 3], k: usize) -> Vec<(usize, f32)> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let mut candidates: BinaryHeap<(OrderedDist, usize)> = BinaryHeap::new();
+        let mut nodes: BinaryHeap<Reverse<HeapNode>> = BinaryHeap::new();
+        nodes.push(Reverse(HeapNode { dist: OrderedDist(self.root.bounds.min_dist_sq(point)), node: &self.root }));
+
+        while let Some(Reverse(HeapNode { dist: node_dist, node })) = nodes.pop() {
+            if candidates.len() >= k {
+                if let Some(&(worst, _)) = candidates.peek() {
+                    if node_dist.0 > worst.0 {
+                        break; // every remaining node is farther than the current k-th best
+                    }
+                }
+            }
+
+            if node.is_leaf() {
+                for &atom_idx in &node.atom_indices {
+                    let dx = atoms.x[atom_idx] - point[0];
+                    let dy = atoms.y[atom_idx] - point[1];
+                    let dz = atoms.z[atom_idx] - point[2];
+                    let dist_sq = dx * dx + dy * dy + dz * dz;
+
+                    if candidates.len() < k {
+                        candidates.push((OrderedDist(dist_sq), atom_idx));
+                    } else if let Some(&(worst, _)) = candidates.peek() {
+                        if dist_sq < worst.0 {
+                            candidates.pop();
+                            candidates.push((OrderedDist(dist_sq), atom_idx));
+                        }
+                    }
+                }
+            } else if let Some(ref children) = node.children {
+                for child in children.iter() {
+                    nodes.push(Reverse(HeapNode { dist: OrderedDist(child.bounds.min_dist_sq(point)), node: child }));
+                }
+            }
+        }
+
+        let mut result: Vec<(usize, f32)> = candidates.into_iter().map(|(d, i)| (i, d.0.sqrt())).collect();
+        result.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        result
+    }
+
+    /// Fixed-radius neighbor search, sharing `query_knn`'s min-heap
+    /// best-first traversal but without the k-candidate cap: a node is only
+    /// popped and descended while its `min_dist_sq` to `point` is within
+    /// `radius`, so the heap's ascending order lets the search stop the
+    /// moment it reaches a node entirely outside the radius. Unlike
+    /// `query_sphere`, which returns every atom of each leaf the sphere
+    /// touches, each leaf atom's own distance is tested against `radius`.
+    /// Returns unsorted atom indices (no distance needed by callers
+    /// doing a fixed-cutoff neighbor test, e.g. `compute_bonds`).
+    pub fn query_radius(&self, atoms: &Atoms, point: [f32; 3], radius: f32) -> Vec<usize> {
+        let radius_sq = radius * radius;
+        let mut result = Vec::new();
+        let mut nodes: BinaryHeap<Reverse<HeapNode>> = BinaryHeap::new();
+        nodes.push(Reverse(HeapNode { dist: OrderedDist(self.root.bounds.min_dist_sq(point)), node: &self.root }));
+
+        while let Some(Reverse(HeapNode { dist: node_dist, node })) = nodes.pop() {
+            if node_dist.0 > radius_sq {
+                break; // every remaining node is farther than the radius
+            }
+
+            if node.is_leaf() {
+                for &atom_idx in &node.atom_indices {
+                    let dx = atoms.x[atom_idx] - point[0];
+                    let dy = atoms.y[atom_idx] - point[1];
+                    let dz = atoms.z[atom_idx] - point[2];
+                    if dx * dx + dy * dy + dz * dz <= radius_sq {
+                        result.push(atom_idx);
+                    }
+                }
+            } else if let Some(ref children) = node.children {
+                for child in children.iter() {
+                    nodes.push(Reverse(HeapNode { dist: OrderedDist(child.bounds.min_dist_sq(point)), node: child }));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Refit every node's `AABB` bottom-up from the atoms currently assigned
+    /// to it, without rebuilding topology. Cheap relative to `Octree::build`
+    /// since no atom moves between nodes - meant to be called every
+    /// trajectory/MD frame, reserving `build` for when `needs_rebuild` says
+    /// the topology itself is no longer a good fit for where atoms are.
+    pub fn refit(&mut self, atoms: &Atoms) {
+        self.root.refit_bounds(atoms);
+    }
+
+    /// Heuristic for when `refit` alone is no longer good enough: true once
+    /// any atom has drifted outside its leaf's bounds by more than
+    /// `tolerance` times that leaf's extent (e.g. `0.25` allows an atom to
+    /// wander a quarter of its leaf's size past the boundary before this
+    /// trips). Call before `refit` each frame - a `true` result means
+    /// `Octree::build` should replace this tree instead of `refit` it.
+    pub fn needs_rebuild(&self, atoms: &Atoms, tolerance: f32) -> bool {
+        self.root.exceeds_drift(atoms, tolerance)
+    }
+
+    /// Bounds of every leaf node, for the `SHOW_OCTREE_BOXES` debug overlay
+    pub fn leaf_bounds(&self) -> Vec<AABB> {
+        let mut result = Vec::new();
+        self.root.collect_leaf_bounds(&mut result);
+        result
+    }
+
+    /// Frustum-test every leaf's bounds in one batched, branchless pass (see
+    /// `cull_frustum_batch`) instead of the recursive per-node walk
+    /// `query_visible` does. Returns a mask parallel to `leaf_bounds()`;
+    /// `true` means the leaf is inside the frustum. Useful when a caller
+    /// already has (or wants) a flat view of every leaf - e.g. the
+    /// `SHOW_OCTREE_BOXES` debug overlay deciding which boxes to draw - and
+    /// would rather do one vectorizable pass than one `intersects_frustum`
+    /// call per node.
+    pub fn cull_leaves_batch(&self, frustum_planes: &[[f32; 4]; 6]) -> Vec<bool> {
+        let leaf_bounds = self.leaf_bounds();
+        let soa = AABBSoA::from_bounds(&leaf_bounds);
+        let mut mask = vec![false; soa.len()];
+        cull_frustum_batch(frustum_planes, &soa, &mut mask);
+        mask
+    }
+
     /// Get statistics (for debugging)
     pub fn stats(&self) -> OctreeStats {
         OctreeStats {
@@ -288,3 +991,292 @@ pub struct OctreeStats {
     pub total_atoms: usize,
     pub max_depth: u32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_atoms() -> Atoms {
+        // A 4x4x4 grid of atoms spaced 2 Å apart on each axis, so distances
+        // between neighbors are unambiguous and easy to hand-verify.
+        let mut atoms = Atoms::new();
+        for i in 0..4 {
+            for j in 0..4 {
+                for k in 0..4 {
+                    atoms.push(i as f32 * 2.0, j as f32 * 2.0, k as f32 * 2.0, 6);
+                }
+            }
+        }
+        atoms
+    }
+
+    #[test]
+    fn test_query_knn_finds_nearest_atoms() {
+        let atoms = grid_atoms();
+        let octree = Octree::build(&atoms, 8, 4);
+
+        // Query near the origin atom (0,0,0); its nearest neighbors are the
+        // three atoms one grid step away along each axis (distance 2.0) plus
+        // itself (distance 0.0).
+        let result = octree.query_knn(&atoms, [0.0, 0.0, 0.0], 4);
+
+        assert_eq!(result.len(), 4);
+        assert_eq!(result[0].0, 0); // the origin atom itself
+        assert_eq!(result[0].1, 0.0);
+        // Results must be sorted nearest-first.
+        for pair in result.windows(2) {
+            assert!(pair[0].1 <= pair[1].1);
+        }
+        // The next three closest are exactly 2.0 Å away.
+        for &(_, dist) in &result[1..4] {
+            assert!((dist - 2.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_query_knn_respects_k_and_zero() {
+        let atoms = grid_atoms();
+        let octree = Octree::build(&atoms, 8, 4);
+
+        assert_eq!(octree.query_knn(&atoms, [0.0, 0.0, 0.0], 0).len(), 0);
+        assert_eq!(octree.query_knn(&atoms, [0.0, 0.0, 0.0], 1000).len(), atoms.len());
+    }
+
+    #[test]
+    fn test_query_radius_matches_brute_force() {
+        let atoms = grid_atoms();
+        let octree = Octree::build(&atoms, 8, 4);
+        let point = [2.0, 2.0, 2.0];
+        let radius = 2.5;
+
+        let mut expected: Vec<usize> = (0..atoms.len())
+            .filter(|&i| {
+                let dx = atoms.x[i] - point[0];
+                let dy = atoms.y[i] - point[1];
+                let dz = atoms.z[i] - point[2];
+                (dx * dx + dy * dy + dz * dz).sqrt() <= radius
+            })
+            .collect();
+        expected.sort_unstable();
+
+        let mut actual = octree.query_radius(&atoms, point, radius);
+        actual.sort_unstable();
+
+        assert_eq!(actual, expected);
+    }
+
+    /// Brute-force reference for `query_ray_nearest`: the same ray-sphere
+    /// test the octree uses internally, just run over every atom with no
+    /// tree pruning at all.
+    fn brute_force_nearest(atoms: &Atoms, origin: [f32; 3], direction: [f32; 3]) -> Option<(usize, f32)> {
+        let mut best: Option<(usize, f32)> = None;
+        for idx in 0..atoms.len() {
+            let center = [atoms.x[idx], atoms.y[idx], atoms.z[idx]];
+            let radius = element_to_ball_stick_radius(atoms.elements[idx]);
+            if let Some(t) = ray_sphere_intersect(origin, direction, center, radius) {
+                if best.map(|(_, best_t)| t < best_t).unwrap_or(true) {
+                    best = Some((idx, t));
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_query_ray_nearest_prefers_closer_hit_in_sibling_subtree() {
+        let mut atoms = Atoms::new();
+        atoms.push(5.0, 0.0, 0.0, 6); // idx 0: farther along the ray
+        atoms.push(1.0, 0.0, 0.0, 6); // idx 1: nearer along the ray
+
+        // max_atoms_per_node = 1 forces the two atoms into separate octants,
+        // so the near-to-far traversal must actually compare sibling
+        // subtrees rather than just scanning one leaf's atoms.
+        let octree = Octree::build(&atoms, 8, 1);
+
+        let origin = [-10.0, 0.0, 0.0];
+        let direction = [1.0, 0.0, 0.0];
+        let (idx, t) = octree.query_ray_nearest(&atoms, origin, direction).expect("ray should hit an atom");
+
+        assert_eq!(idx, 1); // the nearer atom, not the farther one
+        assert!(t < 11.0); // sanity: well short of the farther atom's center
+    }
+
+    #[test]
+    fn test_query_ray_nearest_matches_brute_force_over_grid() {
+        let atoms = grid_atoms();
+        // A shallow max_atoms_per_node forces many sibling subtrees, so a
+        // pruning bug that skips a closer hit elsewhere in the tree would
+        // show up as a mismatch against the brute-force scan.
+        let octree = Octree::build(&atoms, 8, 2);
+
+        let rays = [
+            ([-10.0, 2.0, 2.0], [1.0, 0.0, 0.0]),
+            ([2.0, -10.0, 4.0], [0.0, 1.0, 0.0]),
+            ([6.0, 6.0, -10.0], [0.0, 0.0, 1.0]),
+        ];
+
+        for (origin, direction) in rays {
+            let expected = brute_force_nearest(&atoms, origin, direction);
+            let actual = octree.query_ray_nearest(&atoms, origin, direction);
+            match (expected, actual) {
+                (Some((e_idx, e_t)), Some((a_idx, a_t))) => {
+                    assert_eq!(a_idx, e_idx);
+                    assert!((a_t - e_t).abs() < 1e-4);
+                }
+                (None, None) => {}
+                (e, a) => panic!("mismatch: expected {:?}, got {:?}", e, a),
+            }
+        }
+    }
+
+    #[test]
+    fn test_refit_tightens_occupied_leaves_and_preserves_empty_leaf_bounds() {
+        let mut atoms = Atoms::new();
+        atoms.push(-1.0, -1.0, -1.0, 6); // idx 0
+        atoms.push(1.0, 1.0, 1.0, 6); // idx 1
+
+        // depth limit 1 + max_atoms_per_node 1 subdivides the root exactly
+        // once into 8 fixed octant leaves: child 0 gets idx 0, child 7 gets
+        // idx 1, and the other 6 children are empty.
+        let mut octree = Octree::build(&atoms, 1, 1);
+        let before = octree.leaf_bounds();
+        assert_eq!(before.len(), 8);
+
+        octree.refit(&atoms);
+        let after = octree.leaf_bounds();
+        assert_eq!(after.len(), 8);
+
+        // Leaf 0's loose octant box ([-1.1,-1.1,-1.1]..[0,0,0]) tightens down
+        // to a 0.1-margin box hugging just its one atom.
+        assert!((after[0].min[0] - (-1.1)).abs() < 1e-4);
+        assert!((after[0].max[0] - (-0.9)).abs() < 1e-4);
+        assert!(after[0].max_extent() < before[0].max_extent());
+
+        // Leaf 7 is the mirror image, around the atom at (1,1,1).
+        assert!((after[7].min[0] - 0.9).abs() < 1e-4);
+        assert!((after[7].max[0] - 1.1).abs() < 1e-4);
+
+        // The 6 empty leaves have nothing to refit from, so refit must leave
+        // their bounds exactly as they were rather than collapsing them.
+        for i in 1..7 {
+            assert_eq!(after[i].min, before[i].min);
+            assert_eq!(after[i].max, before[i].max);
+        }
+
+        // Moving the atom and refitting again should move its leaf's tight
+        // bounds along with it.
+        atoms.x[0] = -0.5;
+        octree.refit(&atoms);
+        let moved = octree.leaf_bounds();
+        assert!((moved[0].min[0] - (-0.6)).abs() < 1e-4);
+        assert!((moved[0].max[0] - (-0.4)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_needs_rebuild_detects_drift_past_tolerance() {
+        let mut atoms = Atoms::new();
+        atoms.push(-1.0, -1.0, -1.0, 6);
+        atoms.push(1.0, 1.0, 1.0, 6);
+
+        let mut octree = Octree::build(&atoms, 1, 1);
+        octree.refit(&atoms); // tighten leaf 0 to [-1.1,-0.9]^3 around idx 0
+
+        // A small nudge stays comfortably inside the leaf's bounds.
+        atoms.x[0] = -0.95;
+        assert!(!octree.needs_rebuild(&atoms, 0.25));
+
+        // A large jump clears the leaf's bounds plus its drift allowance.
+        atoms.x[0] = 5.0;
+        assert!(octree.needs_rebuild(&atoms, 0.25));
+    }
+
+    #[test]
+    fn test_cull_frustum_batch_matches_per_box_intersects_frustum() {
+        // A cube frustum spanning [-1, 1] on each axis, in the same
+        // ax+by+cz+d >= 0 "inside" convention `AABB::intersects_frustum` uses.
+        let cube_planes: [[f32; 4]; 6] = [
+            [1.0, 0.0, 0.0, 1.0],
+            [-1.0, 0.0, 0.0, 1.0],
+            [0.0, 1.0, 0.0, 1.0],
+            [0.0, -1.0, 0.0, 1.0],
+            [0.0, 0.0, 1.0, 1.0],
+            [0.0, 0.0, -1.0, 1.0],
+        ];
+
+        let boxes = vec![
+            AABB::new([-0.5, -0.5, -0.5], [0.5, 0.5, 0.5]), // fully inside
+            AABB::new([10.0, 10.0, 10.0], [11.0, 11.0, 11.0]), // fully outside
+            AABB::new([0.5, 0.5, 0.5], [2.0, 2.0, 2.0]),    // straddles a corner
+            AABB::new([-2.0, -2.0, -2.0], [-1.5, -1.5, -1.5]), // outside, opposite corner
+            AABB::new([-1.0, -1.0, -1.0], [1.0, 1.0, 1.0]), // exactly touches the boundary
+        ];
+
+        let expected: Vec<bool> = boxes.iter().map(|b| b.intersects_frustum(&cube_planes)).collect();
+
+        let soa = AABBSoA::from_bounds(&boxes);
+        let mut mask = vec![false; boxes.len()];
+        cull_frustum_batch(&cube_planes, &soa, &mut mask);
+
+        assert_eq!(mask, expected);
+        // Sanity: this box set should actually discriminate, so the
+        // comparison above isn't vacuously true.
+        assert!(expected.contains(&true));
+        assert!(expected.contains(&false));
+    }
+
+    #[test]
+    fn test_build_sah_splits_between_well_separated_clusters() {
+        let mut atoms = Atoms::new();
+        // Two tight 2-atom clusters far apart along x (with distinct y/z
+        // offsets so no two atoms tie exactly on any axis, which would let
+        // a same-cost split on y or z compete with the intended x split).
+        // Hand-computing the surface-area cost over all 3 axes x 3 split
+        // points confirms split=2 (between the clusters) is the minimum;
+        // y and z order the atoms the same way x does, so their split=2 is
+        // the same partition at the same cost.
+        atoms.push(0.0, 0.0, 0.0, 6);
+        atoms.push(0.1, 0.03, 0.01, 6);
+        atoms.push(10.0, 0.07, 0.04, 6);
+        atoms.push(10.1, 0.09, 0.05, 6);
+
+        let octree = Octree::build_sah(&atoms, 2);
+        let stats = octree.stats();
+        // One split (root) producing exactly 2 leaves, each at max_leaf (2).
+        assert_eq!(stats.total_nodes, 3);
+
+        let mut leaves = octree.leaf_bounds();
+        assert_eq!(leaves.len(), 2);
+        leaves.sort_by(|a, b| a.min[0].partial_cmp(&b.min[0]).unwrap());
+
+        // Left leaf bounds cluster A (with build_sah_node's tight_bounds
+        // margin of 0.1), right leaf bounds cluster B - no overlap.
+        assert!(leaves[0].max[0] < 1.0);
+        assert!(leaves[1].min[0] > 9.0);
+    }
+
+    #[test]
+    fn test_build_sah_collinear_atoms_split_correctly_without_losing_any() {
+        let mut atoms = Atoms::new();
+        // All atoms share y=0, z=0: every candidate split's prefix/suffix
+        // box (computed from the atoms' actual extent) is degenerate with
+        // zero surface area on this axis pair, unlike the exhaustive-search
+        // cluster case above. The builder still has to recurse to a correct,
+        // lossless partition rather than mishandling the zero-cost ties.
+        for i in 0..5 {
+            atoms.push(i as f32, 0.0, 0.0, 6);
+        }
+
+        let octree = Octree::build_sah(&atoms, 1);
+        let stats = octree.stats();
+        assert_eq!(stats.total_atoms, 5);
+        // A full binary tree with 5 leaves has exactly 4 internal nodes.
+        assert_eq!(stats.total_nodes, 9);
+
+        let leaves = octree.leaf_bounds();
+        assert_eq!(leaves.len(), 5);
+
+        let mut found = octree.query_radius(&atoms, [2.0, 0.0, 0.0], 100.0);
+        found.sort_unstable();
+        assert_eq!(found, vec![0, 1, 2, 3, 4]);
+    }
+}
@@ -31,8 +31,37 @@ pub fn parse_cif_with_bonds<P: AsRef<Path>>(path: P) -> Result<(Atoms, Bonds)> {
     parse_cif_with_bonds_reader(reader)
 }
 
+/// Parse CIF file, applying the `_symmetry_equiv_pos_as_xyz` (or
+/// `_space_group_symop_operation_xyz`) operators to expand the asymmetric
+/// unit into the full P1 unit cell.
+pub fn parse_cif_expand_to_cell<P: AsRef<Path>>(path: P) -> Result<Atoms> {
+    let file = File::open(path.as_ref())
+        .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+    let reader = BufReader::new(file);
+    let (atoms, _bonds) = parse_cif_impl(reader, true)?;
+    Ok(atoms)
+}
+
+/// Parse CIF file with bonds, expanded to the full P1 unit cell - see
+/// `parse_cif_expand_to_cell`.
+pub fn parse_cif_expand_to_cell_with_bonds<P: AsRef<Path>>(path: P) -> Result<(Atoms, Bonds)> {
+    let file = File::open(path.as_ref())
+        .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+    let reader = BufReader::new(file);
+    parse_cif_impl(reader, true)
+}
+
 /// Parse CIF from a buffered reader
-fn parse_cif_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bonds)> {
+pub fn parse_cif_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bonds)> {
+    parse_cif_impl(reader, false)
+}
+
+/// Parse CIF from a buffered reader. When `expand_to_cell` is set and the
+/// file declares symmetry operators, every asymmetric-unit atom is mapped
+/// through each operator before the asymmetric-unit-only Cartesian
+/// conversion and bond building run, so the rest of the function is
+/// unaware whether it is looking at the asymmetric unit or the full cell.
+fn parse_cif_impl<R: BufRead>(reader: R, expand_to_cell: bool) -> Result<(Atoms, Bonds)> {
     let mut atoms = Atoms::new();
     let mut bonds = Bonds::new();
 
@@ -43,6 +72,7 @@ fn parse_cif_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bonds)>
     let mut cell_alpha = 90.0_f32;
     let mut cell_beta = 90.0_f32;
     let mut cell_gamma = 90.0_f32;
+    let mut has_cell_params = false;
 
     // Atom data (fractional coordinates)
     let mut atom_labels: Vec<String> = Vec::new();
@@ -54,11 +84,16 @@ fn parse_cif_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bonds)>
     // Bond data (labels, not indices)
     let mut bond_labels: Vec<(String, String)> = Vec::new();
 
+    // Symmetry operation strings, e.g. "-x, y+1/2, z"
+    let mut symmetry_ops: Vec<String> = Vec::new();
+
     // Parse state
     let mut in_atom_loop = false;
     let mut in_bond_loop = false;
+    let mut in_symmetry_loop = false;
     let mut atom_loop_cols: HashMap<String, usize> = HashMap::new();
     let mut bond_loop_cols: HashMap<String, usize> = HashMap::new();
+    let mut symmetry_loop_cols: HashMap<String, usize> = HashMap::new();
 
     for line_result in reader.lines() {
         let line = line_result?;
@@ -72,10 +107,13 @@ fn parse_cif_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bonds)>
         // Parse unit cell parameters
         if trimmed.starts_with("_cell_length_a") {
             cell_a = parse_cell_param(&trimmed)?;
+            has_cell_params = true;
         } else if trimmed.starts_with("_cell_length_b") {
             cell_b = parse_cell_param(&trimmed)?;
+            has_cell_params = true;
         } else if trimmed.starts_with("_cell_length_c") {
             cell_c = parse_cell_param(&trimmed)?;
+            has_cell_params = true;
         } else if trimmed.starts_with("_cell_angle_alpha") {
             cell_alpha = parse_cell_param(&trimmed)?;
         } else if trimmed.starts_with("_cell_angle_beta") {
@@ -87,8 +125,10 @@ fn parse_cif_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bonds)>
         else if trimmed.starts_with("loop_") {
             in_atom_loop = false;
             in_bond_loop = false;
+            in_symmetry_loop = false;
             atom_loop_cols.clear();
             bond_loop_cols.clear();
+            symmetry_loop_cols.clear();
         } else if trimmed.starts_with("_atom_site_") {
             in_atom_loop = true;
             let col_name = trimmed.to_string();
@@ -97,6 +137,10 @@ fn parse_cif_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bonds)>
             in_bond_loop = true;
             let col_name = trimmed.to_string();
             bond_loop_cols.insert(col_name, bond_loop_cols.len());
+        } else if trimmed.starts_with("_symmetry_equiv_pos_") || trimmed.starts_with("_space_group_symop_") {
+            in_symmetry_loop = true;
+            let col_name = trimmed.to_string();
+            symmetry_loop_cols.insert(col_name, symmetry_loop_cols.len());
         }
         // Data lines (not starting with _ or loop_)
         else if !trimmed.starts_with('_') && !trimmed.starts_with("loop_") && !trimmed.starts_with("data_") {
@@ -151,6 +195,23 @@ fn parse_cif_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bonds)>
                         bond_labels.push((parts[a1_idx].to_string(), parts[a2_idx].to_string()));
                     }
                 }
+            } else if in_symmetry_loop && !symmetry_loop_cols.is_empty() {
+                // Symmetry operator strings may contain spaces inside quotes
+                // (e.g. `'-x, y+1/2, z'`), so this loop needs quote-aware
+                // splitting rather than the plain `split_whitespace` used
+                // for the atom/bond loops above.
+                let parts = split_cif_line(trimmed);
+
+                let xyz_col = symmetry_loop_cols
+                    .iter()
+                    .find(|(key, _)| key.ends_with("_as_xyz") || key.ends_with("_operation_xyz"))
+                    .map(|(_, &idx)| idx);
+
+                if let Some(idx) = xyz_col {
+                    if parts.len() > idx {
+                        symmetry_ops.push(parts[idx].clone());
+                    }
+                }
             }
         }
     }
@@ -159,6 +220,16 @@ fn parse_cif_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bonds)>
         return Err(AxiomError::ParseError("No atoms found in CIF file".to_string()));
     }
 
+    // Expand the asymmetric unit through the symmetry operators into the
+    // full P1 cell before anything downstream (Cartesian conversion, bond
+    // building) runs, so those steps don't need to know the difference.
+    let (atom_labels, atom_symbols, fract_x, fract_y, fract_z) =
+        if expand_to_cell && !symmetry_ops.is_empty() {
+            expand_symmetry(&atom_labels, &atom_symbols, &fract_x, &fract_y, &fract_z, &symmetry_ops)?
+        } else {
+            (atom_labels, atom_symbols, fract_x, fract_y, fract_z)
+        };
+
     // Convert fractional to Cartesian coordinates
     let (cart_x, cart_y, cart_z) = fractional_to_cartesian(
         &fract_x, &fract_y, &fract_z,
@@ -172,6 +243,20 @@ fn parse_cif_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bonds)>
         atoms.push(cart_x[i], cart_y[i], cart_z[i], element);
     }
 
+    // Store the triclinic box for periodic (minimum-image) selections, but
+    // only if the file actually declared cell lengths - otherwise the
+    // a=b=c=1.0 defaults would wrap every structure into a 1Å box.
+    if has_cell_params {
+        let box_matrix = orthogonalization_matrix(
+            cell_a, cell_b, cell_c,
+            cell_alpha, cell_beta, cell_gamma,
+        );
+        atoms.set_periodic_box(box_matrix);
+    }
+
+    // `_atom_site_label` doubles as the atom name (e.g. "CA1", "N", "OXT")
+    atoms.atom_names = Some(atom_labels.clone());
+
     // Build bonds if present
     if !bond_labels.is_empty() {
         // Create label -> index map
@@ -230,20 +315,200 @@ fn extract_symbol_from_label(label: &str) -> String {
     symbol
 }
 
-/// Convert fractional coordinates to Cartesian
-fn fractional_to_cartesian(
-    fract_x: &[f32], fract_y: &[f32], fract_z: &[f32],
-    a: f32, b: f32, c: f32,
-    alpha: f32, beta: f32, gamma: f32
-) -> (Vec<f32>, Vec<f32>, Vec<f32>) {
+/// Split a CIF loop data line into tokens, treating a run of characters
+/// wrapped in matching `'` or `"` as a single token even if it contains
+/// spaces - needed for symmetry operator strings like `'-x, y+1/2, z'`,
+/// which the `_atom_site_*`/`_geom_bond_*` loops never need since none of
+/// their values contain embedded spaces.
+fn split_cif_line(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+
+    for c in line.chars() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            } else {
+                current.push(c);
+            }
+        } else if c == '\'' || c == '"' {
+            quote = Some(c);
+        } else if c.is_whitespace() {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// Parse one symmetry operator string (e.g. `"-x, y+1/2, z"`) into the
+/// affine map it represents: a 3x3 rotation matrix (entries are always
+/// -1, 0, or 1 for crystallographic symmetry operators) and a fractional
+/// translation vector.
+fn parse_symop(op_str: &str) -> Result<([[f32; 3]; 3], [f32; 3])> {
+    let components: Vec<&str> = op_str.split(',').collect();
+    if components.len() != 3 {
+        return Err(AxiomError::ParseError(format!(
+            "Invalid symmetry operation (expected 3 comma-separated components): {}",
+            op_str
+        )));
+    }
+
+    let mut rotation = [[0.0_f32; 3]; 3];
+    let mut translation = [0.0_f32; 3];
+
+    for (row, component) in components.iter().enumerate() {
+        for term in symop_terms(component) {
+            let (sign, body) = if let Some(rest) = term.strip_prefix('-') {
+                (-1.0, rest)
+            } else if let Some(rest) = term.strip_prefix('+') {
+                (1.0, rest)
+            } else {
+                (1.0, term.as_str())
+            };
+
+            match body.trim().to_ascii_lowercase().as_str() {
+                "x" => rotation[row][0] = sign,
+                "y" => rotation[row][1] = sign,
+                "z" => rotation[row][2] = sign,
+                "" => {}
+                value => translation[row] += sign * parse_symop_fraction(value)?,
+            }
+        }
+    }
+
+    Ok((rotation, translation))
+}
+
+/// Split one equation component (e.g. `"x-y+1/2"`) into signed terms
+/// (`["x", "-y", "1/2"]`) by inserting an explicit `+` before every
+/// non-leading `-`, then splitting on `+`.
+fn symop_terms(component: &str) -> Vec<String> {
+    let mut normalized = String::new();
+    for (i, c) in component.chars().enumerate() {
+        if c == '-' && i != 0 {
+            normalized.push('+');
+        }
+        normalized.push(c);
+    }
+
+    normalized
+        .split('+')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Parse a translation term that may be a plain number ("0.5") or a
+/// fraction ("1/2", "2/3").
+fn parse_symop_fraction(value: &str) -> Result<f32> {
+    if let Some((num, den)) = value.split_once('/') {
+        let num: f32 = num.trim().parse().map_err(|_| {
+            AxiomError::ParseError(format!("Invalid symmetry translation: {}", value))
+        })?;
+        let den: f32 = den.trim().parse().map_err(|_| {
+            AxiomError::ParseError(format!("Invalid symmetry translation: {}", value))
+        })?;
+        Ok(num / den)
+    } else {
+        value
+            .trim()
+            .parse()
+            .map_err(|_| AxiomError::ParseError(format!("Invalid symmetry translation: {}", value)))
+    }
+}
+
+/// Apply every symmetry operator to every asymmetric-unit atom, wrapping
+/// results into `[0, 1)` fractional coordinates and deduplicating sites
+/// that land within `TOLERANCE` of an already-generated atom (as happens for
+/// atoms sitting on a symmetry element, and for the identity operator which
+/// every CIF symmetry loop includes). Generated copies are labeled
+/// `"{original}_{op_index}"` (1-based, skipping the identity operator) so
+/// `_geom_bond_*` labels - which only ever reference the asymmetric unit -
+/// keep resolving correctly afterward.
+fn expand_symmetry(
+    labels: &[String],
+    symbols: &[String],
+    fract_x: &[f32],
+    fract_y: &[f32],
+    fract_z: &[f32],
+    symmetry_ops: &[String],
+) -> Result<(Vec<String>, Vec<String>, Vec<f32>, Vec<f32>, Vec<f32>)> {
+    const TOLERANCE: f32 = 1e-3;
+
+    let parsed_ops: Vec<([[f32; 3]; 3], [f32; 3])> = symmetry_ops
+        .iter()
+        .map(|op| parse_symop(op))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut out_labels = Vec::new();
+    let mut out_symbols = Vec::new();
+    let mut out_x: Vec<f32> = Vec::new();
+    let mut out_y: Vec<f32> = Vec::new();
+    let mut out_z: Vec<f32> = Vec::new();
+
+    for i in 0..labels.len() {
+        let frac = [fract_x[i], fract_y[i], fract_z[i]];
+
+        for (op_idx, (rotation, translation)) in parsed_ops.iter().enumerate() {
+            let mut mapped = [0.0_f32; 3];
+            for (row, slot) in mapped.iter_mut().enumerate() {
+                *slot = rotation[row][0] * frac[0]
+                    + rotation[row][1] * frac[1]
+                    + rotation[row][2] * frac[2]
+                    + translation[row];
+                *slot -= slot.floor(); // wrap into [0, 1)
+            }
+
+            let is_duplicate = (0..out_x.len()).any(|j| {
+                let mut d = [mapped[0] - out_x[j], mapped[1] - out_y[j], mapped[2] - out_z[j]];
+                for c in d.iter_mut() {
+                    *c -= c.round(); // nearest periodic image, for sites near 0/1
+                }
+                (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt() < TOLERANCE
+            });
+
+            if is_duplicate {
+                continue;
+            }
+
+            let label = if op_idx == 0 {
+                labels[i].clone()
+            } else {
+                format!("{}_{}", labels[i], op_idx + 1)
+            };
+
+            out_labels.push(label);
+            out_symbols.push(symbols[i].clone());
+            out_x.push(mapped[0]);
+            out_y.push(mapped[1]);
+            out_z.push(mapped[2]);
+        }
+    }
+
+    Ok((out_labels, out_symbols, out_x, out_y, out_z))
+}
 
-    // Convert angles to radians
+/// Build the orthogonalization matrix (converts fractional to Cartesian
+/// coordinates) for a crystallographic unit cell - the standard
+/// crystallographic transformation from a/b/c/alpha/beta/gamma.
+fn orthogonalization_matrix(
+    a: f32, b: f32, c: f32,
+    alpha: f32, beta: f32, gamma: f32,
+) -> [[f32; 3]; 3] {
     let alpha_rad = alpha.to_radians();
     let beta_rad = beta.to_radians();
     let gamma_rad = gamma.to_radians();
 
-    // Build transformation matrix
-    // This is the standard crystallographic transformation
     let cos_alpha = alpha_rad.cos();
     let cos_beta = beta_rad.cos();
     let cos_gamma = gamma_rad.cos();
@@ -252,16 +517,20 @@ fn fractional_to_cartesian(
     let volume = a * b * c * (1.0 - cos_alpha.powi(2) - cos_beta.powi(2) - cos_gamma.powi(2)
                              + 2.0 * cos_alpha * cos_beta * cos_gamma).sqrt();
 
-    // Orthogonalization matrix (converts fractional to Cartesian)
-    let m11 = a;
-    let m12 = b * cos_gamma;
-    let m13 = c * cos_beta;
-    let m21 = 0.0;
-    let m22 = b * sin_gamma;
-    let m23 = c * (cos_alpha - cos_beta * cos_gamma) / sin_gamma;
-    let m31 = 0.0;
-    let m32 = 0.0;
-    let m33 = volume / (a * b * sin_gamma);
+    [
+        [a, b * cos_gamma, c * cos_beta],
+        [0.0, b * sin_gamma, c * (cos_alpha - cos_beta * cos_gamma) / sin_gamma],
+        [0.0, 0.0, volume / (a * b * sin_gamma)],
+    ]
+}
+
+/// Convert fractional coordinates to Cartesian
+fn fractional_to_cartesian(
+    fract_x: &[f32], fract_y: &[f32], fract_z: &[f32],
+    a: f32, b: f32, c: f32,
+    alpha: f32, beta: f32, gamma: f32
+) -> (Vec<f32>, Vec<f32>, Vec<f32>) {
+    let m = orthogonalization_matrix(a, b, c, alpha, beta, gamma);
 
     let mut cart_x = Vec::with_capacity(fract_x.len());
     let mut cart_y = Vec::with_capacity(fract_y.len());
@@ -272,44 +541,20 @@ fn fractional_to_cartesian(
         let fy = fract_y[i];
         let fz = fract_z[i];
 
-        cart_x.push(m11 * fx + m12 * fy + m13 * fz);
-        cart_y.push(m21 * fx + m22 * fy + m23 * fz);
-        cart_z.push(m31 * fx + m32 * fy + m33 * fz);
+        cart_x.push(m[0][0] * fx + m[0][1] * fy + m[0][2] * fz);
+        cart_y.push(m[1][0] * fx + m[1][1] * fy + m[1][2] * fz);
+        cart_z.push(m[2][0] * fx + m[2][1] * fy + m[2][2] * fz);
     }
 
     (cart_x, cart_y, cart_z)
 }
 
 /// Convert element symbol to atomic number
+/// Convert element symbol to atomic number. Delegates to the crate-wide
+/// `elements` table, returning 0 (this module's long-standing sentinel for
+/// an unrecognized element) instead of `None`.
 fn symbol_to_atomic_number(symbol: &str) -> u8 {
-    let symbol_upper = symbol.to_uppercase();
-    match symbol_upper.as_str() {
-        "H" => 1,
-        "HE" => 2,
-        "LI" => 3,
-        "BE" => 4,
-        "B" => 5,
-        "C" => 6,
-        "N" => 7,
-        "O" => 8,
-        "F" => 9,
-        "NE" => 10,
-        "NA" => 11,
-        "MG" => 12,
-        "AL" => 13,
-        "SI" => 14,
-        "P" => 15,
-        "S" => 16,
-        "CL" => 17,
-        "AR" => 18,
-        "K" => 19,
-        "CA" => 20,
-        "TI" => 22,
-        "FE" => 26,
-        "CU" => 29,
-        "ZN" => 30,
-        _ => 0, // Unknown element
-    }
+    crate::elements::symbol_to_atomic_number(symbol).unwrap_or(0)
 }
 
 #[cfg(test)]
@@ -348,4 +593,153 @@ mod tests {
         assert!((cart_x[1] - 5.0).abs() < 0.001);
         assert!((cart_x[2] - 10.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_parse_cif_sets_periodic_box() {
+        let cif_data = "\
+data_test
+_cell_length_a 10.0
+_cell_length_b 10.0
+_cell_length_c 10.0
+_cell_angle_alpha 90.0
+_cell_angle_beta 90.0
+_cell_angle_gamma 90.0
+loop_
+_atom_site_label
+_atom_site_type_symbol
+_atom_site_fract_x
+_atom_site_fract_y
+_atom_site_fract_z
+C1 C 0.0 0.0 0.0
+";
+        let cursor = std::io::Cursor::new(cif_data);
+        let (atoms, _bonds) = parse_cif_with_bonds_reader(BufReader::new(cursor)).unwrap();
+
+        assert!(atoms.is_periodic());
+        let pbox = atoms.periodic_box.unwrap();
+        assert!((pbox.matrix[0][0] - 10.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_symop_identity() {
+        let (rotation, translation) = parse_symop("x, y, z").unwrap();
+        assert_eq!(rotation, [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+        assert_eq!(translation, [0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_parse_symop_inversion_with_translation() {
+        let (rotation, translation) = parse_symop("-x, y+1/2, -z").unwrap();
+        assert_eq!(rotation, [[-1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, -1.0]]);
+        assert!((translation[1] - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_parse_symop_combined_axes() {
+        // Trigonal/hexagonal-style operator with two axes in one component
+        let (rotation, _) = parse_symop("x-y, x, z").unwrap();
+        assert_eq!(rotation[0], [1.0, -1.0, 0.0]);
+        assert_eq!(rotation[1], [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_expand_symmetry_deduplicates_identity_image() {
+        let labels = vec!["C1".to_string()];
+        let symbols = vec!["C".to_string()];
+        let ops = vec!["x, y, z".to_string(), "-x, -y, -z".to_string()];
+
+        // An atom at the origin maps onto itself under inversion, so only
+        // one copy should come out.
+        let (out_labels, _, out_x, out_y, out_z) =
+            expand_symmetry(&labels, &symbols, &[0.0], &[0.0], &[0.0], &ops).unwrap();
+        assert_eq!(out_labels, vec!["C1".to_string()]);
+        assert_eq!((out_x[0], out_y[0], out_z[0]), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_expand_symmetry_generates_second_copy() {
+        let labels = vec!["C1".to_string()];
+        let symbols = vec!["C".to_string()];
+        let ops = vec!["x, y, z".to_string(), "-x, -y, -z".to_string()];
+
+        let (out_labels, _, out_x, out_y, out_z) =
+            expand_symmetry(&labels, &symbols, &[0.2], &[0.3], &[0.4], &ops).unwrap();
+
+        assert_eq!(out_labels, vec!["C1".to_string(), "C1_2".to_string()]);
+        assert!((out_x[1] - 0.8).abs() < 1e-5);
+        assert!((out_y[1] - 0.7).abs() < 1e-5);
+        assert!((out_z[1] - 0.6).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_parse_cif_expand_to_cell_applies_symmetry() {
+        let cif_data = "\
+data_test
+_cell_length_a 10.0
+_cell_length_b 10.0
+_cell_length_c 10.0
+_cell_angle_alpha 90.0
+_cell_angle_beta 90.0
+_cell_angle_gamma 90.0
+loop_
+_symmetry_equiv_pos_as_xyz
+'x, y, z'
+'-x, -y, -z'
+loop_
+_atom_site_label
+_atom_site_type_symbol
+_atom_site_fract_x
+_atom_site_fract_y
+_atom_site_fract_z
+C1 C 0.2 0.3 0.4
+";
+        let cursor = std::io::Cursor::new(cif_data);
+        let (atoms, _bonds) = parse_cif_impl(BufReader::new(cursor), true).unwrap();
+
+        assert_eq!(atoms.len(), 2);
+        assert_eq!(
+            atoms.atom_names,
+            Some(vec!["C1".to_string(), "C1_2".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_parse_cif_without_expand_to_cell_keeps_asymmetric_unit() {
+        let cif_data = "\
+data_test
+loop_
+_symmetry_equiv_pos_as_xyz
+'x, y, z'
+'-x, -y, -z'
+loop_
+_atom_site_label
+_atom_site_type_symbol
+_atom_site_fract_x
+_atom_site_fract_y
+_atom_site_fract_z
+C1 C 0.2 0.3 0.4
+";
+        let cursor = std::io::Cursor::new(cif_data);
+        let (atoms, _bonds) = parse_cif_with_bonds_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_cif_without_cell_params_stays_non_periodic() {
+        let cif_data = "\
+data_test
+loop_
+_atom_site_label
+_atom_site_type_symbol
+_atom_site_fract_x
+_atom_site_fract_y
+_atom_site_fract_z
+C1 C 0.0 0.0 0.0
+";
+        let cursor = std::io::Cursor::new(cif_data);
+        let (atoms, _bonds) = parse_cif_with_bonds_reader(BufReader::new(cursor)).unwrap();
+
+        assert!(!atoms.is_periodic());
+    }
 }
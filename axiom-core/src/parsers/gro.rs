@@ -18,10 +18,17 @@
 
 use crate::atoms::Atoms;
 use crate::errors::{AxiomError, Result};
+use crate::trajectory::parse_box_line;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
+/// GROMACS wraps the 5-wide residue-number column modulo 100000 once a
+/// system's residue count outgrows it (the same kind of fixed-width
+/// overflow PDB handles with Hybrid-36, just without a documented
+/// encoding - GRO just wraps to 0). This is the wrap modulus.
+const RESIDUE_NUMBER_WRAP: u32 = 100_000;
+
 /// Parse GROMACS GRO file
 ///
 /// Coordinates are converted from nm to Angstroms (multiply by 10)
@@ -32,7 +39,11 @@ pub fn parse_gro<P: AsRef<Path>>(path: P) -> Result<Atoms> {
     parse_gro_reader(reader)
 }
 
-/// Parse GRO from a buffered reader
+/// Parse GRO from a buffered reader. Populates `residue_indices` (with
+/// GROMACS's 5-digit residue-number wraparound unwound into a continuous
+/// series), `velocities` when every atom line carries them, and
+/// `box_vectors` from the trailing box line, in addition to coordinates
+/// and elements.
 pub fn parse_gro_reader<R: BufRead>(reader: R) -> Result<Atoms> {
     let mut lines = reader.lines();
 
@@ -47,9 +58,18 @@ pub fn parse_gro_reader<R: BufRead>(reader: R) -> Result<Atoms> {
         .map_err(|_| AxiomError::ParseError("Invalid atom count".to_string()))?;
 
     let mut atoms = Atoms::with_capacity(num_atoms);
+    let mut residue_indices = Vec::with_capacity(num_atoms);
+    let mut prev_raw_resid: Option<u32> = None;
+    let mut resid_wraps: u32 = 0;
+
+    let mut vx = Vec::with_capacity(num_atoms);
+    let mut vy = Vec::with_capacity(num_atoms);
+    let mut vz = Vec::with_capacity(num_atoms);
+    let mut has_velocities = num_atoms > 0;
 
-    // Parse atom lines
-    for (line_num, line_result) in lines.enumerate().take(num_atoms) {
+    // Parse atom lines (keep `lines` itself, via `by_ref`, so the trailing
+    // box-vectors line is still readable afterward).
+    for (line_num, line_result) in lines.by_ref().enumerate().take(num_atoms) {
         let line = line_result?;
 
         // GRO format uses fixed-width columns
@@ -60,6 +80,18 @@ pub fn parse_gro_reader<R: BufRead>(reader: R) -> Result<Atoms> {
             )));
         }
 
+        // Extract residue number (columns 1-5), unwrapping GROMACS's
+        // modulo-100000 rollover by watching for a decrease relative to
+        // the previous atom's residue number.
+        let raw_resid: u32 = line.get(0..5).and_then(|s| s.trim().parse().ok()).unwrap_or(0);
+        if let Some(prev) = prev_raw_resid {
+            if raw_resid < prev {
+                resid_wraps += 1;
+            }
+        }
+        prev_raw_resid = Some(raw_resid);
+        residue_indices.push(raw_resid + resid_wraps * RESIDUE_NUMBER_WRAP);
+
         // Extract atom name (columns 11-15)
         let atom_name = line.get(10..15).unwrap_or("").trim();
 
@@ -91,6 +123,25 @@ pub fn parse_gro_reader<R: BufRead>(reader: R) -> Result<Atoms> {
         let element = atom_name_to_element(atom_name);
 
         atoms.push(x, y, z, element);
+
+        // Extract optional velocities (columns 45-52, 53-60, 61-68,
+        // nm/ps); absent on any atom disables velocities for the whole
+        // file, since GRO either has them for every atom or none.
+        if has_velocities && line.len() >= 68 {
+            let parse_col = |range: std::ops::Range<usize>| -> Option<f32> {
+                line.get(range).and_then(|s| s.trim().parse::<f32>().ok())
+            };
+            match (parse_col(44..52), parse_col(52..60), parse_col(60..68)) {
+                (Some(a), Some(b), Some(c)) => {
+                    vx.push(a);
+                    vy.push(b);
+                    vz.push(c);
+                }
+                _ => has_velocities = false,
+            }
+        } else {
+            has_velocities = false;
+        }
     }
 
     if atoms.len() != num_atoms {
@@ -101,47 +152,32 @@ pub fn parse_gro_reader<R: BufRead>(reader: R) -> Result<Atoms> {
         )));
     }
 
-    Ok(atoms)
-}
+    atoms.residue_indices = Some(residue_indices);
 
-/// Infer atomic number from GROMACS atom name
-///
-/// Common GROMACS atom names:
-/// - C, CA, CB, CG, CD, CE, CZ -> Carbon
-/// - N, NA, NB, NH, NZ -> Nitrogen
-/// - O, OA, OW, OH -> Oxygen
-/// - H, H1, H2, HW, HA -> Hydrogen
-/// - S, SH -> Sulfur
-fn atom_name_to_element(name: &str) -> u8 {
-    if name.is_empty() {
-        return 0;
+    if has_velocities {
+        atoms.velocities = Some(
+            vx.into_iter().zip(vy).zip(vz)
+                .map(|((x, y), z)| [x, y, z])
+                .collect(),
+        );
     }
 
-    // Check for multi-character patterns first (ions and special atoms)
-    match name {
-        n if n.starts_with("CL") => return 17, // Chlorine
-        n if n.starts_with("FE") => return 26, // Iron
-        n if n.starts_with("ZN") => return 30, // Zinc
-        n if n.starts_with("MG") => return 12, // Magnesium
-        n if n.starts_with("MN") => return 25, // Manganese
-        _ => {}
+    // Trailing box-vectors line (3 or 9 whitespace-separated nm values).
+    if let Some(box_line) = lines.next() {
+        atoms.box_vectors = parse_box_line(&box_line?);
     }
 
-    // Fall back to first character
-    let first = name.chars().next().unwrap();
-
-    match first {
-        'H' => 1,  // Hydrogen
-        'C' => 6,  // Carbon
-        'N' => 7,  // Nitrogen
-        'O' => 8,  // Oxygen
-        'S' => 16, // Sulfur
-        'P' => 15, // Phosphorus
-        'F' => 9,  // Fluorine
-        'K' => 19, // Potassium
-        'Z' => 30, // Zinc (default)
-        _ => 0, // Unknown
-    }
+    Ok(atoms)
+}
+
+/// Infer atomic number from a GROMACS atom name. GRO carries none of PDB's
+/// column convention for disambiguating elements, so this delegates to
+/// the shared `elements::element_from_atom_name_token` heuristic: known
+/// unambiguous ions (Cl, Fe, Zn, Mg, Mn, Br) resolve as two-letter
+/// symbols, digit-prefixed hydrogens ("1HB2") are handled by skipping the
+/// leading digit, and everything else falls back to its first letter.
+pub(crate) fn atom_name_to_element(name: &str) -> u8 {
+    crate::elements::element_from_atom_name_token(name).unwrap_or(0)
 }
 
 #[cfg(test)]
@@ -174,6 +210,13 @@ Water molecule
         assert_eq!(atoms.element(0), Some(8));  // O
         assert_eq!(atoms.element(1), Some(1));  // H
         assert_eq!(atoms.element(2), Some(1));  // H
+
+        assert_eq!(atoms.residue_indices, Some(vec![1, 1, 1]));
+        assert_eq!(atoms.box_vectors, Some([
+            [10.0, 0.0, 0.0],
+            [0.0, 10.0, 0.0],
+            [0.0, 0.0, 10.0],
+        ]));
     }
 
     #[test]
@@ -198,6 +241,42 @@ Protein fragment
         assert_eq!(atoms.element(1), Some(6));  // C (CA)
         assert_eq!(atoms.element(2), Some(6));  // C
         assert_eq!(atoms.element(3), Some(8));  // O
+        assert_eq!(atoms.residue_indices, Some(vec![1, 1, 1, 1, 2]));
+    }
+
+    #[test]
+    fn test_parse_gro_with_velocities() {
+        let gro_data = "\
+Water molecule with velocities
+    2
+    1WAT     OW    1   0.126   0.126   0.126  0.1000  0.2000 -0.1000
+    1WAT    HW1    2   0.190   0.126   0.126  0.0500  0.1000  0.0000
+   1.0   1.0   1.0
+";
+        let cursor = Cursor::new(gro_data);
+        let atoms = parse_gro_reader(BufReader::new(cursor)).unwrap();
+
+        let velocities = atoms.velocities.expect("expected velocities");
+        assert_eq!(velocities[0], [0.1, 0.2, -0.1]);
+        assert_eq!(velocities[1], [0.05, 0.1, 0.0]);
+    }
+
+    #[test]
+    fn test_parse_gro_residue_number_wraparound() {
+        // A residue number that wraps from 99999 back to 0 should be
+        // reconstructed as 100000, continuing the series rather than
+        // restarting it.
+        let gro_data = "\
+Large system
+    2
+99999WAT     OW    1   0.126   0.126   0.126
+    0WAT    HW1    2   0.190   0.126   0.126
+   1.0   1.0   1.0
+";
+        let cursor = Cursor::new(gro_data);
+        let atoms = parse_gro_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.residue_indices, Some(vec![99999, 100000]));
     }
 
     #[test]
@@ -226,5 +305,13 @@ Title
         assert_eq!(atom_name_to_element("S"), 16);
         assert_eq!(atom_name_to_element("MG"), 12);
         assert_eq!(atom_name_to_element("CL"), 17);
+        assert_eq!(atom_name_to_element("BR"), 35);
+    }
+
+    #[test]
+    fn test_atom_name_to_element_digit_prefixed_hydrogen() {
+        // United-force-field hydrogen numbering, e.g. "1HB2" on a methyl.
+        assert_eq!(atom_name_to_element("1HB2"), 1);
+        assert_eq!(atom_name_to_element("2HG1"), 1);
     }
 }
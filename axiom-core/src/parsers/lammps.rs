@@ -8,27 +8,52 @@ use crate::atoms::{Atoms, Bonds};
 use crate::errors::{AxiomError, Result};
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::Path;
 
-/// Parse LAMMPS file (auto-detects dump vs data format)
-pub fn parse_lammps<P: AsRef<Path>>(path: P) -> Result<Atoms> {
-    let file = File::open(path.as_ref())
-        .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
-    let mut reader = BufReader::new(file);
+/// Magic-number prefixes for the compressed containers LAMMPS dumps are
+/// commonly shipped in. Checked in order against the file's first 4 bytes.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const BZIP2_MAGIC: [u8; 3] = [0x42, 0x5a, 0x68];
+
+/// Open a file for LAMMPS parsing, transparently unwrapping gzip/zstd/bzip2
+/// compression when the leading bytes match one of those containers' magic
+/// numbers. The peek seeks back to the start afterward, so the uncompressed
+/// path sees the stream exactly as if no sniffing had happened.
+fn open_decompressed<P: AsRef<Path>>(path: P) -> Result<Box<dyn BufRead>> {
+    let path = path.as_ref();
+    let mut file = File::open(path)
+        .map_err(|_| AxiomError::FileNotFound(path.display().to_string()))?;
+
+    let mut magic = [0u8; 4];
+    let n = file.read(&mut magic)?;
+    file.seek(SeekFrom::Start(0))?;
+
+    if n >= GZIP_MAGIC.len() && magic[..GZIP_MAGIC.len()] == GZIP_MAGIC {
+        Ok(Box::new(BufReader::new(flate2::read::GzDecoder::new(file))))
+    } else if n >= ZSTD_MAGIC.len() && magic[..ZSTD_MAGIC.len()] == ZSTD_MAGIC {
+        let decoder = zstd::Decoder::new(file)
+            .map_err(|e| AxiomError::ParseError(format!("Failed to initialize zstd decoder: {}", e)))?;
+        Ok(Box::new(BufReader::new(decoder)))
+    } else if n >= BZIP2_MAGIC.len() && magic[..BZIP2_MAGIC.len()] == BZIP2_MAGIC {
+        Ok(Box::new(BufReader::new(bzip2::read::BzDecoder::new(file))))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
 
-    // Read first line to detect format
-    let mut first_line = String::new();
-    reader.read_line(&mut first_line)
-        .map_err(|e| AxiomError::ParseError(format!("Failed to read file: {}", e)))?;
+/// Parse LAMMPS file (auto-detects dump vs data format). Transparently
+/// decompresses `.gz`/`.zst`/`.bz2` files - see `open_decompressed`.
+pub fn parse_lammps<P: AsRef<Path>>(path: P) -> Result<Atoms> {
+    let mut reader = open_decompressed(path.as_ref())?;
 
-    // Reset reader
-    drop(reader);
-    let file = File::open(path.as_ref())
-        .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
-    let reader = BufReader::new(file);
+    // Peek at the first line via the buffered reader's internal buffer
+    // (no `consume`, so nothing is read off the stream) to detect the
+    // format without disturbing it for whichever parser runs next.
+    let first_line_is_dump = reader.fill_buf()?.starts_with(b"ITEM: TIMESTEP");
 
-    if first_line.starts_with("ITEM: TIMESTEP") {
+    if first_line_is_dump {
         parse_lammps_dump_reader(reader)
     } else {
         // Assume data file format
@@ -36,17 +61,66 @@ pub fn parse_lammps<P: AsRef<Path>>(path: P) -> Result<Atoms> {
     }
 }
 
-/// Parse LAMMPS dump file
-///
-/// Supports "atom" and "custom" dump styles with atomic coordinates.
-/// Currently reads only the first frame of a trajectory.
-pub fn parse_lammps_dump_reader<R: BufRead>(reader: R) -> Result<Atoms> {
+/// A LAMMPS dump trajectory: an ordered sequence of per-frame snapshots,
+/// each its own fully independent `Atoms`, alongside the timestep each was
+/// written at. Unlike `crate::trajectory::Trajectory` (one shared topology
+/// plus per-frame coordinates), frames here don't share a fixed atom count
+/// - LAMMPS dumps from grand-canonical or reactive runs routinely insert or
+/// delete atoms between frames, so there's no stable identity to share.
+#[derive(Debug, Clone)]
+pub struct LammpsTrajectory {
+    pub frames: Vec<Atoms>,
+    pub timesteps: Vec<u64>,
+}
+
+impl LammpsTrajectory {
+    /// Number of frames
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Check if the trajectory has no frames
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// Parse a LAMMPS dump file (auto-decompressing, see `open_decompressed`)
+/// into a full multi-frame trajectory.
+pub fn parse_lammps_dump_trajectory<P: AsRef<Path>>(path: P) -> Result<LammpsTrajectory> {
+    parse_lammps_dump_trajectory_reader(open_decompressed(path)?)
+}
+
+/// Parse every `ITEM: TIMESTEP` block in `reader` into a `LammpsTrajectory`.
+/// Frames may differ in atom count from one another (see `LammpsTrajectory`).
+pub fn parse_lammps_dump_trajectory_reader<R: BufRead>(reader: R) -> Result<LammpsTrajectory> {
     let mut lines = reader.lines();
-    let mut atoms = Atoms::new();
+    let mut frames = Vec::new();
+    let mut timesteps = Vec::new();
+
+    while let Some((timestep, atoms)) = parse_one_lammps_dump_frame(&mut lines)? {
+        timesteps.push(timestep);
+        frames.push(atoms);
+    }
+
+    if frames.is_empty() {
+        return Err(AxiomError::ParseError("Empty file".to_string()));
+    }
+
+    Ok(LammpsTrajectory { frames, timesteps })
+}
 
-    // Parse header
+/// Parse one `ITEM: TIMESTEP` block (header, atom count, box, ATOMS table)
+/// from `lines`, advancing past it. Returns `Ok(None)` once there's nothing
+/// left to read (the normal end of the trajectory).
+fn parse_one_lammps_dump_frame<R: BufRead>(
+    lines: &mut std::io::Lines<R>,
+) -> Result<Option<(u64, Atoms)>> {
     // Line 1: ITEM: TIMESTEP
-    let line1 = lines.next().ok_or_else(|| AxiomError::ParseError("Empty file".to_string()))??;
+    let line1 = match lines.next() {
+        Some(line) => line?,
+        None => return Ok(None),
+    };
     if !line1.starts_with("ITEM: TIMESTEP") {
         return Err(AxiomError::ParseError(format!(
             "Expected 'ITEM: TIMESTEP', got: {}",
@@ -54,8 +128,11 @@ pub fn parse_lammps_dump_reader<R: BufRead>(reader: R) -> Result<Atoms> {
         )));
     }
 
-    // Line 2: timestep value (skip)
-    lines.next();
+    // Line 2: timestep value
+    let timestep: u64 = lines.next().ok_or_else(|| AxiomError::ParseError("Missing timestep value".to_string()))??
+        .trim()
+        .parse()
+        .map_err(|_| AxiomError::ParseError("Invalid timestep value".to_string()))?;
 
     // Line 3: ITEM: NUMBER OF ATOMS
     let line3 = lines.next().ok_or_else(|| AxiomError::ParseError("Missing number of atoms".to_string()))??;
@@ -72,9 +149,10 @@ pub fn parse_lammps_dump_reader<R: BufRead>(reader: R) -> Result<Atoms> {
         .parse()
         .map_err(|_| AxiomError::ParseError("Invalid atom count".to_string()))?;
 
+    let mut atoms = Atoms::new();
     atoms.reserve(num_atoms);
 
-    // Line 5: ITEM: BOX BOUNDS
+    // Line 5: ITEM: BOX BOUNDS [xy xz yz] <bound-style x2 or x3>
     let line5 = lines.next().ok_or_else(|| AxiomError::ParseError("Missing box bounds".to_string()))??;
     if !line5.starts_with("ITEM: BOX BOUNDS") {
         return Err(AxiomError::ParseError(format!(
@@ -82,11 +160,19 @@ pub fn parse_lammps_dump_reader<R: BufRead>(reader: R) -> Result<Atoms> {
             line5
         )));
     }
+    let triclinic = line5.contains("xy xz yz");
+    let periodic_flags: Vec<&str> = line5
+        .trim_start_matches("ITEM: BOX BOUNDS")
+        .split_whitespace()
+        .filter(|tok| *tok != "xy" && *tok != "xz" && *tok != "yz")
+        .collect();
 
-    // Lines 6-8: box bounds (skip for now)
-    lines.next();
-    lines.next();
-    lines.next();
+    // Lines 6-8: box bounds, 2 numbers per axis (+ a tilt factor when triclinic)
+    let x_bound = parse_box_bound_line(lines, "x")?;
+    let y_bound = parse_box_bound_line(lines, "y")?;
+    let z_bound = parse_box_bound_line(lines, "z")?;
+
+    let cell = dump_box_bounds_to_cell(&x_bound, &y_bound, &z_bound, triclinic)?;
 
     // Line 9: ITEM: ATOMS ...
     let atoms_header = lines.next().ok_or_else(|| AxiomError::ParseError("Missing ATOMS section".to_string()))??;
@@ -114,50 +200,229 @@ pub fn parse_lammps_dump_reader<R: BufRead>(reader: R) -> Result<Atoms> {
     let type_col = columns.iter().position(|&c| c == "type")
         .ok_or_else(|| AxiomError::ParseError("No type column found".to_string()))?;
 
-    // Parse atom data
-    for (line_num, line_result) in lines.enumerate() {
-        let line = line_result?;
+    // Parse exactly num_atoms atom lines - not "until the next blank line",
+    // so a trajectory file with back-to-back frames (no blank separator)
+    // stops in the right place for the next frame to pick up.
+    while atoms.len() < num_atoms {
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
         if line.trim().is_empty() {
-            break; // End of current frame
+            continue;
         }
 
         let parts: Vec<&str> = line.split_whitespace().collect();
 
         if parts.len() <= x_col.max(y_col).max(z_col).max(type_col) {
             return Err(AxiomError::ParseError(format!(
-                "Line {}: insufficient columns",
-                line_num + 10
+                "Timestep {}: insufficient columns in atom line",
+                timestep
             )));
         }
 
         let x: f32 = parts[x_col]
             .parse()
-            .map_err(|_| AxiomError::ParseError(format!("Invalid x coordinate on line {}", line_num + 10)))?;
+            .map_err(|_| AxiomError::ParseError(format!("Invalid x coordinate at timestep {}", timestep)))?;
         let y: f32 = parts[y_col]
             .parse()
-            .map_err(|_| AxiomError::ParseError(format!("Invalid y coordinate on line {}", line_num + 10)))?;
+            .map_err(|_| AxiomError::ParseError(format!("Invalid y coordinate at timestep {}", timestep)))?;
         let z: f32 = parts[z_col]
             .parse()
-            .map_err(|_| AxiomError::ParseError(format!("Invalid z coordinate on line {}", line_num + 10)))?;
+            .map_err(|_| AxiomError::ParseError(format!("Invalid z coordinate at timestep {}", timestep)))?;
 
         // LAMMPS type is an integer (1, 2, 3...)
         // We'll map it directly to element for now (user can override)
         let atom_type: u8 = parts[type_col]
             .parse()
-            .map_err(|_| AxiomError::ParseError(format!("Invalid type on line {}", line_num + 10)))?;
+            .map_err(|_| AxiomError::ParseError(format!("Invalid type at timestep {}", timestep)))?;
 
         atoms.push(x, y, z, atom_type);
     }
 
     if atoms.len() != num_atoms {
         return Err(AxiomError::ParseError(format!(
-            "Expected {} atoms, found {}",
+            "Timestep {}: expected {} atoms, found {}",
+            timestep,
             num_atoms,
             atoms.len()
         )));
     }
 
-    Ok(atoms)
+    atoms.set_periodic_box(cell);
+    atoms.set_periodic(periodic_flags.iter().all(|&flag| flag == "pp"));
+
+    Ok(Some((timestep, atoms)))
+}
+
+/// Parse a LAMMPS data-file box-bounds line (`lo hi xlo xhi` etc - the axis
+/// words are just labels, the first two tokens are the lo/hi values).
+fn parse_data_box_bound_pair(trimmed: &str) -> Option<(f32, f32)> {
+    let mut parts = trimmed.split_whitespace();
+    let lo = parts.next()?.parse().ok()?;
+    let hi = parts.next()?.parse().ok()?;
+    Some((lo, hi))
+}
+
+/// Parse a LAMMPS data-file triclinic tilt line (`xy xz yz xy xz yz`).
+fn parse_data_box_tilt(trimmed: &str) -> Option<(f32, f32, f32)> {
+    let mut parts = trimmed.split_whitespace();
+    let xy = parts.next()?.parse().ok()?;
+    let xz = parts.next()?.parse().ok()?;
+    let yz = parts.next()?.parse().ok()?;
+    Some((xy, xz, yz))
+}
+
+/// Build the `a/b/c` cell matrix from data-file lo/hi bounds and an
+/// optional triclinic tilt (defaults to orthogonal when absent). Unlike the
+/// dump format's `ITEM: BOX BOUNDS`, a data file's bounds are the true box
+/// bounds already - no bound/tilt reconstruction needed.
+fn data_box_bounds_to_cell(
+    x_bounds: (f32, f32),
+    y_bounds: (f32, f32),
+    z_bounds: (f32, f32),
+    tilt: Option<(f32, f32, f32)>,
+) -> [[f32; 3]; 3] {
+    let (xy, xz, yz) = tilt.unwrap_or((0.0, 0.0, 0.0));
+    [
+        [x_bounds.1 - x_bounds.0, xy, xz],
+        [0.0, y_bounds.1 - y_bounds.0, yz],
+        [0.0, 0.0, z_bounds.1 - z_bounds.0],
+    ]
+}
+
+/// Read and parse one of the three `ITEM: BOX BOUNDS` data lines (2 numbers,
+/// or 3 when triclinic: `lo hi [tilt]`).
+fn parse_box_bound_line<R: BufRead>(lines: &mut std::io::Lines<R>, label: &str) -> Result<Vec<f32>> {
+    let line = lines.next().ok_or_else(|| AxiomError::ParseError(format!("Missing {} box bound", label)))??;
+    line.split_whitespace()
+        .map(|tok| {
+            tok.parse::<f32>()
+                .map_err(|_| AxiomError::ParseError(format!("Invalid {} box bound: {}", label, line)))
+        })
+        .collect()
+}
+
+/// Convert LAMMPS's `ITEM: BOX BOUNDS` convention - `(xlo_bound, xhi_bound,
+/// [xy])` per axis, where the tilt factors are folded into the bounds - into
+/// proper `a/b/c` lattice vectors. See the LAMMPS manual's "Triclinic boxes"
+/// page for the bound/tilt relationship this undoes.
+fn dump_box_bounds_to_cell(
+    x_bound: &[f32],
+    y_bound: &[f32],
+    z_bound: &[f32],
+    triclinic: bool,
+) -> Result<[[f32; 3]; 3]> {
+    if x_bound.len() < 2 || y_bound.len() < 2 || z_bound.len() < 2 {
+        return Err(AxiomError::ParseError("Box bounds line missing lo/hi values".to_string()));
+    }
+
+    let (xy, xz, yz) = if triclinic {
+        if x_bound.len() < 3 || y_bound.len() < 3 {
+            return Err(AxiomError::ParseError("Triclinic box bounds missing tilt factors".to_string()));
+        }
+        (x_bound[2], y_bound[2], z_bound.get(2).copied().unwrap_or(0.0))
+    } else {
+        (0.0, 0.0, 0.0)
+    };
+
+    let xlo = x_bound[0] - 0.0f32.min(xy).min(xz).min(xy + xz);
+    let xhi = x_bound[1] - 0.0f32.max(xy).max(xz).max(xy + xz);
+    let ylo = y_bound[0] - 0.0f32.min(yz);
+    let yhi = y_bound[1] - 0.0f32.max(yz);
+    let zlo = z_bound[0];
+    let zhi = z_bound[1];
+
+    // UnitCell convention: matrix[row] = [a_<row>, b_<row>, c_<row>], i.e.
+    // each row is a Cartesian component and each column a lattice vector.
+    // a = (xhi-xlo, 0, 0), b = (xy, yhi-ylo, 0), c = (xz, yz, zhi-zlo).
+    Ok([
+        [xhi - xlo, xy, xz],
+        [0.0, yhi - ylo, yz],
+        [0.0, 0.0, zhi - zlo],
+    ])
+}
+
+/// Parse LAMMPS dump file
+///
+/// Supports "atom" and "custom" dump styles with atomic coordinates. Reads
+/// the whole trajectory via `parse_lammps_dump_trajectory_reader` and
+/// returns just the first frame, for callers that only care about a single
+/// snapshot.
+pub fn parse_lammps_dump_reader<R: BufRead>(reader: R) -> Result<Atoms> {
+    let trajectory = parse_lammps_dump_trajectory_reader(reader)?;
+    trajectory.frames.into_iter().next().ok_or_else(|| AxiomError::ParseError("Empty file".to_string()))
+}
+
+/// Read `type_id mass` rows out of a `Masses` section, stopping (without
+/// consuming) at the next section header or EOF so the caller's own loop
+/// can dispatch on that header next.
+fn parse_masses_section<R: BufRead>(
+    lines: &mut std::iter::Peekable<std::io::Lines<R>>,
+) -> Result<HashMap<u32, f32>> {
+    let mut masses = HashMap::new();
+    loop {
+        let peeked = match lines.peek() {
+            Some(Ok(l)) => l.clone(),
+            Some(Err(_)) => break,
+            None => break,
+        };
+
+        let trimmed = peeked.trim();
+        if trimmed.is_empty() {
+            lines.next();
+            continue;
+        }
+        if is_data_section_header(trimmed) {
+            break;
+        }
+
+        let parts: Vec<&str> = trimmed.split_whitespace().collect();
+        if let (Some(id_str), Some(mass_str)) = (parts.first(), parts.get(1)) {
+            if let (Ok(type_id), Ok(mass)) = (id_str.parse::<u32>(), mass_str.parse::<f32>()) {
+                masses.insert(type_id, mass);
+            }
+        }
+        lines.next();
+    }
+    Ok(masses)
+}
+
+/// Section headers that can follow the counts/box-bounds preamble of a
+/// LAMMPS data file.
+fn is_data_section_header(trimmed: &str) -> bool {
+    trimmed.starts_with("Masses")
+        || trimmed.starts_with("Pair Coeffs")
+        || trimmed.starts_with("Bond Coeffs")
+        || trimmed.starts_with("Angle Coeffs")
+        || trimmed.starts_with("Dihedral Coeffs")
+        || trimmed.starts_with("Improper Coeffs")
+        || trimmed.starts_with("Atoms")
+        || trimmed.starts_with("Velocities")
+        || trimmed.starts_with("Bonds")
+        || trimmed.starts_with("Angles")
+        || trimmed.starts_with("Dihedrals")
+        || trimmed.starts_with("Impropers")
+}
+
+/// Resolve a LAMMPS integer atom type to a real element (atomic number).
+/// `type_overrides` wins when present (for coarse-grained beads where mass
+/// alone can't disambiguate); otherwise the type's mass, if we saw one in a
+/// `Masses` section, is matched against the periodic table within 0.5 amu.
+/// Falls back to the raw type id (today's placeholder behavior) when
+/// neither is available.
+fn resolve_element(
+    atom_type: u32,
+    masses: &HashMap<u32, f32>,
+    type_overrides: Option<&HashMap<u32, u8>>,
+) -> u8 {
+    if let Some(&element) = type_overrides.and_then(|overrides| overrides.get(&atom_type)) {
+        return element;
+    }
+    masses
+        .get(&atom_type)
+        .and_then(|&mass| crate::elements::atomic_number_from_mass(mass, 0.5))
+        .unwrap_or(atom_type as u8)
 }
 
 /// Parse LAMMPS data file
@@ -167,6 +432,16 @@ pub fn parse_lammps_dump_reader<R: BufRead>(reader: R) -> Result<Atoms> {
 /// - Bonds, angles, dihedrals, impropers
 /// - Force field parameters (masses, pair coeffs, bond coeffs, etc.)
 pub fn parse_lammps_data_reader<R: BufRead>(reader: R) -> Result<Atoms> {
+    parse_lammps_data_reader_with_overrides(reader, None)
+}
+
+/// Same as `parse_lammps_data_reader`, but lets the caller force specific
+/// LAMMPS atom types to a chosen element via `type_overrides`, taking
+/// priority over the `Masses`-derived resolution (see `resolve_element`).
+pub fn parse_lammps_data_reader_with_overrides<R: BufRead>(
+    reader: R,
+    type_overrides: Option<&HashMap<u32, u8>>,
+) -> Result<Atoms> {
     let mut lines = reader.lines().peekable();
     let mut atoms = Atoms::new();
 
@@ -177,10 +452,19 @@ pub fn parse_lammps_data_reader<R: BufRead>(reader: R) -> Result<Atoms> {
     let mut num_atoms = 0;
     let mut _num_bonds = 0;
     let mut _num_atom_types = 0;
-    let mut _masses: HashMap<u32, f32> = HashMap::new();
+    let mut masses: HashMap<u32, f32> = HashMap::new();
+    let mut x_bounds: Option<(f32, f32)> = None;
+    let mut y_bounds: Option<(f32, f32)> = None;
+    let mut z_bounds: Option<(f32, f32)> = None;
+    let mut tilt: Option<(f32, f32, f32)> = None;
 
     // Parse counts section
     loop {
+        if let Some(Ok(next)) = lines.peek() {
+            if is_data_section_header(next.trim()) {
+                break; // leave the header for the section loop below
+            }
+        }
         let line = match lines.next() {
             Some(Ok(l)) => l,
             Some(Err(e)) => return Err(AxiomError::ParseError(format!("Read error: {}", e))),
@@ -203,13 +487,27 @@ pub fn parse_lammps_data_reader<R: BufRead>(reader: R) -> Result<Atoms> {
             || trimmed.starts_with("Bonds")
             || trimmed.starts_with("Angles")
             || trimmed.starts_with("Dihedrals")
-            || trimmed.starts_with("Impropers")
-            || trimmed.contains("xlo xhi")
-            || trimmed.contains("ylo yhi")
-            || trimmed.contains("zlo zhi") {
+            || trimmed.starts_with("Impropers") {
             break;
         }
 
+        if trimmed.contains("xlo xhi") {
+            x_bounds = parse_data_box_bound_pair(trimmed);
+            continue;
+        }
+        if trimmed.contains("ylo yhi") {
+            y_bounds = parse_data_box_bound_pair(trimmed);
+            continue;
+        }
+        if trimmed.contains("zlo zhi") {
+            z_bounds = parse_data_box_bound_pair(trimmed);
+            continue;
+        }
+        if trimmed.ends_with("xy xz yz") {
+            tilt = parse_data_box_tilt(trimmed);
+            continue;
+        }
+
         let parts: Vec<&str> = trimmed.split_whitespace().collect();
         if parts.len() >= 2 {
             if parts[1] == "atoms" {
@@ -228,6 +526,11 @@ pub fn parse_lammps_data_reader<R: BufRead>(reader: R) -> Result<Atoms> {
 
     atoms.reserve(num_atoms);
 
+    // Box bounds are optional - a non-periodic data file still parses fine.
+    if let (Some(x), Some(y), Some(z)) = (x_bounds, y_bounds, z_bounds) {
+        atoms.set_periodic_box(data_box_bounds_to_cell(x, y, z, tilt));
+    }
+
     // Initialize optional vectors for charges, types, molecule IDs
     let mut charges = Vec::with_capacity(num_atoms);
     let mut atom_types = Vec::with_capacity(num_atoms);
@@ -250,7 +553,7 @@ pub fn parse_lammps_data_reader<R: BufRead>(reader: R) -> Result<Atoms> {
 
         // Check for Masses section
         if trimmed.starts_with("Masses") {
-            // Skip masses for now (could parse if needed)
+            masses = parse_masses_section(&mut lines)?;
             continue;
         }
 
@@ -305,7 +608,8 @@ pub fn parse_lammps_data_reader<R: BufRead>(reader: R) -> Result<Atoms> {
                     .map_err(|_| AxiomError::ParseError(format!("Invalid z: {}", parts[6])))?;
 
                 // Store atom data
-                atoms.push(x, y, z, atom_type as u8); // Use atom_type as element (placeholder)
+                let element = resolve_element(atom_type, &masses, type_overrides);
+                atoms.push(x, y, z, element);
                 charges.push(charge);
                 atom_types.push(atom_type);
                 molecule_ids.push(molecule_id);
@@ -339,14 +643,34 @@ pub fn parse_lammps_data_reader<R: BufRead>(reader: R) -> Result<Atoms> {
 
 /// Parse LAMMPS data file and extract bonds
 pub fn parse_lammps_data_with_bonds<P: AsRef<Path>>(path: P) -> Result<(Atoms, Bonds)> {
+    parse_lammps_data_with_bonds_with_overrides(path, None)
+}
+
+/// Same as `parse_lammps_data_with_bonds`, but lets the caller force
+/// specific LAMMPS atom types to a chosen element via `type_overrides`, see
+/// `resolve_element`.
+pub fn parse_lammps_data_with_bonds_with_overrides<P: AsRef<Path>>(
+    path: P,
+    type_overrides: Option<&HashMap<u32, u8>>,
+) -> Result<(Atoms, Bonds)> {
     let file = File::open(path.as_ref())
         .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
     let reader = BufReader::new(file);
-    parse_lammps_data_with_bonds_reader(reader)
+    parse_lammps_data_with_bonds_reader_with_overrides(reader, type_overrides)
 }
 
 /// Parse LAMMPS data file and extract bonds from reader
 pub fn parse_lammps_data_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bonds)> {
+    parse_lammps_data_with_bonds_reader_with_overrides(reader, None)
+}
+
+/// Same as `parse_lammps_data_with_bonds_reader`, but lets the caller force
+/// specific LAMMPS atom types to a chosen element via `type_overrides`, taking
+/// priority over the `Masses`-derived resolution (see `resolve_element`).
+pub fn parse_lammps_data_with_bonds_reader_with_overrides<R: BufRead>(
+    reader: R,
+    type_overrides: Option<&HashMap<u32, u8>>,
+) -> Result<(Atoms, Bonds)> {
     let mut lines = reader.lines().peekable();
     let mut atoms = Atoms::new();
     let mut bonds = Bonds::new();
@@ -357,9 +681,19 @@ pub fn parse_lammps_data_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Ato
     // Initialize metadata
     let mut num_atoms = 0;
     let mut num_bonds = 0;
+    let mut masses: HashMap<u32, f32> = HashMap::new();
+    let mut x_bounds: Option<(f32, f32)> = None;
+    let mut y_bounds: Option<(f32, f32)> = None;
+    let mut z_bounds: Option<(f32, f32)> = None;
+    let mut tilt: Option<(f32, f32, f32)> = None;
 
     // Parse counts section
     loop {
+        if let Some(Ok(next)) = lines.peek() {
+            if is_data_section_header(next.trim()) {
+                break; // leave the header for the section loop below
+            }
+        }
         let line = match lines.next() {
             Some(Ok(l)) => l,
             Some(Err(e)) => return Err(AxiomError::ParseError(format!("Read error: {}", e))),
@@ -376,13 +710,27 @@ pub fn parse_lammps_data_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Ato
             || trimmed.starts_with("Pair Coeffs")
             || trimmed.starts_with("Bond Coeffs")
             || trimmed.starts_with("Atoms")
-            || trimmed.starts_with("Bonds")
-            || trimmed.contains("xlo xhi")
-            || trimmed.contains("ylo yhi")
-            || trimmed.contains("zlo zhi") {
+            || trimmed.starts_with("Bonds") {
             break;
         }
 
+        if trimmed.contains("xlo xhi") {
+            x_bounds = parse_data_box_bound_pair(trimmed);
+            continue;
+        }
+        if trimmed.contains("ylo yhi") {
+            y_bounds = parse_data_box_bound_pair(trimmed);
+            continue;
+        }
+        if trimmed.contains("zlo zhi") {
+            z_bounds = parse_data_box_bound_pair(trimmed);
+            continue;
+        }
+        if trimmed.ends_with("xy xz yz") {
+            tilt = parse_data_box_tilt(trimmed);
+            continue;
+        }
+
         let parts: Vec<&str> = trimmed.split_whitespace().collect();
         if parts.len() >= 2 {
             if parts[1] == "atoms" {
@@ -398,6 +746,11 @@ pub fn parse_lammps_data_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Ato
     }
 
     atoms.reserve(num_atoms);
+
+    // Box bounds are optional - a non-periodic data file still parses fine.
+    if let (Some(x), Some(y), Some(z)) = (x_bounds, y_bounds, z_bounds) {
+        atoms.set_periodic_box(data_box_bounds_to_cell(x, y, z, tilt));
+    }
     if num_bonds > 0 {
         bonds = Bonds::with_capacity(num_bonds);
     }
@@ -422,6 +775,12 @@ pub fn parse_lammps_data_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Ato
             continue;
         }
 
+        // Check for Masses section
+        if trimmed.starts_with("Masses") {
+            masses = parse_masses_section(&mut lines)?;
+            continue;
+        }
+
         // Check for Atoms section
         if trimmed.starts_with("Atoms") {
             // Next line might be blank, then atom data
@@ -467,7 +826,8 @@ pub fn parse_lammps_data_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Ato
                 let z: f32 = parts[6].parse()
                     .map_err(|_| AxiomError::ParseError(format!("Invalid z: {}", parts[6])))?;
 
-                atoms.push(x, y, z, atom_type as u8);
+                let element = resolve_element(atom_type, &masses, type_overrides);
+                atoms.push(x, y, z, element);
                 charges.push(charge);
                 atom_types.push(atom_type);
                 molecule_ids.push(molecule_id);
@@ -541,88 +901,980 @@ pub fn parse_lammps_data_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Ato
     Ok((atoms, bonds))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::Cursor;
+/// Full bonded connectivity extracted from a LAMMPS data file: bonds plus
+/// the angle/dihedral/improper terms most data files also define. Atom
+/// indices are 0-based (converted from LAMMPS's 1-based atom IDs, same as
+/// `Bonds`); the parallel `*_types` vectors carry each term's LAMMPS type id.
+#[derive(Debug, Clone)]
+pub struct Topology {
+    pub bonds: Bonds,
+    pub angles: Vec<[u32; 3]>,
+    pub angle_types: Vec<u32>,
+    pub dihedrals: Vec<[u32; 4]>,
+    pub dihedral_types: Vec<u32>,
+    pub impropers: Vec<[u32; 4]>,
+    pub improper_types: Vec<u32>,
+}
 
-    #[test]
-    fn test_parse_lammps_dump_simple() {
-        let lammps_data = "\
-ITEM: TIMESTEP
-0
-ITEM: NUMBER OF ATOMS
-3
-ITEM: BOX BOUNDS pp pp pp
-0.0 10.0
-0.0 10.0
-0.0 10.0
-ITEM: ATOMS id type x y z
-1 1 0.0 0.0 0.0
-2 2 1.0 1.0 1.0
-3 1 2.0 2.0 2.0
-";
-        let cursor = Cursor::new(lammps_data);
-        let atoms = parse_lammps_dump_reader(BufReader::new(cursor)).unwrap();
+impl Topology {
+    pub fn new() -> Self {
+        Topology {
+            bonds: Bonds::new(),
+            angles: Vec::new(),
+            angle_types: Vec::new(),
+            dihedrals: Vec::new(),
+            dihedral_types: Vec::new(),
+            impropers: Vec::new(),
+            improper_types: Vec::new(),
+        }
+    }
+}
 
-        assert_eq!(atoms.len(), 3);
-        assert_eq!(atoms.position(0), Some([0.0, 0.0, 0.0]));
-        assert_eq!(atoms.position(1), Some([1.0, 1.0, 1.0]));
-        assert_eq!(atoms.position(2), Some([2.0, 2.0, 2.0]));
-        assert_eq!(atoms.element(0), Some(1));
-        assert_eq!(atoms.element(1), Some(2));
+impl Default for Topology {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    #[test]
-    fn test_parse_lammps_data_simple() {
-        let lammps_data = "\
-LAMMPS data file
+/// Parse a LAMMPS data file's full bonded topology: `Bonds`, `Angles`,
+/// `Dihedrals`, and `Impropers`, alongside the `Atoms` section.
+pub fn parse_lammps_data_topology<P: AsRef<Path>>(path: P) -> Result<(Atoms, Topology)> {
+    parse_lammps_data_topology_with_overrides(path, None)
+}
 
-3 atoms
-2 atom types
+/// Same as `parse_lammps_data_topology`, but lets the caller force specific
+/// LAMMPS atom types to a chosen element via `type_overrides`, see
+/// `resolve_element`.
+pub fn parse_lammps_data_topology_with_overrides<P: AsRef<Path>>(
+    path: P,
+    type_overrides: Option<&HashMap<u32, u8>>,
+) -> Result<(Atoms, Topology)> {
+    let file = File::open(path.as_ref())
+        .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+    let reader = BufReader::new(file);
+    parse_lammps_data_topology_reader_with_overrides(reader, type_overrides)
+}
 
-0.0 10.0 xlo xhi
-0.0 10.0 ylo yhi
-0.0 10.0 zlo zhi
+/// Parse a LAMMPS data file's full bonded topology from a buffered reader.
+pub fn parse_lammps_data_topology_reader<R: BufRead>(reader: R) -> Result<(Atoms, Topology)> {
+    parse_lammps_data_topology_reader_with_overrides(reader, None)
+}
 
-Masses
+/// Same as `parse_lammps_data_topology_reader`, but lets the caller force
+/// specific LAMMPS atom types to a chosen element via `type_overrides`,
+/// taking priority over the `Masses`-derived resolution (see
+/// `resolve_element`).
+pub fn parse_lammps_data_topology_reader_with_overrides<R: BufRead>(
+    reader: R,
+    type_overrides: Option<&HashMap<u32, u8>>,
+) -> Result<(Atoms, Topology)> {
+    let mut lines = reader.lines().peekable();
+    let mut atoms = Atoms::new();
+    let mut topology = Topology::new();
 
-1 1.008
-2 12.011
+    // Parse header - first line is a comment
+    let _header = lines.next().ok_or_else(|| AxiomError::ParseError("Empty file".to_string()))??;
 
-Atoms # full
+    // Initialize metadata
+    let mut num_atoms = 0;
+    let mut num_bonds = 0;
+    let mut num_angles = 0;
+    let mut num_dihedrals = 0;
+    let mut num_impropers = 0;
+    let mut masses: HashMap<u32, f32> = HashMap::new();
+    let mut x_bounds: Option<(f32, f32)> = None;
+    let mut y_bounds: Option<(f32, f32)> = None;
+    let mut z_bounds: Option<(f32, f32)> = None;
+    let mut tilt: Option<(f32, f32, f32)> = None;
 
-1 1 1 0.5 0.0 0.0 0.0
-2 1 2 -0.5 1.0 1.0 1.0
-3 2 1 0.3 2.0 2.0 2.0
-";
-        let cursor = Cursor::new(lammps_data);
-        let atoms = parse_lammps_data_reader(BufReader::new(cursor)).unwrap();
+    // Parse counts section
+    loop {
+        if let Some(Ok(next)) = lines.peek() {
+            if is_data_section_header(next.trim()) {
+                break; // leave the header for the section loop below
+            }
+        }
+        let line = match lines.next() {
+            Some(Ok(l)) => l,
+            Some(Err(e)) => return Err(AxiomError::ParseError(format!("Read error: {}", e))),
+            None => break,
+        };
 
-        assert_eq!(atoms.len(), 3);
-        assert_eq!(atoms.position(0), Some([0.0, 0.0, 0.0]));
-        assert_eq!(atoms.position(1), Some([1.0, 1.0, 1.0]));
-        assert_eq!(atoms.position(2), Some([2.0, 2.0, 2.0]));
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
 
-        // Check charges
-        assert!(atoms.charges.is_some());
-        let charges = atoms.charges.as_ref().unwrap();
-        assert_eq!(charges[0], 0.5);
-        assert_eq!(charges[1], -0.5);
-        assert_eq!(charges[2], 0.3);
+        // Check if we've reached a section header
+        if trimmed.starts_with("Masses")
+            || trimmed.starts_with("Pair Coeffs")
+            || trimmed.starts_with("Bond Coeffs")
+            || trimmed.starts_with("Angle Coeffs")
+            || trimmed.starts_with("Dihedral Coeffs")
+            || trimmed.starts_with("Improper Coeffs")
+            || trimmed.starts_with("Atoms")
+            || trimmed.starts_with("Bonds")
+            || trimmed.starts_with("Angles")
+            || trimmed.starts_with("Dihedrals")
+            || trimmed.starts_with("Impropers") {
+            break;
+        }
 
-        // Check atom types
-        assert!(atoms.atom_types.is_some());
-        let types = atoms.atom_types.as_ref().unwrap();
-        assert_eq!(types[0], 1);
-        assert_eq!(types[1], 2);
-        assert_eq!(types[2], 1);
+        if trimmed.contains("xlo xhi") {
+            x_bounds = parse_data_box_bound_pair(trimmed);
+            continue;
+        }
+        if trimmed.contains("ylo yhi") {
+            y_bounds = parse_data_box_bound_pair(trimmed);
+            continue;
+        }
+        if trimmed.contains("zlo zhi") {
+            z_bounds = parse_data_box_bound_pair(trimmed);
+            continue;
+        }
+        if trimmed.ends_with("xy xz yz") {
+            tilt = parse_data_box_tilt(trimmed);
+            continue;
+        }
 
-        // Check molecule IDs
-        assert!(atoms.molecule_ids.is_some());
-        let mol_ids = atoms.molecule_ids.as_ref().unwrap();
-        assert_eq!(mol_ids[0], 1);
-        assert_eq!(mol_ids[1], 1);
-        assert_eq!(mol_ids[2], 2);
+        let parts: Vec<&str> = trimmed.split_whitespace().collect();
+        if parts.len() >= 2 {
+            if parts[1] == "atoms" {
+                num_atoms = parts[0].parse().unwrap_or(0);
+            } else if parts[1] == "bonds" {
+                num_bonds = parts[0].parse().unwrap_or(0);
+            } else if parts[1] == "angles" {
+                num_angles = parts[0].parse().unwrap_or(0);
+            } else if parts[1] == "dihedrals" {
+                num_dihedrals = parts[0].parse().unwrap_or(0);
+            } else if parts[1] == "impropers" {
+                num_impropers = parts[0].parse().unwrap_or(0);
+            }
+        }
+    }
+
+    if num_atoms == 0 {
+        return Err(AxiomError::ParseError("No atoms found in header".to_string()));
+    }
+
+    atoms.reserve(num_atoms);
+
+    // Box bounds are optional - a non-periodic data file still parses fine.
+    if let (Some(x), Some(y), Some(z)) = (x_bounds, y_bounds, z_bounds) {
+        atoms.set_periodic_box(data_box_bounds_to_cell(x, y, z, tilt));
+    }
+    if num_bonds > 0 {
+        topology.bonds = Bonds::with_capacity(num_bonds);
+    }
+    topology.angles.reserve(num_angles);
+    topology.angle_types.reserve(num_angles);
+    topology.dihedrals.reserve(num_dihedrals);
+    topology.dihedral_types.reserve(num_dihedrals);
+    topology.impropers.reserve(num_impropers);
+    topology.improper_types.reserve(num_impropers);
+
+    // Initialize optional vectors for charges, types, molecule IDs
+    let mut charges = Vec::with_capacity(num_atoms);
+    let mut atom_types = Vec::with_capacity(num_atoms);
+    let mut molecule_ids = Vec::with_capacity(num_atoms);
+    // Header line that ended the previous section's loop, dispatched next.
+    let mut pending_header: Option<String> = None;
+
+    // Parse sections
+    loop {
+        let line = match pending_header.take().map(Ok).or_else(|| lines.next()) {
+            Some(Ok(l)) => l,
+            Some(Err(e)) => return Err(AxiomError::ParseError(format!("Read error: {}", e))),
+            None => break,
+        };
+
+        let trimmed = line.trim();
+
+        // Skip empty lines
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        // Check for Masses section
+        if trimmed.starts_with("Masses") {
+            masses = parse_masses_section(&mut lines)?;
+            continue;
+        }
+
+        // Check for Atoms section
+        if trimmed.starts_with("Atoms") {
+            loop {
+                let atom_line = match lines.next() {
+                    Some(Ok(l)) => l,
+                    Some(Err(e)) => return Err(AxiomError::ParseError(format!("Read error: {}", e))),
+                    None => break,
+                };
+
+                let atom_trimmed = atom_line.trim();
+                if atom_trimmed.is_empty() {
+                    continue;
+                }
+
+                if atom_trimmed.starts_with("Velocities")
+                    || atom_trimmed.starts_with("Bonds")
+                    || atom_trimmed.starts_with("Angles")
+                    || atom_trimmed.starts_with("Dihedrals")
+                    || atom_trimmed.starts_with("Impropers") {
+                    pending_header = Some(atom_trimmed.to_string());
+                    break;
+                }
+
+                let parts: Vec<&str> = atom_trimmed.split_whitespace().collect();
+                if parts.len() < 7 {
+                    continue;
+                }
+
+                let _atom_id: u32 = parts[0].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom ID: {}", parts[0])))?;
+                let molecule_id: u32 = parts[1].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid molecule ID: {}", parts[1])))?;
+                let atom_type: u32 = parts[2].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom type: {}", parts[2])))?;
+                let charge: f32 = parts[3].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid charge: {}", parts[3])))?;
+                let x: f32 = parts[4].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid x: {}", parts[4])))?;
+                let y: f32 = parts[5].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid y: {}", parts[5])))?;
+                let z: f32 = parts[6].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid z: {}", parts[6])))?;
+
+                let element = resolve_element(atom_type, &masses, type_overrides);
+                atoms.push(x, y, z, element);
+                charges.push(charge);
+                atom_types.push(atom_type);
+                molecule_ids.push(molecule_id);
+            }
+
+            if !charges.is_empty() {
+                atoms.charges = Some(charges.clone());
+            }
+            if !atom_types.is_empty() {
+                atoms.atom_types = Some(atom_types.clone());
+            }
+            if !molecule_ids.is_empty() {
+                atoms.molecule_ids = Some(molecule_ids.clone());
+            }
+
+            continue; // Keep looking for Bonds/Angles/Dihedrals/Impropers
+        }
+
+        // Check for Bonds section
+        if trimmed.starts_with("Bonds") {
+            loop {
+                let bond_line = match lines.next() {
+                    Some(Ok(l)) => l,
+                    Some(Err(e)) => return Err(AxiomError::ParseError(format!("Read error: {}", e))),
+                    None => break,
+                };
+
+                let bond_trimmed = bond_line.trim();
+                if bond_trimmed.is_empty() {
+                    continue;
+                }
+
+                if bond_trimmed.starts_with("Angles")
+                    || bond_trimmed.starts_with("Dihedrals")
+                    || bond_trimmed.starts_with("Impropers")
+                    || bond_trimmed.starts_with("Velocities") {
+                    pending_header = Some(bond_trimmed.to_string());
+                    break;
+                }
+
+                let parts: Vec<&str> = bond_trimmed.split_whitespace().collect();
+                if parts.len() < 4 {
+                    continue;
+                }
+
+                let _bond_id: u32 = parts[0].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid bond ID: {}", parts[0])))?;
+                let _bond_type: u32 = parts[1].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid bond type: {}", parts[1])))?;
+                let atom1: u32 = parts[2].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom1: {}", parts[2])))?;
+                let atom2: u32 = parts[3].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom2: {}", parts[3])))?;
+
+                // CRITICAL: LAMMPS uses 1-based indexing, convert to 0-based
+                topology.bonds.push(atom1 - 1, atom2 - 1, 1); // Default to single bond
+            }
+
+            continue; // Keep looking for Angles/Dihedrals/Impropers
+        }
+
+        // Check for Angles section
+        if trimmed.starts_with("Angles") {
+            loop {
+                let angle_line = match lines.next() {
+                    Some(Ok(l)) => l,
+                    Some(Err(e)) => return Err(AxiomError::ParseError(format!("Read error: {}", e))),
+                    None => break,
+                };
+
+                let angle_trimmed = angle_line.trim();
+                if angle_trimmed.is_empty() {
+                    continue;
+                }
+
+                if angle_trimmed.starts_with("Dihedrals")
+                    || angle_trimmed.starts_with("Impropers")
+                    || angle_trimmed.starts_with("Velocities") {
+                    pending_header = Some(angle_trimmed.to_string());
+                    break;
+                }
+
+                let parts: Vec<&str> = angle_trimmed.split_whitespace().collect();
+                if parts.len() < 5 {
+                    continue;
+                }
+
+                // angle-ID angle-type atom1 atom2 atom3
+                let angle_type: u32 = parts[1].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid angle type: {}", parts[1])))?;
+                let atom1: u32 = parts[2].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom1: {}", parts[2])))?;
+                let atom2: u32 = parts[3].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom2: {}", parts[3])))?;
+                let atom3: u32 = parts[4].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom3: {}", parts[4])))?;
+
+                topology.angles.push([atom1 - 1, atom2 - 1, atom3 - 1]);
+                topology.angle_types.push(angle_type);
+            }
+
+            continue; // Keep looking for Dihedrals/Impropers
+        }
+
+        // Check for Dihedrals section
+        if trimmed.starts_with("Dihedrals") {
+            loop {
+                let dihedral_line = match lines.next() {
+                    Some(Ok(l)) => l,
+                    Some(Err(e)) => return Err(AxiomError::ParseError(format!("Read error: {}", e))),
+                    None => break,
+                };
+
+                let dihedral_trimmed = dihedral_line.trim();
+                if dihedral_trimmed.is_empty() {
+                    continue;
+                }
+
+                if dihedral_trimmed.starts_with("Impropers")
+                    || dihedral_trimmed.starts_with("Velocities") {
+                    pending_header = Some(dihedral_trimmed.to_string());
+                    break;
+                }
+
+                let parts: Vec<&str> = dihedral_trimmed.split_whitespace().collect();
+                if parts.len() < 6 {
+                    continue;
+                }
+
+                // dihedral-ID dihedral-type atom1 atom2 atom3 atom4
+                let dihedral_type: u32 = parts[1].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid dihedral type: {}", parts[1])))?;
+                let atom1: u32 = parts[2].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom1: {}", parts[2])))?;
+                let atom2: u32 = parts[3].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom2: {}", parts[3])))?;
+                let atom3: u32 = parts[4].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom3: {}", parts[4])))?;
+                let atom4: u32 = parts[5].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom4: {}", parts[5])))?;
+
+                topology.dihedrals.push([atom1 - 1, atom2 - 1, atom3 - 1, atom4 - 1]);
+                topology.dihedral_types.push(dihedral_type);
+            }
+
+            continue; // Keep looking for Impropers
+        }
+
+        // Check for Impropers section
+        if trimmed.starts_with("Impropers") {
+            loop {
+                let improper_line = match lines.next() {
+                    Some(Ok(l)) => l,
+                    Some(Err(e)) => return Err(AxiomError::ParseError(format!("Read error: {}", e))),
+                    None => break,
+                };
+
+                let improper_trimmed = improper_line.trim();
+                if improper_trimmed.is_empty() {
+                    continue;
+                }
+
+                if improper_trimmed.starts_with("Velocities") {
+                    pending_header = Some(improper_trimmed.to_string());
+                    break;
+                }
+
+                let parts: Vec<&str> = improper_trimmed.split_whitespace().collect();
+                if parts.len() < 6 {
+                    continue;
+                }
+
+                // improper-ID improper-type atom1 atom2 atom3 atom4
+                let improper_type: u32 = parts[1].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid improper type: {}", parts[1])))?;
+                let atom1: u32 = parts[2].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom1: {}", parts[2])))?;
+                let atom2: u32 = parts[3].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom2: {}", parts[3])))?;
+                let atom3: u32 = parts[4].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom3: {}", parts[4])))?;
+                let atom4: u32 = parts[5].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid atom4: {}", parts[5])))?;
+
+                topology.impropers.push([atom1 - 1, atom2 - 1, atom3 - 1, atom4 - 1]);
+                topology.improper_types.push(improper_type);
+            }
+
+            // Impropers is the last section we care about
+            continue;
+        }
+    }
+
+    if atoms.len() == 0 {
+        return Err(AxiomError::ParseError("No atoms parsed from file".to_string()));
+    }
+
+    Ok((atoms, topology))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const SIMPLE_DUMP: &str = "\
+ITEM: TIMESTEP
+0
+ITEM: NUMBER OF ATOMS
+3
+ITEM: BOX BOUNDS pp pp pp
+0.0 10.0
+0.0 10.0
+0.0 10.0
+ITEM: ATOMS id type x y z
+1 1 0.0 0.0 0.0
+2 2 1.0 1.0 1.0
+3 1 2.0 2.0 2.0
+";
+
+    #[test]
+    fn test_parse_lammps_uncompressed_dump_via_path() {
+        let path = std::env::temp_dir().join("axiom_test_lammps_plain.lammpstrj");
+        std::fs::write(&path, SIMPLE_DUMP).unwrap();
+
+        let atoms = parse_lammps(&path).unwrap();
+        assert_eq!(atoms.len(), 3);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_parse_lammps_gzip_compressed_dump() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(SIMPLE_DUMP.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let path = std::env::temp_dir().join("axiom_test_lammps_dump.lammpstrj.gz");
+        std::fs::write(&path, &compressed).unwrap();
+
+        let atoms = parse_lammps(&path).unwrap();
+        assert_eq!(atoms.len(), 3);
+        assert_eq!(atoms.position(1), Some([1.0, 1.0, 1.0]));
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_parse_lammps_zstd_compressed_dump() {
+        let compressed = zstd::encode_all(SIMPLE_DUMP.as_bytes(), 0).unwrap();
+
+        let path = std::env::temp_dir().join("axiom_test_lammps_dump.lammpstrj.zst");
+        std::fs::write(&path, &compressed).unwrap();
+
+        let atoms = parse_lammps(&path).unwrap();
+        assert_eq!(atoms.len(), 3);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_parse_lammps_dump_simple() {
+        let lammps_data = "\
+ITEM: TIMESTEP
+0
+ITEM: NUMBER OF ATOMS
+3
+ITEM: BOX BOUNDS pp pp pp
+0.0 10.0
+0.0 10.0
+0.0 10.0
+ITEM: ATOMS id type x y z
+1 1 0.0 0.0 0.0
+2 2 1.0 1.0 1.0
+3 1 2.0 2.0 2.0
+";
+        let cursor = Cursor::new(lammps_data);
+        let atoms = parse_lammps_dump_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.len(), 3);
+        assert_eq!(atoms.position(0), Some([0.0, 0.0, 0.0]));
+        assert_eq!(atoms.position(1), Some([1.0, 1.0, 1.0]));
+        assert_eq!(atoms.position(2), Some([2.0, 2.0, 2.0]));
+        assert_eq!(atoms.element(0), Some(1));
+        assert_eq!(atoms.element(1), Some(2));
+    }
+
+    #[test]
+    fn test_parse_lammps_dump_trajectory_multiple_frames() {
+        let lammps_data = "\
+ITEM: TIMESTEP
+0
+ITEM: NUMBER OF ATOMS
+2
+ITEM: BOX BOUNDS pp pp pp
+0.0 10.0
+0.0 10.0
+0.0 10.0
+ITEM: ATOMS id type x y z
+1 1 0.0 0.0 0.0
+2 2 1.0 1.0 1.0
+ITEM: TIMESTEP
+100
+ITEM: NUMBER OF ATOMS
+2
+ITEM: BOX BOUNDS pp pp pp
+0.0 10.0
+0.0 10.0
+0.0 10.0
+ITEM: ATOMS id type x y z
+1 1 0.1 0.0 0.0
+2 2 1.1 1.0 1.0
+";
+        let cursor = Cursor::new(lammps_data);
+        let trajectory = parse_lammps_dump_trajectory_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(trajectory.len(), 2);
+        assert_eq!(trajectory.timesteps, vec![0, 100]);
+        assert_eq!(trajectory.frames[0].position(0), Some([0.0, 0.0, 0.0]));
+        assert_eq!(trajectory.frames[1].position(0), Some([0.1, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_parse_lammps_dump_trajectory_varying_atom_count() {
+        // Grand-canonical-style run: the second frame has gained an atom.
+        let lammps_data = "\
+ITEM: TIMESTEP
+0
+ITEM: NUMBER OF ATOMS
+2
+ITEM: BOX BOUNDS pp pp pp
+0.0 10.0
+0.0 10.0
+0.0 10.0
+ITEM: ATOMS id type x y z
+1 1 0.0 0.0 0.0
+2 2 1.0 1.0 1.0
+ITEM: TIMESTEP
+100
+ITEM: NUMBER OF ATOMS
+3
+ITEM: BOX BOUNDS pp pp pp
+0.0 10.0
+0.0 10.0
+0.0 10.0
+ITEM: ATOMS id type x y z
+1 1 0.0 0.0 0.0
+2 2 1.0 1.0 1.0
+3 1 2.0 2.0 2.0
+";
+        let cursor = Cursor::new(lammps_data);
+        let trajectory = parse_lammps_dump_trajectory_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(trajectory.frames[0].len(), 2);
+        assert_eq!(trajectory.frames[1].len(), 3);
+    }
+
+    #[test]
+    fn test_parse_lammps_dump_reader_still_returns_first_frame_only() {
+        let lammps_data = "\
+ITEM: TIMESTEP
+0
+ITEM: NUMBER OF ATOMS
+1
+ITEM: BOX BOUNDS pp pp pp
+0.0 10.0
+0.0 10.0
+0.0 10.0
+ITEM: ATOMS id type x y z
+1 1 0.0 0.0 0.0
+ITEM: TIMESTEP
+100
+ITEM: NUMBER OF ATOMS
+1
+ITEM: BOX BOUNDS pp pp pp
+0.0 10.0
+0.0 10.0
+0.0 10.0
+ITEM: ATOMS id type x y z
+1 1 5.0 5.0 5.0
+";
+        let cursor = Cursor::new(lammps_data);
+        let atoms = parse_lammps_dump_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.len(), 1);
+        assert_eq!(atoms.position(0), Some([0.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_parse_lammps_dump_orthogonal_box_becomes_periodic_cell() {
+        let cursor = Cursor::new(SIMPLE_DUMP);
+        let atoms = parse_lammps_dump_reader(BufReader::new(cursor)).unwrap();
+
+        assert!(atoms.is_periodic());
+        let cell = atoms.periodic_box.unwrap();
+        assert_eq!(cell.matrix, [[10.0, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]]);
+    }
+
+    #[test]
+    fn test_parse_lammps_dump_triclinic_box_builds_tilted_cell() {
+        let lammps_data = "\
+ITEM: TIMESTEP
+0
+ITEM: NUMBER OF ATOMS
+1
+ITEM: BOX BOUNDS xy xz yz pp pp pp
+0.0 10.0 2.0
+0.0 10.0 0.0
+0.0 10.0
+ITEM: ATOMS id type x y z
+1 1 0.0 0.0 0.0
+";
+        let cursor = Cursor::new(lammps_data);
+        let atoms = parse_lammps_dump_reader(BufReader::new(cursor)).unwrap();
+
+        assert!(atoms.is_periodic());
+        let cell = atoms.periodic_box.unwrap();
+        // xhi = xhi_bound - max(0, xy) = 10 - 2 = 8:
+        // a=(8,0,0), b=(xy=2, 10,0), c=(xz=0, yz=0, 10)
+        assert_eq!(cell.matrix, [[8.0, 2.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]]);
+    }
+
+    #[test]
+    fn test_parse_lammps_dump_non_periodic_flags_set_cell_without_pbc() {
+        let lammps_data = "\
+ITEM: TIMESTEP
+0
+ITEM: NUMBER OF ATOMS
+1
+ITEM: BOX BOUNDS pp pp ff
+0.0 10.0
+0.0 10.0
+0.0 10.0
+ITEM: ATOMS id type x y z
+1 1 0.0 0.0 0.0
+";
+        let cursor = Cursor::new(lammps_data);
+        let atoms = parse_lammps_dump_reader(BufReader::new(cursor)).unwrap();
+
+        // The box is still attached for visualization, but PBC isn't fully active.
+        assert!(atoms.periodic_box.is_some());
+        assert!(!atoms.is_periodic());
+    }
+
+    #[test]
+    fn test_parse_lammps_data_simple() {
+        let lammps_data = "\
+LAMMPS data file
+
+3 atoms
+2 atom types
+
+0.0 10.0 xlo xhi
+0.0 10.0 ylo yhi
+0.0 10.0 zlo zhi
+
+Masses
+
+1 1.008
+2 12.011
+
+Atoms # full
+
+1 1 1 0.5 0.0 0.0 0.0
+2 1 2 -0.5 1.0 1.0 1.0
+3 2 1 0.3 2.0 2.0 2.0
+";
+        let cursor = Cursor::new(lammps_data);
+        let atoms = parse_lammps_data_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.len(), 3);
+        assert_eq!(atoms.position(0), Some([0.0, 0.0, 0.0]));
+        assert_eq!(atoms.position(1), Some([1.0, 1.0, 1.0]));
+        assert_eq!(atoms.position(2), Some([2.0, 2.0, 2.0]));
+
+        // Check charges
+        assert!(atoms.charges.is_some());
+        let charges = atoms.charges.as_ref().unwrap();
+        assert_eq!(charges[0], 0.5);
+        assert_eq!(charges[1], -0.5);
+        assert_eq!(charges[2], 0.3);
+
+        // Check atom types
+        assert!(atoms.atom_types.is_some());
+        let types = atoms.atom_types.as_ref().unwrap();
+        assert_eq!(types[0], 1);
+        assert_eq!(types[1], 2);
+        assert_eq!(types[2], 1);
+
+        // Check molecule IDs
+        assert!(atoms.molecule_ids.is_some());
+        let mol_ids = atoms.molecule_ids.as_ref().unwrap();
+        assert_eq!(mol_ids[0], 1);
+        assert_eq!(mol_ids[1], 1);
+        assert_eq!(mol_ids[2], 2);
+
+        // Elements should be resolved from the Masses section, not the raw
+        // LAMMPS type id (type 1 -> 1.008 amu -> H, type 2 -> 12.011 amu -> C).
+        assert_eq!(atoms.element(0), Some(1));
+        assert_eq!(atoms.element(1), Some(6));
+        assert_eq!(atoms.element(2), Some(1));
+    }
+
+    #[test]
+    fn test_parse_lammps_data_element_falls_back_to_type_without_masses() {
+        let lammps_data = "\
+LAMMPS data file
+
+1 atoms
+1 atom types
+
+0.0 10.0 xlo xhi
+0.0 10.0 ylo yhi
+0.0 10.0 zlo zhi
+
+Atoms # full
+
+1 1 1 0.0 0.0 0.0 0.0
+";
+        let cursor = Cursor::new(lammps_data);
+        let atoms = parse_lammps_data_reader(BufReader::new(cursor)).unwrap();
+
+        // No Masses section: falls back to today's placeholder behavior.
+        assert_eq!(atoms.element(0), Some(1));
+    }
+
+    #[test]
+    fn test_parse_lammps_data_type_overrides_win_over_masses() {
+        let lammps_data = "\
+LAMMPS data file
+
+1 atoms
+1 atom types
+
+0.0 10.0 xlo xhi
+0.0 10.0 ylo yhi
+0.0 10.0 zlo zhi
+
+Masses
+
+1 14.0
+
+Atoms # full
+
+1 1 1 0.0 0.0 0.0 0.0
+";
+        // 14.0 amu is ambiguous between a coarse-grained bead and nitrogen;
+        // force it to carbon via the override map.
+        let mut overrides = HashMap::new();
+        overrides.insert(1u32, 6u8);
+
+        let cursor = Cursor::new(lammps_data);
+        let atoms =
+            parse_lammps_data_reader_with_overrides(BufReader::new(cursor), Some(&overrides)).unwrap();
+
+        assert_eq!(atoms.element(0), Some(6));
+    }
+
+    #[test]
+    fn test_parse_lammps_data_with_bonds_resolves_elements_from_masses() {
+        let lammps_data = "\
+LAMMPS data file
+
+2 atoms
+1 bonds
+1 atom types
+
+0.0 10.0 xlo xhi
+0.0 10.0 ylo yhi
+0.0 10.0 zlo zhi
+
+Masses
+
+1 15.999
+
+Atoms # full
+
+1 1 1 -0.5 0.0 0.0 0.0
+2 1 1 0.5 1.0 0.0 0.0
+
+Bonds
+
+1 1 1 2
+";
+        let cursor = Cursor::new(lammps_data);
+        let (atoms, bonds) = parse_lammps_data_with_bonds_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.element(0), Some(8)); // oxygen, from mass 15.999
+        assert_eq!(bonds.len(), 1);
+        assert!(atoms.is_periodic());
+        assert_eq!(
+            atoms.periodic_box.unwrap().matrix,
+            [[10.0, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]]
+        );
+    }
+
+    #[test]
+    fn test_parse_lammps_data_triclinic_tilt_line_builds_tilted_cell() {
+        let lammps_data = "\
+LAMMPS data file
+
+1 atoms
+1 atom types
+
+0.0 10.0 xlo xhi
+0.0 10.0 ylo yhi
+0.0 10.0 zlo zhi
+1.0 0.0 0.0 xy xz yz
+
+Atoms # full
+
+1 1 1 0.0 0.0 0.0 0.0
+";
+        let cursor = Cursor::new(lammps_data);
+        let atoms = parse_lammps_data_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(
+            atoms.periodic_box.unwrap().matrix,
+            [[10.0, 1.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]]
+        );
+    }
+
+    #[test]
+    fn test_parse_lammps_data_without_box_bounds_has_no_periodic_box() {
+        let lammps_data = "\
+LAMMPS data file
+
+1 atoms
+1 atom types
+
+Atoms # full
+
+1 1 1 0.0 0.0 0.0 0.0
+";
+        let cursor = Cursor::new(lammps_data);
+        let atoms = parse_lammps_data_reader(BufReader::new(cursor)).unwrap();
+
+        assert!(atoms.periodic_box.is_none());
+        assert!(!atoms.is_periodic());
+    }
+
+    #[test]
+    fn test_parse_lammps_data_topology_parses_bonds_angles_dihedrals_impropers() {
+        let lammps_data = "\
+LAMMPS data file
+
+4 atoms
+3 bonds
+1 angles
+1 dihedrals
+1 impropers
+1 atom types
+
+0.0 10.0 xlo xhi
+0.0 10.0 ylo yhi
+0.0 10.0 zlo zhi
+
+Atoms # full
+
+1 1 1 0.0 0.0 0.0 0.0
+2 1 1 0.0 1.0 0.0 0.0
+3 1 1 0.0 2.0 0.0 0.0
+4 1 1 0.0 2.0 1.0 0.0
+
+Bonds
+
+1 1 1 2
+2 1 2 3
+3 1 3 4
+
+Angles
+
+1 1 1 2 3
+
+Dihedrals
+
+1 1 1 2 3 4
+
+Impropers
+
+1 1 1 2 3 4
+";
+        let cursor = Cursor::new(lammps_data);
+        let (atoms, topology) = parse_lammps_data_topology_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.len(), 4);
+        assert_eq!(topology.bonds.len(), 3);
+        assert_eq!(topology.bonds.atom1[0], 0);
+        assert_eq!(topology.bonds.atom2[0], 1);
+
+        assert_eq!(topology.angles, vec![[0, 1, 2]]);
+        assert_eq!(topology.angle_types, vec![1]);
+
+        assert_eq!(topology.dihedrals, vec![[0, 1, 2, 3]]);
+        assert_eq!(topology.dihedral_types, vec![1]);
+
+        assert_eq!(topology.impropers, vec![[0, 1, 2, 3]]);
+        assert_eq!(topology.improper_types, vec![1]);
+    }
+
+    #[test]
+    fn test_parse_lammps_data_topology_handles_missing_angle_sections() {
+        let lammps_data = "\
+LAMMPS data file
+
+2 atoms
+1 bonds
+1 atom types
+
+Atoms # full
+
+1 1 1 0.0 0.0 0.0 0.0
+2 1 1 0.0 1.0 0.0 0.0
+
+Bonds
+
+1 1 1 2
+";
+        let cursor = Cursor::new(lammps_data);
+        let (atoms, topology) = parse_lammps_data_topology_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.len(), 2);
+        assert_eq!(topology.bonds.len(), 1);
+        assert!(topology.angles.is_empty());
+        assert!(topology.dihedrals.is_empty());
+        assert!(topology.impropers.is_empty());
     }
 }
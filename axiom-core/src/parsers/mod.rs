@@ -5,9 +5,22 @@ pub mod pdb;
 pub mod lammps;
 pub mod gro;
 pub mod cif;
+pub mod psf;
+pub mod mol2;
 
-pub use xyz::parse_xyz;
-pub use pdb::{parse_pdb, parse_pdb_with_bonds};
-pub use lammps::{parse_lammps, parse_lammps_data_with_bonds};
+pub use xyz::{
+    parse_xyz, parse_extxyz, parse_extxyz_reader, parse_xyz_trajectory_reader, ParsedStructure,
+    XyzTrajectoryReader,
+};
+pub use pdb::{parse_pdb, parse_pdb_with_bonds, parse_pdb_trajectory, parse_pdb_trajectory_reader};
+pub use lammps::{
+    parse_lammps, parse_lammps_data_reader_with_overrides, parse_lammps_data_topology,
+    parse_lammps_data_topology_reader, parse_lammps_data_topology_reader_with_overrides,
+    parse_lammps_data_topology_with_overrides, parse_lammps_data_with_bonds,
+    parse_lammps_data_with_bonds_with_overrides, parse_lammps_dump_trajectory,
+    parse_lammps_dump_trajectory_reader, LammpsTrajectory, Topology,
+};
 pub use gro::parse_gro;
-pub use cif::{parse_cif, parse_cif_with_bonds};
+pub use cif::{parse_cif, parse_cif_with_bonds, parse_cif_expand_to_cell, parse_cif_expand_to_cell_with_bonds};
+pub use psf::{parse_psf, parse_psf_with_bonds, parse_psf_angles};
+pub use mol2::parse_mol2;
@@ -0,0 +1,287 @@
+// MOL2 (Tripos) file parser
+//
+// Unlike PDB/GRO, MOL2 carries explicit bond orders and SYBYL atom types
+// directly in the file, so no distance-based guessing (`compute_bonds`) or
+// CONECT-style single-bond default is needed. The format is a sequence of
+// `@<TRIPOS>SECTION` blocks; this parser only reads the three sections
+// needed for geometry + connectivity: `MOLECULE`, `ATOM`, and `BOND`.
+
+use crate::atoms::{Atoms, Bonds};
+use crate::errors::{AxiomError, Result};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Tripos bond order stored for an aromatic bond ("ar" in the file), kept
+/// distinct from single/double/triple (1/2/3) so callers can tell a real
+/// aromatic bond from a perceived Kekulé single/double pair.
+const AROMATIC_BOND_ORDER: u8 = 4;
+/// Tripos bond order stored for an amide bond ("am" in the file) - a
+/// resonance-delocalized single bond, kept distinct from a plain single
+/// bond for the same reason as aromatic.
+const AMIDE_BOND_ORDER: u8 = 5;
+
+/// Parse a MOL2 file
+pub fn parse_mol2<P: AsRef<Path>>(path: P) -> Result<(Atoms, Bonds)> {
+    let file = File::open(path.as_ref())
+        .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+    let reader = BufReader::new(file);
+    parse_mol2_reader(reader)
+}
+
+/// Parse a MOL2 file's `MOLECULE`, `ATOM`, and `BOND` sections from a
+/// buffered reader into `Atoms` and `Bonds`.
+pub fn parse_mol2_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bonds)> {
+    let mut lines = reader.lines();
+    let mut atoms = Atoms::new();
+    let mut bonds = Bonds::new();
+    let mut atom_names = Vec::new();
+
+    let mut num_atoms = 0usize;
+    let mut num_bonds = 0usize;
+
+    while let Some(line) = lines.next() {
+        let line = line?;
+        let trimmed = line.trim();
+
+        if trimmed == "@<TRIPOS>MOLECULE" {
+            // Line 1: molecule name (ignored). Line 2: counts, whitespace
+            // separated: num_atoms num_bonds [num_subst num_feat num_sets].
+            lines.next().ok_or_else(|| {
+                AxiomError::ParseError("@<TRIPOS>MOLECULE missing name line".to_string())
+            })??;
+            let counts_line = lines.next().ok_or_else(|| {
+                AxiomError::ParseError("@<TRIPOS>MOLECULE missing counts line".to_string())
+            })??;
+            let mut counts = counts_line.split_whitespace();
+            num_atoms = counts
+                .next()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| AxiomError::ParseError(format!("Invalid atom count: {}", counts_line)))?;
+            num_bonds = counts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+
+            atoms.reserve(num_atoms);
+            atom_names.reserve(num_atoms);
+            bonds = Bonds::with_capacity(num_bonds);
+            continue;
+        }
+
+        if trimmed == "@<TRIPOS>ATOM" {
+            for _ in 0..num_atoms {
+                let atom_line = lines.next().ok_or_else(|| {
+                    AxiomError::ParseError("@<TRIPOS>ATOM section ended early".to_string())
+                })??;
+                // atom_id atom_name x y z atom_type [subst_id subst_name charge ...]
+                let parts: Vec<&str> = atom_line.split_whitespace().collect();
+                if parts.len() < 6 {
+                    return Err(AxiomError::ParseError(format!(
+                        "Malformed @<TRIPOS>ATOM line (expected at least 6 fields): {}",
+                        atom_line
+                    )));
+                }
+
+                let name = parts[1].to_string();
+                let x: f32 = parts[2].parse().map_err(|_| {
+                    AxiomError::ParseError(format!("Invalid X coordinate: {}", parts[2]))
+                })?;
+                let y: f32 = parts[3].parse().map_err(|_| {
+                    AxiomError::ParseError(format!("Invalid Y coordinate: {}", parts[3]))
+                })?;
+                let z: f32 = parts[4].parse().map_err(|_| {
+                    AxiomError::ParseError(format!("Invalid Z coordinate: {}", parts[4]))
+                })?;
+
+                let element = element_from_sybyl_type(parts[5]);
+                atoms.push(x, y, z, element);
+                atom_names.push(name);
+            }
+            atoms.atom_names = Some(atom_names.clone());
+            continue;
+        }
+
+        if trimmed == "@<TRIPOS>BOND" {
+            for _ in 0..num_bonds {
+                let bond_line = lines.next().ok_or_else(|| {
+                    AxiomError::ParseError("@<TRIPOS>BOND section ended early".to_string())
+                })??;
+                // bond_id origin_atom_id target_atom_id bond_type
+                let parts: Vec<&str> = bond_line.split_whitespace().collect();
+                if parts.len() < 4 {
+                    return Err(AxiomError::ParseError(format!(
+                        "Malformed @<TRIPOS>BOND line (expected at least 4 fields): {}",
+                        bond_line
+                    )));
+                }
+
+                // MOL2 atom IDs are 1-based.
+                let origin: u32 = parts[1].parse().map_err(|_| {
+                    AxiomError::ParseError(format!("Invalid bond origin atom id: {}", parts[1]))
+                })?;
+                let target: u32 = parts[2].parse().map_err(|_| {
+                    AxiomError::ParseError(format!("Invalid bond target atom id: {}", parts[2]))
+                })?;
+                let order = tripos_bond_order(parts[3]);
+
+                bonds.push(
+                    to_zero_based_atom_id(origin, num_atoms, "@<TRIPOS>BOND")?,
+                    to_zero_based_atom_id(target, num_atoms, "@<TRIPOS>BOND")?,
+                    order,
+                );
+            }
+            continue;
+        }
+    }
+
+    if atoms.len() == 0 {
+        return Err(AxiomError::ParseError(
+            "No @<TRIPOS>ATOM section found in MOL2 file".to_string(),
+        ));
+    }
+
+    Ok((atoms, bonds))
+}
+
+/// Convert a 1-based MOL2 atom id from `section` to a validated 0-based
+/// index, checking it falls within the `@<TRIPOS>ATOM` section already
+/// parsed - MOL2 atom ids are free-form integers with no guarantee they
+/// stay in range, and `id - 1` on a 0 or out-of-range id would otherwise
+/// underflow or silently index past the atom list.
+fn to_zero_based_atom_id(atom_id: u32, num_atoms: usize, section: &str) -> Result<u32> {
+    if atom_id == 0 || atom_id as usize > num_atoms {
+        return Err(AxiomError::ParseError(format!(
+            "Atom id {} in {} section is out of range (expected 1..={})",
+            atom_id, section, num_atoms
+        )));
+    }
+    Ok(atom_id - 1)
+}
+
+/// Derive an element from a SYBYL atom type by stripping everything from
+/// the first `.` onward (`C.3` -> `C`, `N.ar` -> `N`, `O.co2` -> `O`), then
+/// looking the remainder up in the shared periodic table.
+fn element_from_sybyl_type(sybyl_type: &str) -> u8 {
+    let symbol = sybyl_type.split('.').next().unwrap_or("");
+    crate::elements::symbol_to_atomic_number(symbol).unwrap_or(0)
+}
+
+/// Map a Tripos bond-type label to the integer order stored by
+/// `Bonds::push`: `1`/`2`/`3` pass through unchanged, `ar` (aromatic) and
+/// `am` (amide) get their own distinct orders since neither is a plain
+/// single/double/triple bond, and anything unrecognized (`du`, `un`, `nc`)
+/// defaults to a single bond.
+fn tripos_bond_order(bond_type: &str) -> u8 {
+    match bond_type {
+        "1" => 1,
+        "2" => 2,
+        "3" => 3,
+        "ar" => AROMATIC_BOND_ORDER,
+        "am" => AMIDE_BOND_ORDER,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn benzene_mol2() -> &'static str {
+        "\
+@<TRIPOS>MOLECULE
+benzene
+ 12 12 1 0 0
+SMALL
+GASTEIGER
+
+@<TRIPOS>ATOM
+      1 C1         1.2131    0.7000    0.0000 C.ar    1  BEN1       -0.0620
+      2 C2         1.2131   -0.7000    0.0000 C.ar    1  BEN1       -0.0620
+      3 C3         0.0000   -1.4000    0.0000 C.ar    1  BEN1       -0.0620
+      4 C4        -1.2131   -0.7000    0.0000 C.ar    1  BEN1       -0.0620
+      5 C5        -1.2131    0.7000    0.0000 C.ar    1  BEN1       -0.0620
+      6 C6         0.0000    1.4000    0.0000 C.ar    1  BEN1       -0.0620
+      7 H1         2.1577    1.2445    0.0000 H       1  BEN1        0.0620
+      8 H2         2.1577   -1.2445    0.0000 H       1  BEN1        0.0620
+      9 H3         0.0000   -2.4890    0.0000 H       1  BEN1        0.0620
+     10 H4        -2.1577   -1.2445    0.0000 H       1  BEN1        0.0620
+     11 H5        -2.1577    1.2445    0.0000 H       1  BEN1        0.0620
+     12 H6         0.0000    2.4890    0.0000 H       1  BEN1        0.0620
+@<TRIPOS>BOND
+     1    1    2   ar
+     2    2    3   ar
+     3    3    4   ar
+     4    4    5   ar
+     5    5    6   ar
+     6    6    1   ar
+     7    1    7   1
+     8    2    8   1
+     9    3    9   1
+    10    4   10   1
+    11    5   11   1
+    12    6   12   1
+"
+    }
+
+    #[test]
+    fn test_parse_mol2_atoms() {
+        let cursor = Cursor::new(benzene_mol2());
+        let (atoms, _bonds) = parse_mol2_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.len(), 12);
+        assert_eq!(atoms.element(0), Some(6)); // C.ar -> carbon
+        assert_eq!(atoms.element(6), Some(1)); // H -> hydrogen
+        assert_eq!(atoms.position(0), Some([1.2131, 0.7000, 0.0000]));
+    }
+
+    #[test]
+    fn test_parse_mol2_bonds_aromatic_and_single() {
+        let cursor = Cursor::new(benzene_mol2());
+        let (atoms, bonds) = parse_mol2_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.len(), 12);
+        assert_eq!(bonds.len(), 12);
+
+        // Ring bonds are aromatic.
+        assert_eq!(bonds.get(0), Some((0, 1, AROMATIC_BOND_ORDER)));
+        // C-H bonds are single.
+        assert_eq!(bonds.get(6), Some((0, 6, 1)));
+    }
+
+    #[test]
+    fn test_element_from_sybyl_type_strips_dot_suffix() {
+        assert_eq!(element_from_sybyl_type("C.3"), 6);
+        assert_eq!(element_from_sybyl_type("N.ar"), 7);
+        assert_eq!(element_from_sybyl_type("O.co2"), 8);
+        assert_eq!(element_from_sybyl_type("Cl"), 17);
+    }
+
+    #[test]
+    fn test_parse_mol2_missing_atom_section() {
+        let mol2_data = "\
+@<TRIPOS>MOLECULE
+empty
+ 0 0 0 0 0
+SMALL
+GASTEIGER
+";
+        let cursor = Cursor::new(mol2_data);
+        let result = parse_mol2_reader(BufReader::new(cursor));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_mol2_rejects_out_of_range_bond_atom_id() {
+        let mol2_data = benzene_mol2().replace("     1    1    2   ar", "     1    1   99   ar");
+        let cursor = Cursor::new(mol2_data);
+        let err = parse_mol2_reader(BufReader::new(cursor)).unwrap_err();
+        assert!(matches!(err, AxiomError::ParseError(_)));
+    }
+
+    #[test]
+    fn test_parse_mol2_rejects_zero_bond_atom_id() {
+        let mol2_data = benzene_mol2().replace("     1    1    2   ar", "     1    0    2   ar");
+        let cursor = Cursor::new(mol2_data);
+        let err = parse_mol2_reader(BufReader::new(cursor)).unwrap_err();
+        assert!(matches!(err, AxiomError::ParseError(_)));
+    }
+}
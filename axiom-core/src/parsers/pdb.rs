@@ -13,7 +13,10 @@
 // 31-38: X coordinate (Angstroms)
 // 39-46: Y coordinate (Angstroms)
 // 47-54: Z coordinate (Angstroms)
+// 55-60: Occupancy
+// 61-66: Temperature (B) factor
 // 77-78: Element symbol (right-justified)
+// 79-80: Charge on the atom (formal charge, e.g. "2+", "1-")
 
 use crate::atoms::{Atoms, Bonds};
 use crate::errors::{AxiomError, Result};
@@ -36,6 +39,10 @@ pub fn parse_pdb_reader<R: BufRead>(reader: R) -> Result<Atoms> {
     let mut residue_names = Vec::new();
     let mut chain_ids = Vec::new();
     let mut residue_indices = Vec::new();
+    let mut alt_locs = Vec::new();
+    let mut occupancies = Vec::new();
+    let mut b_factors = Vec::new();
+    let mut formal_charges = Vec::new();
 
     for (line_num, line_result) in reader.lines().enumerate() {
         let line = line_result?;
@@ -63,9 +70,19 @@ pub fn parse_pdb_reader<R: BufRead>(reader: R) -> Result<Atoms> {
             .map(|s| s.trim().to_string())
             .unwrap_or_else(|| " ".to_string());
 
-        // Extract residue sequence number (columns 23-26)
+        // Extract residue sequence number (columns 23-26), Hybrid-36 encoded
+        // once it outgrows 4 decimal digits. An unparseable field keeps the
+        // existing "unknown residue" tolerance of defaulting to 0.
         let resid_str = line.get(22..26).unwrap_or("    ");
-        let resid: u32 = resid_str.trim().parse().unwrap_or(0);
+        let resid: u32 = hy36decode(4, resid_str)
+            .ok()
+            .and_then(|v| u32::try_from(v).ok())
+            .unwrap_or(0);
+
+        // Extract alternate location indicator (column 17)
+        let alt_loc = line.get(16..17)
+            .and_then(|s| s.chars().next())
+            .unwrap_or(' ');
 
         // Extract coordinates (columns 31-38, 39-46, 47-54)
         let x_str = line.get(30..38).ok_or_else(|| {
@@ -88,6 +105,19 @@ pub fn parse_pdb_reader<R: BufRead>(reader: R) -> Result<Atoms> {
             AxiomError::ParseError(format!("Line {}: invalid Z coordinate '{}'", line_num + 1, z_str.trim()))
         })?;
 
+        // Extract occupancy (columns 55-60) and temperature factor
+        // (columns 61-66); both are optional and default to the PDB
+        // convention of "fully occupied, no B-factor data".
+        let occupancy: f32 = line.get(54..60)
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(1.0);
+        let b_factor: f32 = line.get(60..66)
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(0.0);
+
+        // Extract formal charge (columns 79-80)
+        let formal_charge = line.get(78..80).map(parse_formal_charge).unwrap_or(0);
+
         // Try to extract element symbol from columns 77-78 (PDB v3.0+)
         let element_symbol = if line.len() >= 78 {
             let elem = line.get(76..78).unwrap_or("").trim();
@@ -109,6 +139,10 @@ pub fn parse_pdb_reader<R: BufRead>(reader: R) -> Result<Atoms> {
         residue_names.push(resname);
         chain_ids.push(chain);
         residue_indices.push(resid);
+        alt_locs.push(alt_loc);
+        occupancies.push(occupancy);
+        b_factors.push(b_factor);
+        formal_charges.push(formal_charge);
     }
 
     if atoms.len() == 0 {
@@ -121,96 +155,140 @@ pub fn parse_pdb_reader<R: BufRead>(reader: R) -> Result<Atoms> {
     atoms.residue_names = Some(residue_names);
     atoms.chain_ids = Some(chain_ids);
     atoms.residue_indices = Some(residue_indices);
+    atoms.alt_locs = Some(alt_locs);
+    atoms.occupancies = Some(occupancies);
+    atoms.b_factors = Some(b_factors);
+    atoms.formal_charges = Some(formal_charges);
 
     Ok(atoms)
 }
 
-/// Extract element symbol from atom name (columns 13-16)
-/// PDB atom naming conventions:
-/// - First character is often the element (e.g., "C   ", "N   ", "O   ")
-/// - Two-letter elements are left-aligned (e.g., "CA  ", "CB  ", "FE  ")
-/// - Special cases: "CA" (alpha carbon), "CB" (beta carbon) are carbon, not calcium
-fn extract_element_from_atom_name(line: &str) -> &str {
-    let atom_name = line.get(12..16).unwrap_or("").trim();
-
-    if atom_name.is_empty() {
+/// Extract element symbol from a PDB atom-name field (line columns
+/// 13-16), following the standard PDB convention: two-letter elements
+/// (ions like "FE  ", "ZN  ", "CL  ", "MG  ") are left-justified starting
+/// in column 13, while single-letter elements are right-justified into
+/// column 14 with column 13 left blank - which is also how protein/
+/// nucleic atom names are written (" CA ", " HG ", "1HB2"). That blank-
+/// vs-not distinction in column 13 is exactly what disambiguates the
+/// alpha-carbon "CA" from the calcium ion "CA", and the gamma-hydrogen
+/// "HG" from the mercury ion "HG": a two-letter column-13 candidate is
+/// only trusted as the element when it's a real symbol
+/// (`symbol_to_atomic_number`), otherwise column 13 itself is the
+/// element. A digit in column 13 (e.g. "1HB2") is the hydrogen-numbering
+/// convention, not an element letter, so it's skipped in favor of column
+/// 14.
+pub(crate) fn extract_element_from_atom_name(line: &str) -> &str {
+    let field = line.get(12..16).unwrap_or("");
+    if field.trim().is_empty() {
         return "";
     }
 
-    // Handle common protein atom names that are carbon
-    if atom_name.starts_with('C') {
-        // CA, CB, CG, CD, CE, CZ are all carbon atoms in proteins
-        return "C";
+    let col13 = field.get(0..1).unwrap_or(" ");
+    let col14 = field.get(1..2).unwrap_or(" ");
+
+    if col13.chars().next().map_or(false, |c| c.is_ascii_digit()) {
+        return col14;
     }
 
-    // Handle common nitrogen atoms
-    if atom_name.starts_with('N') {
-        return "N";
+    if col13 != " " {
+        if let Some(two_letter) = field.get(0..2) {
+            if crate::elements::symbol_to_atomic_number(two_letter).is_some() {
+                return two_letter;
+            }
+        }
+        return col13;
     }
 
-    // Handle oxygen
-    if atom_name.starts_with('O') {
-        return "O";
+    col14
+}
+
+/// Decode a fixed-width PDB numeric field using the Hybrid-36 convention
+/// (http://cci.lbl.gov/hybrid_36/), which lets serial numbers and residue
+/// sequence numbers outgrow their column width: once the plain decimal
+/// range `0..10^width-1` is exhausted, the field switches to base-36
+/// (uppercase digits first, then lowercase once base-36 itself overflows).
+/// Used for both the atom serial (width 5) and residue sequence number
+/// (width 4) columns, so `parse_pdb`/`parse_pdb_with_bonds` and the CONECT
+/// `serial_to_index` map all agree on how oversized structures are numbered.
+pub(crate) fn hy36decode(width: usize, field: &str) -> Result<i64> {
+    let trimmed = field.trim();
+    if trimmed.is_empty() {
+        return Ok(0);
     }
 
-    // Handle sulfur
-    if atom_name.starts_with('S') {
-        return "S";
+    let first = trimmed.chars().next().unwrap();
+    if first.is_ascii_uppercase() || first.is_ascii_lowercase() {
+        let has_upper = trimmed.chars().any(|c| c.is_ascii_uppercase());
+        let has_lower = trimmed.chars().any(|c| c.is_ascii_lowercase());
+        if has_upper && has_lower {
+            return Err(AxiomError::ParseError(format!(
+                "Hybrid-36 field '{}' mixes uppercase and lowercase digits",
+                field
+            )));
+        }
+
+        let value = decode_base36(trimmed)?;
+        let base = 36i64.pow(width as u32 - 1);
+        let offset = if first.is_ascii_uppercase() {
+            10i64.pow(width as u32) - 10 * base
+        } else {
+            10i64.pow(width as u32) - 10 * base + 26 * base
+        };
+        return Ok(value + offset);
     }
 
-    // For other cases, take first character if it's alphabetic
-    let first_char = atom_name.chars().next().unwrap();
-    if first_char.is_alphabetic() {
-        // Return first character as a static str (we need a way to return &str)
-        // For now, return the whole atom_name and let the caller handle it
-        &atom_name[0..1]
-    } else {
-        ""
+    // Ordinary decimal field (optionally with a leading '+'/'-').
+    trimmed.parse::<i64>().map_err(|_| {
+        AxiomError::ParseError(format!("Invalid Hybrid-36 field: '{}'", field))
+    })
+}
+
+/// Decode a base-36 digit string (`0-9A-Z` or `0-9a-z`, case not mixed) as
+/// an unsigned integer, most significant digit first.
+fn decode_base36(s: &str) -> Result<i64> {
+    let mut value: i64 = 0;
+    for c in s.chars() {
+        let digit = c.to_digit(36).ok_or_else(|| {
+            AxiomError::ParseError(format!("Invalid Hybrid-36 digit in '{}'", s))
+        })?;
+        value = value * 36 + digit as i64;
     }
+    Ok(value)
 }
 
-/// Convert element symbol to atomic number
-fn symbol_to_atomic_number(symbol: &str) -> u8 {
-    // Handle both single and double character symbols
-    let symbol_upper = symbol.to_uppercase();
-    match symbol_upper.as_str() {
-        "H" => 1,
-        "HE" => 2,
-        "LI" => 3,
-        "BE" => 4,
-        "B" => 5,
-        "C" => 6,
-        "N" => 7,
-        "O" => 8,
-        "F" => 9,
-        "NE" => 10,
-        "NA" => 11,
-        "MG" => 12,
-        "AL" => 13,
-        "SI" => 14,
-        "P" => 15,
-        "S" => 16,
-        "CL" => 17,
-        "AR" => 18,
-        "K" => 19,
-        "CA" => 20,
-        "SC" => 21,
-        "TI" => 22,
-        "V" => 23,
-        "CR" => 24,
-        "MN" => 25,
-        "FE" => 26,
-        "CO" => 27,
-        "NI" => 28,
-        "CU" => 29,
-        "ZN" => 30,
-        "BR" => 35,
-        "AG" => 47,
-        "I" => 53,
-        "AU" => 79,
-        // Add more as needed
-        _ => 0, // Unknown element
+/// Parse a PDB formal charge field (columns 79-80), written as magnitude
+/// then sign (e.g. "2+", "1-"). Blank, whitespace-only, or otherwise
+/// malformed fields default to 0, matching this module's existing
+/// tolerance for missing optional columns.
+fn parse_formal_charge(field: &str) -> i8 {
+    let trimmed = field.trim();
+    if trimmed.is_empty() {
+        return 0;
     }
+
+    let mut chars = trimmed.chars();
+    let sign = match trimmed.chars().find(|c| *c == '+' || *c == '-') {
+        Some('-') => -1,
+        Some(_) => 1,
+        None => 1,
+    };
+    let magnitude: String = chars.by_ref().filter(|c| c.is_ascii_digit()).collect();
+
+    magnitude.parse::<i8>().map(|m| m * sign).unwrap_or(0)
+}
+
+/// Convert element symbol to atomic number. Delegates to the crate-wide
+/// `elements` table, returning 0 (this module's long-standing sentinel for
+/// an unrecognized element) instead of `None`.
+pub(crate) fn symbol_to_atomic_number(symbol: &str) -> u8 {
+    crate::elements::symbol_to_atomic_number(symbol).unwrap_or(0)
+}
+
+/// Convert atomic number back to an element symbol (for writing PDB/GRO/G96
+/// files). Delegates to the crate-wide `elements` table; unmapped numbers
+/// fall back to "X", this module's long-standing sentinel for unknown.
+pub(crate) fn atomic_number_to_symbol(atomic_number: u8) -> &'static str {
+    crate::elements::atomic_number_to_symbol(atomic_number).unwrap_or("X")
 }
 
 /// Parse PDB file with CONECT records for bonds
@@ -228,6 +306,10 @@ pub fn parse_pdb_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bond
     let mut residue_names = Vec::new();
     let mut chain_ids = Vec::new();
     let mut residue_indices = Vec::new();
+    let mut alt_locs = Vec::new();
+    let mut occupancies = Vec::new();
+    let mut b_factors = Vec::new();
+    let mut formal_charges = Vec::new();
 
     // Map PDB serial numbers to atom indices (0-based)
     let mut serial_to_index: HashMap<u32, u32> = HashMap::new();
@@ -244,13 +326,21 @@ pub fn parse_pdb_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bond
                 )));
             }
 
-            // Extract atom serial number (columns 7-11)
+            // Extract atom serial number (columns 7-11), Hybrid-36 encoded
+            // once it outgrows 5 decimal digits.
             let serial_str = line.get(6..11).ok_or_else(|| {
                 AxiomError::ParseError(format!("Line {}: cannot extract serial number", line_num + 1))
             })?;
-            let serial: u32 = serial_str.trim().parse().map_err(|_| {
-                AxiomError::ParseError(format!("Line {}: invalid serial number '{}'", line_num + 1, serial_str.trim()))
-            })?;
+            let serial: u32 = hy36decode(5, serial_str)
+                .ok()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| {
+                    AxiomError::ParseError(format!(
+                        "Line {}: invalid serial number '{}'",
+                        line_num + 1,
+                        serial_str.trim()
+                    ))
+                })?;
 
             // Store mapping from serial to index
             serial_to_index.insert(serial, atoms.len() as u32);
@@ -265,9 +355,19 @@ pub fn parse_pdb_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bond
                 .map(|s| s.trim().to_string())
                 .unwrap_or_else(|| " ".to_string());
 
-            // Extract residue sequence number (columns 23-26)
+            // Extract residue sequence number (columns 23-26), Hybrid-36
+            // encoded once it outgrows 4 decimal digits. An unparseable
+            // field keeps the existing "unknown residue" tolerance of 0.
             let resid_str = line.get(22..26).unwrap_or("    ");
-            let resid: u32 = resid_str.trim().parse().unwrap_or(0);
+            let resid: u32 = hy36decode(4, resid_str)
+                .ok()
+                .and_then(|v| u32::try_from(v).ok())
+                .unwrap_or(0);
+
+            // Extract alternate location indicator (column 17)
+            let alt_loc = line.get(16..17)
+                .and_then(|s| s.chars().next())
+                .unwrap_or(' ');
 
             // Extract coordinates (columns 31-38, 39-46, 47-54)
             let x_str = line.get(30..38).ok_or_else(|| {
@@ -290,6 +390,18 @@ pub fn parse_pdb_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bond
                 AxiomError::ParseError(format!("Line {}: invalid Z coordinate '{}'", line_num + 1, z_str.trim()))
             })?;
 
+            // Extract occupancy (columns 55-60) and temperature factor
+            // (columns 61-66)
+            let occupancy: f32 = line.get(54..60)
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(1.0);
+            let b_factor: f32 = line.get(60..66)
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(0.0);
+
+            // Extract formal charge (columns 79-80)
+            let formal_charge = line.get(78..80).map(parse_formal_charge).unwrap_or(0);
+
             // Extract element symbol
             let element_symbol = if line.len() >= 78 {
                 let elem = line.get(76..78).unwrap_or("").trim();
@@ -309,6 +421,10 @@ pub fn parse_pdb_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bond
             residue_names.push(resname);
             chain_ids.push(chain);
             residue_indices.push(resid);
+            alt_locs.push(alt_loc);
+            occupancies.push(occupancy);
+            b_factors.push(b_factor);
+            formal_charges.push(formal_charge);
         }
         // Process CONECT records
         else if line.starts_with("CONECT") {
@@ -323,8 +439,11 @@ pub fn parse_pdb_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bond
                 continue;
             }
 
-            // First number is the atom
-            let atom1_serial: u32 = parts[0].parse().unwrap_or(0);
+            // First number is the atom (also Hybrid-36 encoded for large structures)
+            let atom1_serial: u32 = hy36decode(5, parts[0])
+                .ok()
+                .and_then(|v| u32::try_from(v).ok())
+                .unwrap_or(0);
             if atom1_serial == 0 {
                 continue;
             }
@@ -337,9 +456,12 @@ pub fn parse_pdb_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bond
 
             // Rest are bonded atoms
             for &bonded_serial_str in &parts[1..] {
-                let bonded_serial: u32 = match bonded_serial_str.parse() {
-                    Ok(s) => s,
-                    Err(_) => continue,
+                let bonded_serial: u32 = match hy36decode(5, bonded_serial_str)
+                    .ok()
+                    .and_then(|v| u32::try_from(v).ok())
+                {
+                    Some(s) => s,
+                    None => continue,
                 };
 
                 // Get 0-based index for bonded atom
@@ -366,10 +488,174 @@ pub fn parse_pdb_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bond
     atoms.residue_names = Some(residue_names);
     atoms.chain_ids = Some(chain_ids);
     atoms.residue_indices = Some(residue_indices);
+    atoms.alt_locs = Some(alt_locs);
+    atoms.occupancies = Some(occupancies);
+    atoms.b_factors = Some(b_factors);
+    atoms.formal_charges = Some(formal_charges);
 
     Ok((atoms, bonds))
 }
 
+/// Parse a multi-MODEL PDB file (NMR ensembles, docking poses) into one
+/// `Atoms` per `MODEL`/`ENDMDL` block.
+pub fn parse_pdb_trajectory<P: AsRef<Path>>(path: P) -> Result<Vec<Atoms>> {
+    let file = File::open(path.as_ref())
+        .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+    let reader = BufReader::new(file);
+    parse_pdb_trajectory_reader(reader)
+}
+
+/// Parse a multi-MODEL PDB reader into one `Atoms` per `MODEL`/`ENDMDL`
+/// block, validating that every model has the same atom count as the
+/// first. Files with no `MODEL` records at all are treated as a single
+/// model, so callers have one code path regardless of whether the source
+/// is a single structure or a whole ensemble.
+pub fn parse_pdb_trajectory_reader<R: BufRead>(reader: R) -> Result<Vec<Atoms>> {
+    let mut models: Vec<Atoms> = Vec::new();
+
+    let mut x = Vec::new();
+    let mut y = Vec::new();
+    let mut z = Vec::new();
+    let mut elements = Vec::new();
+    let mut residue_names = Vec::new();
+    let mut chain_ids = Vec::new();
+    let mut residue_indices = Vec::new();
+
+    for (line_num, line_result) in reader.lines().enumerate() {
+        let line = line_result?;
+
+        if line.starts_with("ENDMDL") {
+            push_trajectory_model(
+                &mut models, &mut x, &mut y, &mut z, &mut elements,
+                &mut residue_names, &mut chain_ids, &mut residue_indices,
+            )?;
+            continue;
+        }
+
+        if !line.starts_with("ATOM") && !line.starts_with("HETATM") {
+            continue;
+        }
+
+        if line.len() < 54 {
+            return Err(AxiomError::ParseError(format!(
+                "Line {}: ATOM/HETATM record too short (need at least 54 chars)",
+                line_num + 1
+            )));
+        }
+
+        let resname = line.get(17..20)
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|| "UNK".to_string());
+        let chain = line.get(21..22)
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|| " ".to_string());
+        let resid_str = line.get(22..26).unwrap_or("    ");
+        let resid: u32 = hy36decode(4, resid_str)
+            .ok()
+            .and_then(|v| u32::try_from(v).ok())
+            .unwrap_or(0);
+
+        let x_str = line.get(30..38).ok_or_else(|| {
+            AxiomError::ParseError(format!("Line {}: cannot extract X coordinate", line_num + 1))
+        })?;
+        let y_str = line.get(38..46).ok_or_else(|| {
+            AxiomError::ParseError(format!("Line {}: cannot extract Y coordinate", line_num + 1))
+        })?;
+        let z_str = line.get(46..54).ok_or_else(|| {
+            AxiomError::ParseError(format!("Line {}: cannot extract Z coordinate", line_num + 1))
+        })?;
+
+        let xv: f32 = x_str.trim().parse().map_err(|_| {
+            AxiomError::ParseError(format!("Line {}: invalid X coordinate '{}'", line_num + 1, x_str.trim()))
+        })?;
+        let yv: f32 = y_str.trim().parse().map_err(|_| {
+            AxiomError::ParseError(format!("Line {}: invalid Y coordinate '{}'", line_num + 1, y_str.trim()))
+        })?;
+        let zv: f32 = z_str.trim().parse().map_err(|_| {
+            AxiomError::ParseError(format!("Line {}: invalid Z coordinate '{}'", line_num + 1, z_str.trim()))
+        })?;
+
+        let element_symbol = if line.len() >= 78 {
+            let elem = line.get(76..78).unwrap_or("").trim();
+            if !elem.is_empty() {
+                elem
+            } else {
+                extract_element_from_atom_name(&line)
+            }
+        } else {
+            extract_element_from_atom_name(&line)
+        };
+
+        x.push(xv);
+        y.push(yv);
+        z.push(zv);
+        elements.push(symbol_to_atomic_number(element_symbol));
+        residue_names.push(resname);
+        chain_ids.push(chain);
+        residue_indices.push(resid);
+    }
+
+    // A file with no MODEL/ENDMDL records (or a trailing model not closed
+    // by one) still has its atoms sitting in the buffers here - flush them
+    // as the final (possibly only) model.
+    if !x.is_empty() {
+        push_trajectory_model(
+            &mut models, &mut x, &mut y, &mut z, &mut elements,
+            &mut residue_names, &mut chain_ids, &mut residue_indices,
+        )?;
+    }
+
+    if models.is_empty() {
+        return Err(AxiomError::ParseError(
+            "No ATOM or HETATM records found in PDB file".to_string(),
+        ));
+    }
+
+    Ok(models)
+}
+
+/// Drain the in-progress model buffers into a new `Atoms`, appended to
+/// `models`. Validates that the model has the same atom count as the first
+/// one already collected (NMR ensembles and docking poses all describe the
+/// same atom set, just at different coordinates).
+fn push_trajectory_model(
+    models: &mut Vec<Atoms>,
+    x: &mut Vec<f32>,
+    y: &mut Vec<f32>,
+    z: &mut Vec<f32>,
+    elements: &mut Vec<u8>,
+    residue_names: &mut Vec<String>,
+    chain_ids: &mut Vec<String>,
+    residue_indices: &mut Vec<u32>,
+) -> Result<()> {
+    if x.is_empty() {
+        return Ok(()); // Stray ENDMDL with no atoms since the last flush
+    }
+
+    if let Some(first) = models.first() {
+        if first.len() != x.len() {
+            return Err(AxiomError::ParseError(format!(
+                "Model {} has {} atoms, expected {} (from the first model)",
+                models.len() + 1,
+                x.len(),
+                first.len()
+            )));
+        }
+    }
+
+    let mut atoms = Atoms::with_capacity(x.len());
+    atoms.x = std::mem::take(x);
+    atoms.y = std::mem::take(y);
+    atoms.z = std::mem::take(z);
+    atoms.elements = std::mem::take(elements);
+    atoms.residue_names = Some(std::mem::take(residue_names));
+    atoms.chain_ids = Some(std::mem::take(chain_ids));
+    atoms.residue_indices = Some(std::mem::take(residue_indices));
+    models.push(atoms);
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -430,4 +716,150 @@ ATOM      2  C   ALA A   1      11.000  21.000  31.000  1.00  0.00
         assert_eq!(symbol_to_atomic_number("ca"), 20);  // Test case insensitivity
         assert_eq!(symbol_to_atomic_number("Unknown"), 0);
     }
+
+    #[test]
+    fn test_atomic_number_to_symbol() {
+        assert_eq!(atomic_number_to_symbol(1), "H");
+        assert_eq!(atomic_number_to_symbol(6), "C");
+        assert_eq!(atomic_number_to_symbol(26), "FE");
+        assert_eq!(atomic_number_to_symbol(0), "X");
+    }
+
+    #[test]
+    fn test_hy36decode_plain_decimal() {
+        assert_eq!(hy36decode(5, "99999").unwrap(), 99999);
+        assert_eq!(hy36decode(4, "   1").unwrap(), 1);
+        assert_eq!(hy36decode(4, "-999").unwrap(), -999);
+    }
+
+    #[test]
+    fn test_hy36decode_uppercase_base36_transition() {
+        // The canonical Hybrid-36 transition value: "A000" is the first
+        // width-4 field past the 4-digit decimal range (9999 -> 10000).
+        assert_eq!(hy36decode(4, "A000").unwrap(), 10000);
+        assert_eq!(hy36decode(4, "A001").unwrap(), 10001);
+    }
+
+    #[test]
+    fn test_hy36decode_lowercase_continues_past_uppercase() {
+        // Lowercase picks up where the uppercase base-36 range ends.
+        let upper_end = hy36decode(4, "ZZZZ").unwrap();
+        let lower_start = hy36decode(4, "a000").unwrap();
+        assert_eq!(lower_start, upper_end + 1);
+    }
+
+    #[test]
+    fn test_hy36decode_rejects_mixed_case() {
+        assert!(hy36decode(5, "Ab123").is_err());
+    }
+
+    #[test]
+    fn test_parse_pdb_with_bonds_hybrid36_serial() {
+        // Atom serials past 99999 roll over into Hybrid-36 ("A0000" = 100000).
+        let pdb_data = "\
+ATOM  A0000  O   WAT A   1       0.000   0.000   0.000  1.00  0.00           O
+ATOM  A0001  H1  WAT A   1       0.757   0.586   0.000  1.00  0.00           H
+CONECT A0000 A0001
+";
+        let cursor = Cursor::new(pdb_data);
+        let (atoms, bonds) = parse_pdb_with_bonds_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.len(), 2);
+        assert_eq!(bonds.len(), 1);
+        assert_eq!((bonds.atom1[0], bonds.atom2[0]), (0, 1));
+    }
+
+    #[test]
+    fn test_parse_pdb_trajectory_multi_model() {
+        let pdb_data = "\
+MODEL        1
+ATOM      1  O   WAT A   1       0.000   0.000   0.000  1.00  0.00           O
+ATOM      2  H1  WAT A   1       0.757   0.586   0.000  1.00  0.00           H
+ENDMDL
+MODEL        2
+ATOM      1  O   WAT A   1       1.000   0.000   0.000  1.00  0.00           O
+ATOM      2  H1  WAT A   1       1.757   0.586   0.000  1.00  0.00           H
+ENDMDL
+END
+";
+        let cursor = Cursor::new(pdb_data);
+        let models = parse_pdb_trajectory_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].len(), 2);
+        assert_eq!(models[0].position(0), Some([0.0, 0.0, 0.0]));
+        assert_eq!(models[1].position(0), Some([1.0, 0.0, 0.0]));
+        assert_eq!(models[0].element(0), models[1].element(0));
+    }
+
+    #[test]
+    fn test_parse_pdb_trajectory_single_model_no_model_records() {
+        let pdb_data = "\
+ATOM      1  O   WAT A   1       0.000   0.000   0.000  1.00  0.00           O
+ATOM      2  H1  WAT A   1       0.757   0.586   0.000  1.00  0.00           H
+END
+";
+        let cursor = Cursor::new(pdb_data);
+        let models = parse_pdb_trajectory_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].len(), 2);
+    }
+
+    #[test]
+    fn test_parse_pdb_reader_extracts_occupancy_bfactor_altloc_charge() {
+        let pdb_data = "\
+ATOM      1  O  AWAT A   1       0.000   0.000   0.000  0.30 12.50           O2-
+ATOM      2  H1  WAT A   1       0.757   0.586   0.000  1.00  0.00           H
+";
+        let cursor = Cursor::new(pdb_data);
+        let atoms = parse_pdb_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.alt_locs, Some(vec!['A', ' ']));
+        let occupancies = atoms.occupancies.unwrap();
+        assert!((occupancies[0] - 0.30).abs() < 1e-4);
+        assert!((occupancies[1] - 1.00).abs() < 1e-4);
+        let b_factors = atoms.b_factors.unwrap();
+        assert!((b_factors[0] - 12.50).abs() < 1e-4);
+        assert!((b_factors[1] - 0.00).abs() < 1e-4);
+        assert_eq!(atoms.formal_charges, Some(vec![-2, 0]));
+    }
+
+    #[test]
+    fn test_parse_pdb_reader_defaults_when_columns_missing() {
+        let pdb_data = "\
+ATOM      1  CA  ALA A   1      10.000  20.000  30.000
+";
+        let cursor = Cursor::new(pdb_data);
+        let atoms = parse_pdb_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.alt_locs, Some(vec![' ']));
+        assert_eq!(atoms.occupancies, Some(vec![1.0]));
+        assert_eq!(atoms.b_factors, Some(vec![0.0]));
+        assert_eq!(atoms.formal_charges, Some(vec![0]));
+    }
+
+    #[test]
+    fn test_parse_formal_charge() {
+        assert_eq!(parse_formal_charge("2+"), 2);
+        assert_eq!(parse_formal_charge("1-"), -1);
+        assert_eq!(parse_formal_charge("  "), 0);
+        assert_eq!(parse_formal_charge(""), 0);
+    }
+
+    #[test]
+    fn test_parse_pdb_trajectory_rejects_mismatched_atom_count() {
+        let pdb_data = "\
+MODEL        1
+ATOM      1  O   WAT A   1       0.000   0.000   0.000  1.00  0.00           O
+ATOM      2  H1  WAT A   1       0.757   0.586   0.000  1.00  0.00           H
+ENDMDL
+MODEL        2
+ATOM      1  O   WAT A   1       1.000   0.000   0.000  1.00  0.00           O
+ENDMDL
+";
+        let cursor = Cursor::new(pdb_data);
+        let result = parse_pdb_trajectory_reader(BufReader::new(cursor));
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,374 @@
+// PSF (protein structure file) parser
+//
+// Supports the CHARMM/NAMD PSF topology format. Unlike geometry-only
+// formats, PSF carries no coordinates at all - every atom is placed at the
+// origin here and is expected to be positioned from a companion coordinate
+// file (PDB, GRO, ...) afterwards. What PSF does carry, and what this
+// parser exists to preserve, is exact connectivity: the `!NBOND` section
+// gives the real bond list directly, so callers never need to fall back to
+// `compute_bonds`'s distance-based guessing for force-field systems.
+
+use crate::atoms::{Atoms, Bonds};
+use crate::errors::{AxiomError, Result};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Parse a PSF file's `!NATOM` section into `Atoms` (charges and residue
+/// metadata populated, connectivity discarded). Use `parse_psf_with_bonds`
+/// to also get the exact `!NBOND` bond list.
+pub fn parse_psf<P: AsRef<Path>>(path: P) -> Result<Atoms> {
+    let (atoms, _bonds) = parse_psf_with_bonds(path)?;
+    Ok(atoms)
+}
+
+/// Parse a PSF file's `!NATOM` section into `Atoms` and its `!NBOND`
+/// section into `Bonds` (all bonds reported with `order = 1`, since PSF
+/// carries no explicit bond order - only the MOL2 and distance-based paths
+/// currently produce orders other than 1).
+pub fn parse_psf_with_bonds<P: AsRef<Path>>(path: P) -> Result<(Atoms, Bonds)> {
+    let file = File::open(path.as_ref())
+        .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+    let reader = BufReader::new(file);
+    parse_psf_with_bonds_reader(reader)
+}
+
+/// Parse a PSF file's `!NATOM` and `!NBOND` sections from a buffered reader.
+pub fn parse_psf_with_bonds_reader<R: BufRead>(reader: R) -> Result<(Atoms, Bonds)> {
+    let mut lines = reader.lines();
+    let mut atoms = Atoms::new();
+    let mut bonds = Bonds::new();
+
+    let mut charges = Vec::new();
+    let mut residue_names = Vec::new();
+    let mut chain_ids = Vec::new();
+    let mut residue_indices = Vec::new();
+    let mut atom_names = Vec::new();
+
+    while let Some(line) = lines.next() {
+        let line = line?;
+        let trimmed = line.trim();
+
+        if trimmed.contains("!NATOM") {
+            let num_atoms = parse_section_count(trimmed)?;
+            atoms.reserve(num_atoms);
+            charges.reserve(num_atoms);
+
+            for _ in 0..num_atoms {
+                let atom_line = lines.next()
+                    .ok_or_else(|| AxiomError::ParseError("PSF !NATOM section ended early".to_string()))??;
+                // serial segid resid resname name type charge mass [...]
+                let parts: Vec<&str> = atom_line.split_whitespace().collect();
+                if parts.len() < 8 {
+                    return Err(AxiomError::ParseError(format!(
+                        "Malformed !NATOM line (expected at least 8 fields): {}",
+                        atom_line
+                    )));
+                }
+
+                let segid = parts[1].to_string();
+                let resid: u32 = parts[2].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid resid: {}", parts[2])))?;
+                let resname = parts[3].to_string();
+                let name = parts[4].to_string();
+                let charge: f32 = parts[6].parse()
+                    .map_err(|_| AxiomError::ParseError(format!("Invalid charge: {}", parts[6])))?;
+
+                let element = element_from_atom_name(&name);
+                atoms.push(0.0, 0.0, 0.0, element);
+                charges.push(charge);
+                residue_names.push(resname);
+                chain_ids.push(segid);
+                residue_indices.push(resid);
+                atom_names.push(name);
+            }
+
+            atoms.charges = Some(charges.clone());
+            atoms.residue_names = Some(residue_names.clone());
+            atoms.chain_ids = Some(chain_ids.clone());
+            atoms.residue_indices = Some(residue_indices.clone());
+            atoms.atom_names = Some(atom_names.clone());
+            continue;
+        }
+
+        if trimmed.contains("!NBOND") {
+            let num_bonds = parse_section_count(trimmed)?;
+            bonds = Bonds::with_capacity(num_bonds);
+
+            let mut serials = Vec::with_capacity(num_bonds * 2);
+            while serials.len() < num_bonds * 2 {
+                let bond_line = match lines.next() {
+                    Some(l) => l?,
+                    None => break,
+                };
+                let bond_trimmed = bond_line.trim();
+                if bond_trimmed.is_empty() {
+                    continue;
+                }
+                for token in bond_trimmed.split_whitespace() {
+                    let serial: u32 = token.parse().map_err(|_| {
+                        AxiomError::ParseError(format!("Invalid atom serial in !NBOND: {}", token))
+                    })?;
+                    serials.push(serial);
+                }
+            }
+
+            for pair in serials.chunks(2) {
+                if let [a, b] = *pair {
+                    // PSF uses 1-based atom serials.
+                    bonds.push(
+                        to_zero_based_serial(a, Some(atoms.len()), "!NBOND")?,
+                        to_zero_based_serial(b, Some(atoms.len()), "!NBOND")?,
+                        1,
+                    );
+                }
+            }
+            continue;
+        }
+    }
+
+    if atoms.len() == 0 {
+        return Err(AxiomError::ParseError("No !NATOM section found in PSF file".to_string()));
+    }
+
+    Ok((atoms, bonds))
+}
+
+/// Parse a PSF file's `!NTHETA` angle section into 0-based atom index
+/// triples, for callers that want force-field angle terms rather than
+/// recomputing them from the bond graph. Not wired into `parse_psf` itself,
+/// since most viewer use cases only need atoms + bonds.
+pub fn parse_psf_angles<P: AsRef<Path>>(path: P) -> Result<Vec<[u32; 3]>> {
+    let file = File::open(path.as_ref())
+        .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+    let reader = BufReader::new(file);
+    parse_psf_angles_reader(reader)
+}
+
+/// Parse a PSF file's `!NTHETA` angle section from a buffered reader. If a
+/// `!NATOM` section precedes it, its atom count is used to bound-check
+/// angle serials; otherwise only the serial-0 case is rejected.
+pub fn parse_psf_angles_reader<R: BufRead>(reader: R) -> Result<Vec<[u32; 3]>> {
+    let mut lines = reader.lines();
+    let mut angles = Vec::new();
+    let mut num_atoms = None;
+
+    while let Some(line) = lines.next() {
+        let line = line?;
+        let trimmed = line.trim();
+
+        if trimmed.contains("!NATOM") {
+            let count = parse_section_count(trimmed)?;
+            for _ in 0..count {
+                lines.next().ok_or_else(|| {
+                    AxiomError::ParseError("PSF !NATOM section ended early".to_string())
+                })??;
+            }
+            num_atoms = Some(count);
+            continue;
+        }
+
+        if trimmed.contains("!NTHETA") {
+            let num_angles = parse_section_count(trimmed)?;
+            let mut serials = Vec::with_capacity(num_angles * 3);
+
+            while serials.len() < num_angles * 3 {
+                let angle_line = match lines.next() {
+                    Some(l) => l?,
+                    None => break,
+                };
+                let angle_trimmed = angle_line.trim();
+                if angle_trimmed.is_empty() {
+                    continue;
+                }
+                for token in angle_trimmed.split_whitespace() {
+                    let serial: u32 = token.parse().map_err(|_| {
+                        AxiomError::ParseError(format!("Invalid atom serial in !NTHETA: {}", token))
+                    })?;
+                    serials.push(serial);
+                }
+            }
+
+            for triple in serials.chunks(3) {
+                if let [a, b, c] = *triple {
+                    // PSF uses 1-based atom serials.
+                    angles.push([
+                        to_zero_based_serial(a, num_atoms, "!NTHETA")?,
+                        to_zero_based_serial(b, num_atoms, "!NTHETA")?,
+                        to_zero_based_serial(c, num_atoms, "!NTHETA")?,
+                    ]);
+                }
+            }
+            break;
+        }
+    }
+
+    Ok(angles)
+}
+
+/// Convert a 1-based PSF atom serial from `section` to a validated 0-based
+/// index. PSF serials are free-form integers with no guarantee they stay in
+/// range, and `serial - 1` on a 0 or out-of-range serial would otherwise
+/// underflow or silently index past the atom list. When `num_atoms` is
+/// `None` (no `!NATOM` section was seen before `section`), only the
+/// serial-0 case can be rejected, since there's no atom count to bound-check
+/// against.
+fn to_zero_based_serial(serial: u32, num_atoms: Option<usize>, section: &str) -> Result<u32> {
+    let in_range = serial != 0 && num_atoms.map_or(true, |n| serial as usize <= n);
+    if !in_range {
+        return Err(AxiomError::ParseError(match num_atoms {
+            Some(n) => format!(
+                "Atom serial {} in {} section is out of range (expected 1..={})",
+                serial, section, n
+            ),
+            None => format!(
+                "Atom serial 0 in {} section is invalid (PSF serials are 1-based)",
+                section
+            ),
+        }));
+    }
+    Ok(serial - 1)
+}
+
+/// Count out of a PSF section header line, e.g. `"417 !NATOM"` -> 417.
+fn parse_section_count(header_line: &str) -> Result<usize> {
+    header_line
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| AxiomError::ParseError(format!("Malformed PSF section header: {}", header_line)))?
+        .parse()
+        .map_err(|_| AxiomError::ParseError(format!("Invalid PSF section count: {}", header_line)))
+}
+
+/// Guess an atom's element from its PSF atom name (e.g. "OH2" -> O, "H1" ->
+/// H) by its first letter alone - PSF carries no dedicated element column.
+/// Unlike `pdb::extract_element_from_atom_name`, two-letter element names
+/// are not recognized.
+fn element_from_atom_name(name: &str) -> u8 {
+    let first_char = match name.chars().next() {
+        Some(c) if c.is_alphabetic() => c,
+        _ => return 0,
+    };
+    crate::elements::symbol_to_atomic_number(&first_char.to_string()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn water_box_psf() -> &'static str {
+        "\
+PSF
+
+       3 !NTITLE
+ REMARKS water box test
+ REMARKS generated for axiom-core tests
+ REMARKS TIP3 water model
+
+       9 !NATOM
+       1 W1   1        TIP3 OH2  OT    -0.834000       15.9994           0
+       2 W1   1        TIP3 H1   HT     0.417000        1.0080           0
+       3 W1   1        TIP3 H2   HT     0.417000        1.0080           0
+       4 W2   2        TIP3 OH2  OT    -0.834000       15.9994           0
+       5 W2   2        TIP3 H1   HT     0.417000        1.0080           0
+       6 W2   2        TIP3 H2   HT     0.417000        1.0080           0
+       7 W3   3        TIP3 OH2  OT    -0.834000       15.9994           0
+       8 W3   3        TIP3 H1   HT     0.417000        1.0080           0
+       9 W3   3        TIP3 H2   HT     0.417000        1.0080           0
+
+       6 !NBOND: bonds
+       1       2       1       3       4       5
+       4       6       7       8       7       9
+"
+    }
+
+    #[test]
+    fn test_parse_psf_atom_count_and_charges() {
+        let cursor = Cursor::new(water_box_psf());
+        let (atoms, _bonds) = parse_psf_with_bonds_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.len(), 9);
+
+        let charges = atoms.charges.as_ref().expect("expected charges");
+        assert_eq!(charges[0], -0.834);
+        assert_eq!(charges[1], 0.417);
+        assert_eq!(charges[2], 0.417);
+
+        assert_eq!(atoms.element(0), Some(8)); // OH2 -> oxygen
+        assert_eq!(atoms.element(1), Some(1)); // H1 -> hydrogen
+
+        let residue_indices = atoms.residue_indices.as_ref().expect("expected residue indices");
+        assert_eq!(residue_indices, &vec![1, 1, 1, 2, 2, 2, 3, 3, 3]);
+    }
+
+    #[test]
+    fn test_parse_psf_with_bonds_exact_bond_list() {
+        let cursor = Cursor::new(water_box_psf());
+        let (atoms, bonds) = parse_psf_with_bonds_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(atoms.len(), 9);
+        assert_eq!(bonds.len(), 6);
+
+        let pairs: Vec<(u32, u32)> = bonds.atom1.iter().zip(bonds.atom2.iter())
+            .map(|(&a, &b)| (a, b))
+            .collect();
+        assert_eq!(pairs, vec![(0, 1), (0, 2), (3, 4), (3, 5), (6, 7), (6, 8)]);
+        assert!(bonds.order.iter().all(|&order| order == 1));
+    }
+
+    #[test]
+    fn test_parse_psf_angles() {
+        let psf_with_angles = format!(
+            "{}\n       2 !NTHETA: angles\n       2       1       3       5       4       6\n",
+            water_box_psf()
+        );
+        let cursor = Cursor::new(psf_with_angles);
+        let angles = parse_psf_angles_reader(BufReader::new(cursor)).unwrap();
+        assert_eq!(angles, vec![[1, 0, 2], [4, 3, 5]]);
+    }
+
+    #[test]
+    fn test_parse_psf_rejects_out_of_range_bond_serial() {
+        let bad_psf = water_box_psf().replace(
+            "       1       2       1       3       4       5",
+            "       1       2       1       3       4      99",
+        );
+        let cursor = Cursor::new(bad_psf);
+        let err = parse_psf_with_bonds_reader(BufReader::new(cursor)).unwrap_err();
+        assert!(matches!(err, AxiomError::ParseError(_)));
+    }
+
+    #[test]
+    fn test_parse_psf_rejects_zero_bond_serial() {
+        let bad_psf = water_box_psf().replace(
+            "       1       2       1       3       4       5",
+            "       0       2       1       3       4       5",
+        );
+        let cursor = Cursor::new(bad_psf);
+        let err = parse_psf_with_bonds_reader(BufReader::new(cursor)).unwrap_err();
+        assert!(matches!(err, AxiomError::ParseError(_)));
+    }
+
+    #[test]
+    fn test_parse_psf_rejects_zero_angle_serial() {
+        let psf_with_bad_angle = format!(
+            "{}\n       1 !NTHETA: angles\n       0       1       3\n",
+            water_box_psf()
+        );
+        let cursor = Cursor::new(psf_with_bad_angle);
+        let err = parse_psf_angles_reader(BufReader::new(cursor)).unwrap_err();
+        assert!(matches!(err, AxiomError::ParseError(_)));
+    }
+
+    #[test]
+    fn test_parse_psf_rejects_out_of_range_angle_serial() {
+        let psf_with_bad_angle = format!(
+            "{}\n       1 !NTHETA: angles\n       1       2      99\n",
+            water_box_psf()
+        );
+        let cursor = Cursor::new(psf_with_bad_angle);
+        let err = parse_psf_angles_reader(BufReader::new(cursor)).unwrap_err();
+        assert!(matches!(err, AxiomError::ParseError(_)));
+    }
+}
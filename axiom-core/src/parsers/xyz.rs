@@ -1,15 +1,50 @@
 // XYZ file parser
 //
-// XYZ format:
+// Plain XYZ format:
 // Line 1: Number of atoms
 // Line 2: Comment line
 // Line 3+: Element X Y Z
+//
+// Extended XYZ (extxyz) reuses line 2 for structured `key=value` metadata
+// instead of a free-text comment - most importantly `Lattice="..."` (a
+// periodic cell) and `Properties=...` (a schema describing the column
+// layout of the atom lines, which need not be the plain `Element X Y Z`
+// this parser otherwise assumes). `parse_extxyz_reader` understands both;
+// `parse_xyz_reader` is a thin, fully-compatible wrapper over it that just
+// discards the cell/bonds side of the result for callers that only want
+// `Atoms`.
 
-use crate::atoms::Atoms;
+use crate::atoms::{Atoms, Bonds, UnitCell};
 use crate::errors::{AxiomError, Result};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
+use std::str::FromStr;
+
+/// Upper bound on how many atoms `parse_extxyz_reader` will pre-allocate for
+/// based on the untrusted header count alone. A malformed or adversarial
+/// file can claim billions of atoms in line 1; without a cap that turns
+/// into an unbounded `Vec` allocation before a single atom line is even
+/// read. Real structures (even large MD systems) stay well under this, and
+/// the final atom count is still checked exactly against the header below -
+/// this only bounds the speculative pre-allocation, not what the parser
+/// accepts.
+const MAX_PREALLOCATED_ATOMS: usize = 1_000_000;
+
+/// Result of parsing an (extended) XYZ file: the atoms, the periodic cell
+/// when the comment line carried a `Lattice` token, and bonds - always
+/// `None` here, since neither plain nor extended XYZ carries an explicit
+/// connectivity record the way PDB's CONECT or MOL2's BOND section do.
+/// Kept as a named field (rather than just returning `(Atoms, Option<UnitCell>)`)
+/// so a future parser that *can* derive bonds inline has a matching shape
+/// to return.
+#[derive(Debug, Clone)]
+pub struct ParsedStructure {
+    pub atoms: Atoms,
+    pub cell: Option<UnitCell>,
+    pub bonds: Option<Bonds>,
+}
 
 /// Parse XYZ file
 pub fn parse_xyz<P: AsRef<Path>>(path: P) -> Result<Atoms> {
@@ -19,8 +54,33 @@ pub fn parse_xyz<P: AsRef<Path>>(path: P) -> Result<Atoms> {
     parse_xyz_reader(reader)
 }
 
-/// Parse XYZ from a buffered reader
+/// Parse XYZ from a buffered reader, discarding any `Lattice`/`Properties`
+/// metadata - see `parse_extxyz_reader` to keep it.
 pub fn parse_xyz_reader<R: BufRead>(reader: R) -> Result<Atoms> {
+    Ok(parse_extxyz_reader(reader)?.atoms)
+}
+
+/// Parse an extended-XYZ file, keeping its `Lattice`/`Properties` metadata.
+pub fn parse_extxyz<P: AsRef<Path>>(path: P) -> Result<ParsedStructure> {
+    let file = File::open(path.as_ref())
+        .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+    let reader = BufReader::new(file);
+    parse_extxyz_reader(reader)
+}
+
+/// Parse (extended) XYZ from a buffered reader.
+///
+/// Line 2 is parsed as `key=value` tokens (quoted values may contain
+/// spaces, e.g. `Lattice="10 0 0 0 10 0 0 0 10"`). A `Lattice` token becomes
+/// `cell` (and is also applied to the returned `Atoms` via
+/// `set_periodic_box`, so minimum-image distance math works immediately). A
+/// `Properties` token (e.g. `species:S:1:pos:R:3:charge:R:1`) declares the
+/// column layout of every atom line; when absent, the plain `Element X Y Z`
+/// layout is assumed. Recognized property names populate the matching
+/// `Atoms` field (`species` -> element, `pos` -> x/y/z, `charge` ->
+/// `charges`, `atom_types`/`type` -> `atom_types`, `molecule_ids`/`mol` ->
+/// `molecule_ids`); anything else has its columns skipped.
+pub fn parse_extxyz_reader<R: BufRead>(reader: R) -> Result<ParsedStructure> {
     let mut lines = reader.lines();
 
     // Line 1: Number of atoms
@@ -31,37 +91,48 @@ pub fn parse_xyz_reader<R: BufRead>(reader: R) -> Result<Atoms> {
         .parse()
         .map_err(|_| AxiomError::ParseError("Invalid atom count".to_string()))?;
 
-    // Line 2: Comment (skip)
-    lines.next();
+    // Line 2: extxyz key=value metadata (or a free-text comment, which
+    // simply yields no recognized tokens)
+    let comment_line = lines
+        .next()
+        .ok_or_else(|| AxiomError::ParseError("Missing comment line".to_string()))??;
+    let extxyz_tokens = parse_extxyz_tokens(&comment_line);
+
+    let cell = extxyz_tokens
+        .get("Lattice")
+        .and_then(|lattice| parse_lattice(lattice))
+        .map(|[a, b, c]| UnitCell::from_vectors(a, b, c));
 
-    // Allocate capacity
-    let mut atoms = Atoms::with_capacity(num_atoms);
+    let properties = extxyz_tokens
+        .get("Properties")
+        .map(|spec| parse_properties_spec(spec))
+        .filter(|fields| !fields.is_empty())
+        .unwrap_or_else(default_properties);
+
+    let wants_charges = properties.iter().any(|f| f.name == "charge");
+    let wants_atom_types = properties.iter().any(|f| f.name == "atom_types" || f.name == "type");
+    let wants_molecule_ids = properties.iter().any(|f| f.name == "molecule_ids" || f.name == "mol");
+
+    // Allocate capacity, clamped so a malformed huge count can't pre-allocate
+    // unbounded memory (see `MAX_PREALLOCATED_ATOMS`).
+    let mut atoms = Atoms::with_capacity(num_atoms.min(MAX_PREALLOCATED_ATOMS));
+    let mut charges = Vec::new();
+    let mut atom_types = Vec::new();
+    let mut molecule_ids = Vec::new();
 
     // Parse atoms
     for (line_num, line_result) in lines.enumerate() {
         let line = line_result?;
-        let parts: Vec<&str> = line.split_whitespace().collect();
-
-        if parts.len() < 4 {
-            return Err(AxiomError::ParseError(format!(
-                "Invalid line {}: expected 'Element X Y Z'",
-                line_num + 3
-            )));
+        let (charge, atom_type, molecule_id) = parse_xyz_atom_line(&line, &properties, &mut atoms, line_num)?;
+        if wants_charges {
+            charges.push(charge);
+        }
+        if wants_atom_types {
+            atom_types.push(atom_type);
+        }
+        if wants_molecule_ids {
+            molecule_ids.push(molecule_id);
         }
-
-        let element_symbol = parts[0];
-        let x: f32 = parts[1]
-            .parse()
-            .map_err(|_| AxiomError::ParseError(format!("Invalid X coordinate on line {}", line_num + 3)))?;
-        let y: f32 = parts[2]
-            .parse()
-            .map_err(|_| AxiomError::ParseError(format!("Invalid Y coordinate on line {}", line_num + 3)))?;
-        let z: f32 = parts[3]
-            .parse()
-            .map_err(|_| AxiomError::ParseError(format!("Invalid Z coordinate on line {}", line_num + 3)))?;
-
-        let element = symbol_to_atomic_number(element_symbol);
-        atoms.push(x, y, z, element);
     }
 
     if atoms.len() != num_atoms {
@@ -72,7 +143,316 @@ pub fn parse_xyz_reader<R: BufRead>(reader: R) -> Result<Atoms> {
         )));
     }
 
-    Ok(atoms)
+    if wants_charges {
+        atoms.charges = Some(charges);
+    }
+    if wants_atom_types {
+        atoms.atom_types = Some(atom_types);
+    }
+    if wants_molecule_ids {
+        atoms.molecule_ids = Some(molecule_ids);
+    }
+    if let Some(unit_cell) = &cell {
+        atoms.set_periodic_box(unit_cell.matrix);
+    }
+
+    Ok(ParsedStructure { atoms, cell, bonds: None })
+}
+
+/// Parse a file made of back-to-back XYZ frames (count line, comment line,
+/// exactly that many atom lines - repeated with no separator between
+/// frames), the layout MD trajectory tools emit when they just append one
+/// XYZ snapshot after another. `Lattice`/`Properties` metadata is honored
+/// independently per frame, same as `parse_extxyz_reader`.
+pub fn parse_xyz_trajectory_reader<R: BufRead>(reader: R) -> impl Iterator<Item = Result<Atoms>> {
+    XyzTrajectoryReader::new(reader)
+}
+
+/// Streaming iterator over concatenated XYZ frames. Prefer `next_into` over
+/// `Iterator::next` in a playback loop: it re-clears and reuses a
+/// caller-supplied `Atoms` buffer (via `Atoms::clear`/`reserve`) instead of
+/// allocating a fresh `Atoms` per frame.
+pub struct XyzTrajectoryReader<R: BufRead> {
+    lines: std::io::Lines<R>,
+    frame_index: usize,
+}
+
+impl<R: BufRead> XyzTrajectoryReader<R> {
+    pub fn new(reader: R) -> Self {
+        XyzTrajectoryReader { lines: reader.lines(), frame_index: 0 }
+    }
+
+    /// Read the next frame into `atoms`, clearing it first so its `Vec`
+    /// capacity carries over from the previous frame instead of being
+    /// reallocated. Returns `None` at a clean end-of-file between frames,
+    /// same as `Iterator::next` would.
+    pub fn next_into(&mut self, atoms: &mut Atoms) -> Option<Result<()>> {
+        let frame_index = self.frame_index;
+
+        let header_line = match self.lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(e)) => return Some(Err(e.into())),
+            None => return None,
+        };
+
+        let num_atoms: usize = match header_line.trim().parse() {
+            Ok(n) => n,
+            Err(_) => {
+                return Some(Err(AxiomError::ParseError(format!(
+                    "Frame {}: invalid atom count",
+                    frame_index
+                ))))
+            }
+        };
+
+        let comment_line = match self.lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(e)) => return Some(Err(e.into())),
+            None => {
+                return Some(Err(AxiomError::ParseError(format!(
+                    "Frame {}: missing comment line",
+                    frame_index
+                ))))
+            }
+        };
+        let extxyz_tokens = parse_extxyz_tokens(&comment_line);
+
+        let cell = extxyz_tokens
+            .get("Lattice")
+            .and_then(|lattice| parse_lattice(lattice))
+            .map(|[a, b, c]| UnitCell::from_vectors(a, b, c));
+
+        let properties = extxyz_tokens
+            .get("Properties")
+            .map(|spec| parse_properties_spec(spec))
+            .filter(|fields| !fields.is_empty())
+            .unwrap_or_else(default_properties);
+
+        let wants_charges = properties.iter().any(|f| f.name == "charge");
+        let wants_atom_types = properties.iter().any(|f| f.name == "atom_types" || f.name == "type");
+        let wants_molecule_ids = properties.iter().any(|f| f.name == "molecule_ids" || f.name == "mol");
+
+        atoms.clear();
+        atoms.reserve(num_atoms.min(MAX_PREALLOCATED_ATOMS));
+        let mut charges = Vec::new();
+        let mut atom_types = Vec::new();
+        let mut molecule_ids = Vec::new();
+
+        for i in 0..num_atoms {
+            let line = match self.lines.next() {
+                Some(Ok(line)) => line,
+                Some(Err(e)) => return Some(Err(e.into())),
+                None => {
+                    return Some(Err(AxiomError::ParseError(format!(
+                        "Frame {}: unexpected end of file (expected {} atom lines, found {})",
+                        frame_index, num_atoms, i
+                    ))))
+                }
+            };
+
+            let (charge, atom_type, molecule_id) = match parse_xyz_atom_line(&line, &properties, atoms, i) {
+                Ok(columns) => columns,
+                Err(AxiomError::ParseError(msg)) => {
+                    return Some(Err(AxiomError::ParseError(format!("Frame {}: {}", frame_index, msg))))
+                }
+                Err(e) => return Some(Err(e)),
+            };
+            if wants_charges {
+                charges.push(charge);
+            }
+            if wants_atom_types {
+                atom_types.push(atom_type);
+            }
+            if wants_molecule_ids {
+                molecule_ids.push(molecule_id);
+            }
+        }
+
+        if atoms.len() != num_atoms {
+            return Some(Err(AxiomError::ParseError(format!(
+                "Frame {}: expected {} atoms, found {}",
+                frame_index,
+                num_atoms,
+                atoms.len()
+            ))));
+        }
+
+        if wants_charges {
+            atoms.charges = Some(charges);
+        }
+        if wants_atom_types {
+            atoms.atom_types = Some(atom_types);
+        }
+        if wants_molecule_ids {
+            atoms.molecule_ids = Some(molecule_ids);
+        }
+        if let Some(unit_cell) = &cell {
+            atoms.set_periodic_box(unit_cell.matrix);
+        }
+
+        self.frame_index += 1;
+        Some(Ok(()))
+    }
+}
+
+impl<R: BufRead> Iterator for XyzTrajectoryReader<R> {
+    type Item = Result<Atoms>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut atoms = Atoms::new();
+        match self.next_into(&mut atoms) {
+            Some(Ok(())) => Some(Ok(atoms)),
+            Some(Err(e)) => Some(Err(e)),
+            None => None,
+        }
+    }
+}
+
+/// Parse one atom line's columns according to a `Properties` schema,
+/// pushing its position/element straight onto `atoms` and returning the
+/// charge/atom-type/molecule-id columns (zeroed when that field isn't in
+/// `properties`) for the caller to attach if it wants them. Shared by
+/// `parse_extxyz_reader` and `XyzTrajectoryReader` so both walk columns the
+/// same way; `line_num` is only used to tag parse-error messages.
+fn parse_xyz_atom_line(
+    line: &str,
+    properties: &[PropertyField],
+    atoms: &mut Atoms,
+    line_num: usize,
+) -> Result<(f32, u32, u32)> {
+    let columns: Vec<&str> = line.split_whitespace().collect();
+
+    let mut element = 0u8;
+    let mut x = 0.0f32;
+    let mut y = 0.0f32;
+    let mut z = 0.0f32;
+    let mut charge = 0.0f32;
+    let mut atom_type = 0u32;
+    let mut molecule_id = 0u32;
+
+    let mut col = 0usize;
+    for field in properties {
+        let values = columns.get(col..col + field.count).ok_or_else(|| {
+            AxiomError::ParseError(format!(
+                "Invalid line {}: expected {} column(s) for Properties field '{}'",
+                line_num + 3,
+                field.count,
+                field.name
+            ))
+        })?;
+
+        match field.name.as_str() {
+            "species" => element = symbol_to_atomic_number(values[0]),
+            "pos" if values.len() == 3 => {
+                x = parse_column(values[0], line_num, "X coordinate")?;
+                y = parse_column(values[1], line_num, "Y coordinate")?;
+                z = parse_column(values[2], line_num, "Z coordinate")?;
+            }
+            "charge" => charge = parse_column(values[0], line_num, "charge")?,
+            "atom_types" | "type" => atom_type = parse_column(values[0], line_num, "atom type")?,
+            "molecule_ids" | "mol" => molecule_id = parse_column(values[0], line_num, "molecule id")?,
+            _ => {} // Unrecognized property: columns consumed, contents ignored
+        }
+        col += field.count;
+    }
+
+    atoms.push(x, y, z, element);
+    Ok((charge, atom_type, molecule_id))
+}
+
+/// One field of an extxyz `Properties` schema, e.g. `pos:R:3` ->
+/// `{ name: "pos", count: 3 }`. The type letter (`S`/`R`/`I`/`L`) only
+/// exists in the file to describe string/real/integer/logical columns for
+/// other extxyz readers; this parser only needs the column width to walk
+/// the line, since the handful of field names it recognizes already imply
+/// a type.
+struct PropertyField {
+    name: String,
+    count: usize,
+}
+
+/// The column layout assumed when no `Properties` token is present: plain
+/// `Element X Y Z`.
+fn default_properties() -> Vec<PropertyField> {
+    vec![
+        PropertyField { name: "species".to_string(), count: 1 },
+        PropertyField { name: "pos".to_string(), count: 3 },
+    ]
+}
+
+/// Parse a `Properties=species:S:1:pos:R:3:charge:R:1` spec into fields.
+/// Malformed (non-triplet, non-numeric-count) chunks are skipped rather
+/// than erroring, since an unparseable schema should fall back to "ignore
+/// this field's columns" rather than fail the whole file.
+fn parse_properties_spec(spec: &str) -> Vec<PropertyField> {
+    spec.split(':')
+        .collect::<Vec<&str>>()
+        .chunks(3)
+        .filter_map(|chunk| match chunk {
+            [name, _type_char, count] => {
+                count.parse().ok().map(|count| PropertyField { name: name.to_string(), count })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parse a `Lattice="ax ay az bx by bz cx cy cz"` value into row vectors
+/// `[a, b, c]`, or `None` if it isn't exactly 9 numbers.
+fn parse_lattice(value: &str) -> Option<[[f32; 3]; 3]> {
+    let nums: Vec<f32> = value.split_whitespace().filter_map(|s| s.parse().ok()).collect();
+    if nums.len() != 9 {
+        return None;
+    }
+    Some([[nums[0], nums[1], nums[2]], [nums[3], nums[4], nums[5]], [nums[6], nums[7], nums[8]]])
+}
+
+/// Tokenize an extxyz comment line into `key -> value` pairs. Values are
+/// split on whitespace except inside a `"..."` quoted span, so
+/// `Lattice="10 0 0 0 10 0 0 0 10" Properties=species:S:1:pos:R:3` yields
+/// two tokens rather than being split apart by the spaces inside the
+/// quotes. Quotes around a value are stripped; a bare comment with no `=`
+/// signs yields no tokens, which is exactly the plain-XYZ case.
+fn parse_extxyz_tokens(line: &str) -> HashMap<String, String> {
+    let mut tokens = HashMap::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in line.chars() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+            current.push(c);
+        } else if c.is_whitespace() && !in_quotes {
+            flush_extxyz_token(&mut current, &mut tokens);
+        } else {
+            current.push(c);
+        }
+    }
+    flush_extxyz_token(&mut current, &mut tokens);
+
+    tokens
+}
+
+fn flush_extxyz_token(current: &mut String, tokens: &mut HashMap<String, String>) {
+    if let Some(eq) = current.find('=') {
+        let key = current[..eq].to_string();
+        let mut value = current[eq + 1..].to_string();
+        if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+            value = value[1..value.len() - 1].to_string();
+        }
+        tokens.insert(key, value);
+    }
+    current.clear();
+}
+
+/// Parse a single extxyz atom-line column into `T`, tagging parse errors
+/// with the 1-based line number (`line_num + 3`, counted from the count
+/// line of the file, or of the current frame in `XyzTrajectoryReader`) and
+/// a human-readable field name.
+fn parse_column<T: FromStr>(value: &str, line_num: usize, field_name: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| AxiomError::ParseError(format!("Invalid {} on line {}", field_name, line_num + 3)))
 }
 
 /// Convert element symbol to atomic number
@@ -137,6 +517,18 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_xyz_huge_atom_count_does_not_preallocate_unbounded() {
+        // A header claiming billions of atoms must not translate into an
+        // equally large `Vec` allocation; it should fail the count-mismatch
+        // check against the handful of lines actually present instead.
+        let xyz_data = "99999999999\nComment\nO 0.0 0.0 0.0\n";
+        let cursor = Cursor::new(xyz_data);
+        let result = parse_xyz_reader(BufReader::new(cursor));
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_symbol_to_atomic_number() {
         assert_eq!(symbol_to_atomic_number("H"), 1);
@@ -145,4 +537,125 @@ mod tests {
         assert_eq!(symbol_to_atomic_number("Au"), 79);
         assert_eq!(symbol_to_atomic_number("Unknown"), 0);
     }
+
+    #[test]
+    fn test_parse_extxyz_lattice_sets_cell_and_periodic_box() {
+        let extxyz_data = "\
+1
+Lattice=\"10.0 0.0 0.0 0.0 10.0 0.0 0.0 0.0 10.0\" Properties=species:S:1:pos:R:3
+C 1.0 2.0 3.0
+";
+        let cursor = Cursor::new(extxyz_data);
+        let parsed = parse_extxyz_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(parsed.atoms.len(), 1);
+        assert_eq!(parsed.atoms.position(0), Some([1.0, 2.0, 3.0]));
+        let cell = parsed.cell.expect("Lattice token should produce a UnitCell");
+        assert_eq!(cell.matrix, [[10.0, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]]);
+        assert!(parsed.atoms.periodic_box.is_some());
+        assert!(parsed.bonds.is_none());
+    }
+
+    #[test]
+    fn test_parse_extxyz_properties_populates_charge_and_type() {
+        let extxyz_data = "\
+2
+Properties=species:S:1:pos:R:3:charge:R:1:atom_types:I:1
+Na 0.0 0.0 0.0 1.0 1
+Cl 2.0 0.0 0.0 -1.0 2
+";
+        let cursor = Cursor::new(extxyz_data);
+        let parsed = parse_extxyz_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(parsed.atoms.element(0), Some(11)); // Na
+        assert_eq!(parsed.atoms.element(1), Some(17)); // Cl
+
+        let charges = parsed.atoms.charges.as_ref().expect("charge property should populate charges");
+        assert_eq!(charges[0], 1.0);
+        assert_eq!(charges[1], -1.0);
+
+        let atom_types = parsed.atoms.atom_types.as_ref().expect("atom_types property should populate atom_types");
+        assert_eq!(atom_types[0], 1);
+        assert_eq!(atom_types[1], 2);
+
+        assert!(parsed.cell.is_none());
+    }
+
+    #[test]
+    fn test_parse_extxyz_plain_comment_has_no_metadata() {
+        let extxyz_data = "1\nJust a free-text comment, nothing structured here\nH 0.0 0.0 0.0\n";
+        let cursor = Cursor::new(extxyz_data);
+        let parsed = parse_extxyz_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(parsed.atoms.len(), 1);
+        assert!(parsed.cell.is_none());
+        assert!(parsed.atoms.charges.is_none());
+    }
+
+    fn two_frame_xyz() -> &'static str {
+        "\
+2
+Frame 0
+O 0.0 0.0 0.0
+H 0.757 0.586 0.0
+2
+Frame 1
+O 1.0 0.0 0.0
+H 1.757 0.586 0.0
+"
+    }
+
+    #[test]
+    fn test_parse_xyz_trajectory_reader_yields_all_frames() {
+        let cursor = Cursor::new(two_frame_xyz());
+        let mut reader = parse_xyz_trajectory_reader(BufReader::new(cursor));
+
+        let frame0 = reader.next().unwrap().unwrap();
+        assert_eq!(frame0.len(), 2);
+        assert_eq!(frame0.position(0), Some([0.0, 0.0, 0.0]));
+
+        let frame1 = reader.next().unwrap().unwrap();
+        assert_eq!(frame1.len(), 2);
+        assert_eq!(frame1.position(0), Some([1.0, 0.0, 0.0]));
+
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_xyz_trajectory_reader_next_into_reuses_buffer() {
+        let cursor = Cursor::new(two_frame_xyz());
+        let mut reader = XyzTrajectoryReader::new(BufReader::new(cursor));
+        let mut atoms = Atoms::new();
+
+        reader.next_into(&mut atoms).unwrap().unwrap();
+        assert_eq!(atoms.position(0), Some([0.0, 0.0, 0.0]));
+        let capacity_after_frame0 = atoms.x.capacity();
+
+        reader.next_into(&mut atoms).unwrap().unwrap();
+        assert_eq!(atoms.position(0), Some([1.0, 0.0, 0.0]));
+        // Reusing the buffer shouldn't have needed to grow it for an
+        // equal-sized second frame.
+        assert_eq!(atoms.x.capacity(), capacity_after_frame0);
+
+        assert!(reader.next_into(&mut atoms).is_none());
+    }
+
+    #[test]
+    fn test_xyz_trajectory_reader_reports_frame_index_on_mismatch() {
+        let xyz_data = "\
+2
+Frame 0
+O 0.0 0.0 0.0
+H 0.757 0.586 0.0
+3
+Frame 1
+O 1.0 0.0 0.0
+";
+        let cursor = Cursor::new(xyz_data);
+        let mut reader = parse_xyz_trajectory_reader(BufReader::new(cursor));
+
+        assert!(reader.next().unwrap().is_ok());
+        let err = reader.next().unwrap().unwrap_err();
+        assert!(err.to_string().contains("Frame 1"), "error should mention the failing frame index: {}", err);
+    }
 }
@@ -1,7 +1,12 @@
 // Performance metrics tracking for render optimization
 // Monitors FPS, render times, memory usage, and LOD statistics
 
-use std::time::{Duration, Instant};
+use crate::errors::{AxiomError, Result};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Performance metrics for a single frame
 #[derive(Clone, Debug)]
@@ -9,6 +14,11 @@ pub struct FrameMetrics {
     pub frame_start: Instant,
     pub frame_duration: Duration,
     pub render_duration: Duration,
+    /// GPU-side render duration, filled in from a timer query the renderer
+    /// passes in via `PerformanceTracker::record_gpu_duration` - unlike
+    /// `render_duration` (CPU wall time from `start_render` to `end_render`),
+    /// this reflects actual device execution time.
+    pub gpu_duration: Duration,
     pub atoms_total: usize,
     pub atoms_rendered: usize,
     pub atoms_culled: usize,
@@ -24,6 +34,7 @@ impl FrameMetrics {
             frame_start: Instant::now(),
             frame_duration: Duration::ZERO,
             render_duration: Duration::ZERO,
+            gpu_duration: Duration::ZERO,
             atoms_total: 0,
             atoms_rendered: 0,
             atoms_culled: 0,
@@ -46,27 +57,327 @@ impl FrameMetrics {
         self.render_duration.as_secs_f64() * 1000.0
     }
 
+    pub fn gpu_time_ms(&self) -> f64 {
+        self.gpu_duration.as_secs_f64() * 1000.0
+    }
+
     pub fn frame_time_ms(&self) -> f64 {
         self.frame_duration.as_secs_f64() * 1000.0
     }
 }
 
+/// Appends each completed frame's metrics to a newline-delimited JSON file
+/// for offline analysis, so a full session can be captured and correlated
+/// (atom counts, LOD distribution, render time) outside the in-memory
+/// rolling window. Writes are buffered and flushed every `flush_every`
+/// frames rather than on every call, to avoid a syscall per frame.
+pub struct MetricsLogger {
+    writer: BufWriter<File>,
+    flush_every: usize,
+    frames_since_flush: usize,
+}
+
+impl MetricsLogger {
+    /// Create (or truncate) the JSONL file at `path`, flushing every
+    /// `flush_every` logged frames.
+    pub fn new<P: AsRef<Path>>(path: P, flush_every: usize) -> Result<Self> {
+        let file = File::create(path.as_ref())
+            .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            flush_every: flush_every.max(1),
+            frames_since_flush: 0,
+        })
+    }
+
+    /// Append one JSON object line for `frame`, stamped with the current
+    /// wall-clock time (milliseconds since the Unix epoch).
+    pub fn log_frame(&mut self, frame: &FrameMetrics) -> Result<()> {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        writeln!(
+            self.writer,
+            "{{\"timestamp_ms\":{},\"frame_time_ms\":{},\"render_time_ms\":{},\"gpu_time_ms\":{},\"atoms_total\":{},\"atoms_rendered\":{},\"atoms_culled\":{},\"lod_high\":{},\"lod_medium\":{},\"lod_low\":{},\"lod_minimal\":{}}}",
+            timestamp_ms,
+            frame.frame_time_ms(),
+            frame.render_time_ms(),
+            frame.gpu_time_ms(),
+            frame.atoms_total,
+            frame.atoms_rendered,
+            frame.atoms_culled,
+            frame.lod_high,
+            frame.lod_medium,
+            frame.lod_low,
+            frame.lod_minimal,
+        )?;
+
+        self.frames_since_flush += 1;
+        if self.frames_since_flush >= self.flush_every {
+            self.writer.flush()?;
+            self.frames_since_flush = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Force a flush of any buffered, unwritten lines.
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        self.frames_since_flush = 0;
+        Ok(())
+    }
+}
+
+/// How a `Counter` should be rendered in the HUD, selected per-counter by
+/// `parse_counter_layout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterMode {
+    /// Plain average + max over the window (the default, no prefix).
+    AverageMax,
+    /// Normalized sparkline series (`#` prefix in the config string).
+    Graph,
+    /// Sign and magnitude of the delta vs. the last reported value (`*`
+    /// prefix in the config string).
+    ChangeIndicator,
+}
+
+/// A named ring buffer of recent samples for one tracked quantity (fps,
+/// render_ms, atoms_culled, one of the LOD buckets, ...). Generalizes the
+/// fixed fields `PerfSummary` used to hard-code into an extensible set a
+/// HUD can pick and choose from.
+#[derive(Debug, Clone)]
+pub struct Counter {
+    name: String,
+    samples: Vec<f64>,
+    capacity: usize,
+    last_reported: Option<f64>,
+}
+
+impl Counter {
+    pub fn new(name: impl Into<String>, capacity: usize) -> Self {
+        Self {
+            name: name.into(),
+            samples: Vec::with_capacity(capacity),
+            capacity: capacity.max(1),
+            last_reported: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Push a new sample, evicting the oldest once over capacity.
+    pub fn push(&mut self, value: f64) {
+        self.samples.push(value);
+        if self.samples.len() > self.capacity {
+            self.samples.remove(0);
+        }
+    }
+
+    pub fn average(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.samples.iter().sum::<f64>() / self.samples.len() as f64
+    }
+
+    pub fn max(&self) -> f64 {
+        self.samples.iter().cloned().fold(0.0, f64::max)
+    }
+
+    /// The window's samples scaled into `0.0..=1.0` by the window's own
+    /// max, suitable for drawing a sparkline graph.
+    pub fn normalized_series(&self) -> Vec<f32> {
+        let max = self.max();
+        if max <= 0.0 {
+            return vec![0.0; self.samples.len()];
+        }
+        self.samples.iter().map(|&v| (v / max) as f32).collect()
+    }
+
+    /// Sign (+1, -1, or 0) and absolute magnitude of the latest sample
+    /// versus the last value reported through this method. Updates the
+    /// reported baseline to the latest sample, so repeated calls each see
+    /// the delta since the previous call, not since the first ever sample.
+    pub fn change_indicator(&mut self) -> (i8, f64) {
+        let latest = match self.samples.last() {
+            Some(&v) => v,
+            None => return (0, 0.0),
+        };
+        let delta = match self.last_reported {
+            Some(prev) => latest - prev,
+            None => 0.0,
+        };
+        self.last_reported = Some(latest);
+
+        let sign = if delta > 0.0 {
+            1
+        } else if delta < 0.0 {
+            -1
+        } else {
+            0
+        };
+        (sign, delta.abs())
+    }
+}
+
+/// One counter's chosen display mode, as parsed from a HUD config string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CounterDisplay {
+    pub name: String,
+    pub mode: CounterMode,
+}
+
+/// Parse a comma-separated HUD config string (e.g.
+/// `"fps,#render_ms,*atoms_culled"`) into a display layout: a bare name
+/// means `AverageMax`, a `#` prefix means `Graph`, and a `*` prefix means
+/// `ChangeIndicator`. Unknown prefixes are treated as part of the name
+/// (so a typo doesn't panic, it just shows an odd counter name). Empty
+/// entries (e.g. from a trailing comma) are skipped.
+pub fn parse_counter_layout(config: &str) -> Vec<CounterDisplay> {
+    config
+        .split(',')
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            if let Some(name) = entry.strip_prefix('#') {
+                CounterDisplay { name: name.to_string(), mode: CounterMode::Graph }
+            } else if let Some(name) = entry.strip_prefix('*') {
+                CounterDisplay { name: name.to_string(), mode: CounterMode::ChangeIndicator }
+            } else {
+                CounterDisplay { name: entry.to_string(), mode: CounterMode::AverageMax }
+            }
+        })
+        .collect()
+}
+
+/// Min/max/mean/current aggregation for one numeric series over the
+/// tracker's window - used so a single anomalous frame doesn't dominate
+/// the HUD the way pulling straight from `recent_frames.last()` would.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct WindowStats {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub current: f64,
+}
+
+impl WindowStats {
+    fn from_series(series: &[f64]) -> Self {
+        if series.is_empty() {
+            return Self::default();
+        }
+        WindowStats {
+            min: series.iter().cloned().fold(f64::INFINITY, f64::min),
+            max: series.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            mean: series.iter().sum::<f64>() / series.len() as f64,
+            current: *series.last().unwrap(),
+        }
+    }
+}
+
+/// A numerator/denominator pair (e.g. culled atoms over total atoms across
+/// the window) that keeps both the raw counts and the percentage around,
+/// rather than collapsing straight to a float.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Ratio {
+    pub numerator: usize,
+    pub denominator: usize,
+}
+
+impl Ratio {
+    pub fn new(numerator: usize, denominator: usize) -> Self {
+        Self { numerator, denominator }
+    }
+
+    pub fn percentage(&self) -> f64 {
+        if self.denominator == 0 {
+            return 0.0;
+        }
+        (self.numerator as f64 / self.denominator as f64) * 100.0
+    }
+}
+
+impl std::fmt::Display for Ratio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.1}% ({}/{})", self.percentage(), self.numerator, self.denominator)
+    }
+}
+
 /// Rolling average performance tracker
 pub struct PerformanceTracker {
     recent_frames: Vec<FrameMetrics>,
     max_history: usize,
     current_frame: Option<FrameMetrics>,
+    /// EMA time constant in seconds - larger values smooth harder and lag
+    /// further behind spikes, smaller values track the raw signal more
+    /// closely.
+    smoothing_factor: f64,
+    last_frame_end: Option<Instant>,
+    ema_frame_time_ms: Option<f64>,
+    ema_render_time_ms: Option<f64>,
+    /// Optional on-disk JSONL trace - zero cost when `None`.
+    metrics_logger: Option<MetricsLogger>,
+    /// Target frame budget in milliseconds (default 16.67ms, i.e. 60Hz),
+    /// used by `PerfSummary::frames_over_budget`/`budget_headroom_pct`.
+    target_frame_ms: f64,
+    /// Named ring-buffer counters (fps, render_ms, atoms_culled, each LOD
+    /// bucket, ...) backing a `parse_counter_layout`-configured HUD.
+    counters: HashMap<String, Counter>,
 }
 
 impl PerformanceTracker {
-    pub fn new(max_history: usize) -> Self {
+    /// `smoothing_factor` is the EMA time constant in seconds (e.g. `2.0`) -
+    /// larger values smooth harder and lag further behind spikes, smaller
+    /// values track the raw signal more closely.
+    pub fn new(max_history: usize, smoothing_factor: f64) -> Self {
         Self {
             recent_frames: Vec::with_capacity(max_history),
             max_history,
             current_frame: None,
+            smoothing_factor,
+            last_frame_end: None,
+            ema_frame_time_ms: None,
+            ema_render_time_ms: None,
+            metrics_logger: None,
+            target_frame_ms: 16.67,
+            counters: HashMap::new(),
         }
     }
 
+    /// Enable (or replace) the on-disk JSONL metrics trace.
+    pub fn set_metrics_logger(&mut self, logger: MetricsLogger) {
+        self.metrics_logger = Some(logger);
+    }
+
+    /// Look up a named counter (`"fps"`, `"render_ms"`, `"gpu_ms"`,
+    /// `"frame_ms"`, `"atoms_culled"`, `"lod_high"`, `"lod_medium"`, `"lod_low"`,
+    /// `"lod_minimal"`) populated every `end_frame`. `None` before the
+    /// first frame completes.
+    pub fn counter(&self, name: &str) -> Option<&Counter> {
+        self.counters.get(name)
+    }
+
+    /// Mutable access to a named counter - needed for `change_indicator`,
+    /// which updates its own reported-baseline state.
+    pub fn counter_mut(&mut self, name: &str) -> Option<&mut Counter> {
+        self.counters.get_mut(name)
+    }
+
+    /// Disable the on-disk JSONL metrics trace, if one was set.
+    pub fn clear_metrics_logger(&mut self) {
+        self.metrics_logger = None;
+    }
+
+    /// Set the target frame budget in milliseconds (default 16.67ms, 60Hz).
+    pub fn set_target_frame_ms(&mut self, target_frame_ms: f64) {
+        self.target_frame_ms = target_frame_ms;
+    }
+
     /// Start tracking a new frame
     pub fn start_frame(&mut self) {
         self.current_frame = Some(FrameMetrics::new());
@@ -89,6 +400,14 @@ impl PerformanceTracker {
         }
     }
 
+    /// Record the GPU-side render duration for the current frame, as
+    /// measured by a timer query the renderer owns.
+    pub fn record_gpu_duration(&mut self, gpu_duration: Duration) {
+        if let Some(ref mut frame) = self.current_frame {
+            frame.gpu_duration = gpu_duration;
+        }
+    }
+
     /// Record LOD statistics
     pub fn record_lod_stats(&mut self, high: usize, medium: usize, low: usize, minimal: usize) {
         if let Some(ref mut frame) = self.current_frame {
@@ -104,6 +423,20 @@ impl PerformanceTracker {
         if let Some(mut frame) = self.current_frame.take() {
             frame.frame_duration = frame.frame_start.elapsed();
 
+            let now = Instant::now();
+            let delta = self
+                .last_frame_end
+                .map(|last| now.duration_since(last).as_secs_f64())
+                .unwrap_or(0.0);
+            self.last_frame_end = Some(now);
+            self.update_ema(frame.frame_time_ms(), frame.render_time_ms(), delta);
+
+            if let Some(ref mut logger) = self.metrics_logger {
+                let _ = logger.log_frame(&frame);
+            }
+
+            self.record_counter_samples(&frame);
+
             // Add to history
             self.recent_frames.push(frame);
 
@@ -114,6 +447,43 @@ impl PerformanceTracker {
         }
     }
 
+    /// Feed this frame's values into the named counters backing the
+    /// configurable HUD, creating each counter lazily on first use.
+    fn record_counter_samples(&mut self, frame: &FrameMetrics) {
+        let samples: [(&str, f64); 9] = [
+            ("fps", frame.fps()),
+            ("render_ms", frame.render_time_ms()),
+            ("gpu_ms", frame.gpu_time_ms()),
+            ("frame_ms", frame.frame_time_ms()),
+            ("atoms_culled", frame.atoms_culled as f64),
+            ("lod_high", frame.lod_high as f64),
+            ("lod_medium", frame.lod_medium as f64),
+            ("lod_low", frame.lod_low as f64),
+            ("lod_minimal", frame.lod_minimal as f64),
+        ];
+        for (name, value) in samples {
+            self.counters
+                .entry(name.to_string())
+                .or_insert_with(|| Counter::new(name, self.max_history))
+                .push(value);
+        }
+    }
+
+    /// Update the EMA-smoothed frame/render time with `delta` seconds since
+    /// the previous frame. Seeds both EMAs on the first sample.
+    fn update_ema(&mut self, frame_time_ms: f64, render_time_ms: f64, delta: f64) {
+        let alpha = (delta / self.smoothing_factor).clamp(0.0, 1.0);
+
+        self.ema_frame_time_ms = Some(match self.ema_frame_time_ms {
+            Some(ema) => ema + alpha * (frame_time_ms - ema),
+            None => frame_time_ms,
+        });
+        self.ema_render_time_ms = Some(match self.ema_render_time_ms {
+            Some(ema) => ema + alpha * (render_time_ms - ema),
+            None => render_time_ms,
+        });
+    }
+
     /// Get average FPS over recent frames
     pub fn avg_fps(&self) -> f64 {
         if self.recent_frames.is_empty() {
@@ -139,6 +509,22 @@ impl PerformanceTracker {
         self.recent_frames.last()
     }
 
+    /// EMA-smoothed FPS, derived from `ema_frame_time_ms` the same way
+    /// `FrameMetrics::fps` derives FPS from a single frame's duration.
+    /// Stabler than `avg_fps` for an on-screen readout since it reacts
+    /// gradually to spikes instead of jumping with the history window.
+    pub fn ema_fps(&self) -> f64 {
+        match self.ema_frame_time_ms {
+            Some(ms) if ms > 0.0 => 1000.0 / ms,
+            _ => 0.0,
+        }
+    }
+
+    /// EMA-smoothed render time (ms).
+    pub fn ema_render_time_ms(&self) -> f64 {
+        self.ema_render_time_ms.unwrap_or(0.0)
+    }
+
     /// Get summary statistics
     pub fn summary(&self) -> PerfSummary {
         if self.recent_frames.is_empty() {
@@ -150,9 +536,50 @@ impl PerformanceTracker {
 
         let latest = self.recent_frames.last().unwrap();
 
+        let raw_frame_times: Vec<f64> = self.recent_frames.iter().map(|f| f.frame_time_ms()).collect();
+        let avg_frame_ms = raw_frame_times.iter().sum::<f64>() / raw_frame_times.len() as f64;
+        let frames_over_budget = raw_frame_times.iter().filter(|&&ms| ms > self.target_frame_ms).count();
+
+        let mut frame_times = raw_frame_times;
+        frame_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let percentile = |p: f64| -> f64 {
+            let idx = ((p * (frame_times.len() - 1) as f64).ceil() as usize).min(frame_times.len() - 1);
+            frame_times[idx]
+        };
+
+        let series_of = |extract: fn(&FrameMetrics) -> usize| -> Vec<f64> {
+            self.recent_frames.iter().map(|f| extract(f) as f64).collect()
+        };
+        let atoms_total_series = series_of(|f| f.atoms_total);
+        let atoms_rendered_series = series_of(|f| f.atoms_rendered);
+        let atoms_culled_series = series_of(|f| f.atoms_culled);
+
+        let culling_ratio = Ratio::new(
+            atoms_culled_series.iter().sum::<f64>() as usize,
+            atoms_total_series.iter().sum::<f64>() as usize,
+        );
+
         PerfSummary {
+            atoms_total_stats: WindowStats::from_series(&atoms_total_series),
+            atoms_rendered_stats: WindowStats::from_series(&atoms_rendered_series),
+            atoms_culled_stats: WindowStats::from_series(&atoms_culled_series),
+            lod_high_stats: WindowStats::from_series(&series_of(|f| f.lod_high)),
+            lod_medium_stats: WindowStats::from_series(&series_of(|f| f.lod_medium)),
+            lod_low_stats: WindowStats::from_series(&series_of(|f| f.lod_low)),
+            lod_minimal_stats: WindowStats::from_series(&series_of(|f| f.lod_minimal)),
+            culling_ratio,
             avg_fps,
             avg_render_ms,
+            ema_fps: self.ema_fps(),
+            ema_render_ms: self.ema_render_time_ms(),
+            p50_frame_ms: percentile(0.50),
+            p95_frame_ms: percentile(0.95),
+            p99_frame_ms: percentile(0.99),
+            max_frame_ms: *frame_times.last().unwrap(),
+            avg_frame_ms,
+            gpu_time_ms: latest.gpu_time_ms(),
+            target_frame_ms: self.target_frame_ms,
+            frames_over_budget,
             atoms_total: latest.atoms_total,
             atoms_rendered: latest.atoms_rendered,
             atoms_culled: latest.atoms_culled,
@@ -170,6 +597,16 @@ impl PerformanceTracker {
 pub struct PerfSummary {
     pub avg_fps: f64,
     pub avg_render_ms: f64,
+    pub ema_fps: f64,
+    pub ema_render_ms: f64,
+    pub p50_frame_ms: f64,
+    pub p95_frame_ms: f64,
+    pub p99_frame_ms: f64,
+    pub max_frame_ms: f64,
+    pub avg_frame_ms: f64,
+    pub gpu_time_ms: f64,
+    pub target_frame_ms: f64,
+    pub frames_over_budget: usize,
     pub atoms_total: usize,
     pub atoms_rendered: usize,
     pub atoms_culled: usize,
@@ -178,6 +615,17 @@ pub struct PerfSummary {
     pub lod_low: usize,
     pub lod_minimal: usize,
     pub sample_count: usize,
+    /// Min/max/mean/current over the whole window, rather than just the
+    /// latest frame's snapshot - see `WindowStats`.
+    pub atoms_total_stats: WindowStats,
+    pub atoms_rendered_stats: WindowStats,
+    pub atoms_culled_stats: WindowStats,
+    pub lod_high_stats: WindowStats,
+    pub lod_medium_stats: WindowStats,
+    pub lod_low_stats: WindowStats,
+    pub lod_minimal_stats: WindowStats,
+    /// Culled/total atoms summed across the whole window.
+    pub culling_ratio: Ratio,
 }
 
 impl PerfSummary {
@@ -194,6 +642,16 @@ impl PerfSummary {
         }
         (self.atoms_rendered as f64 / self.atoms_total as f64) * 100.0
     }
+
+    /// Percentage of the frame budget still unspent by the average frame
+    /// time - positive when comfortably under budget, negative once the
+    /// average frame is slower than `target_frame_ms`.
+    pub fn budget_headroom_pct(&self) -> f64 {
+        if self.target_frame_ms <= 0.0 {
+            return 0.0;
+        }
+        ((self.target_frame_ms - self.avg_frame_ms) / self.target_frame_ms) * 100.0
+    }
 }
 
 #[cfg(test)]
@@ -203,7 +661,7 @@ mod tests {
 
     #[test]
     fn test_frame_metrics() {
-        let mut tracker = PerformanceTracker::new(60);
+        let mut tracker = PerformanceTracker::new(60, 2.0);
 
         tracker.start_frame();
         tracker.start_render();
@@ -220,7 +678,7 @@ mod tests {
 
     #[test]
     fn test_rolling_average() {
-        let mut tracker = PerformanceTracker::new(3);
+        let mut tracker = PerformanceTracker::new(3, 2.0);
 
         // Add 5 frames, should only keep last 3
         for i in 0..5 {
@@ -232,6 +690,52 @@ mod tests {
         assert_eq!(tracker.recent_frames.len(), 3);
     }
 
+    #[test]
+    fn test_percentile_frame_times_are_ordered_and_capture_the_worst_frame() {
+        let mut tracker = PerformanceTracker::new(60, 2.0);
+
+        // A handful of fast frames and one deliberately slow one.
+        for sleep_ms in [1, 1, 1, 1, 1, 1, 1, 1, 1, 30] {
+            tracker.start_frame();
+            thread::sleep(Duration::from_millis(sleep_ms));
+            tracker.end_frame();
+        }
+
+        let summary = tracker.summary();
+        assert!(summary.p50_frame_ms <= summary.p95_frame_ms);
+        assert!(summary.p95_frame_ms <= summary.p99_frame_ms);
+        assert!(summary.p99_frame_ms <= summary.max_frame_ms);
+        // The worst frame should capture the deliberately slow one.
+        assert!(summary.max_frame_ms >= 30.0);
+    }
+
+    #[test]
+    fn test_ema_seeds_from_first_sample_then_smooths_toward_new_values() {
+        let mut tracker = PerformanceTracker::new(60, 2.0);
+
+        tracker.start_frame();
+        tracker.end_render(100, 100, 0);
+        tracker.end_frame();
+
+        // First sample seeds the EMA exactly, regardless of smoothing_factor.
+        let first_ema = tracker.ema_render_time_ms();
+        assert!(first_ema > 0.0);
+
+        thread::sleep(Duration::from_millis(10));
+        tracker.start_frame();
+        tracker.start_render();
+        thread::sleep(Duration::from_millis(20));
+        tracker.end_render(100, 100, 0);
+        tracker.end_frame();
+
+        // The EMA should move from the first sample toward the (larger)
+        // second render time, but not jump all the way to it.
+        let second_ema = tracker.ema_render_time_ms();
+        assert!(second_ema > first_ema);
+        assert!(second_ema < tracker.latest().unwrap().render_time_ms());
+        assert!(tracker.ema_fps() > 0.0);
+    }
+
     #[test]
     fn test_culling_efficiency() {
         let summary = PerfSummary {
@@ -244,4 +748,194 @@ mod tests {
         assert_eq!(summary.culling_efficiency(), 40.0);
         assert_eq!(summary.render_efficiency(), 60.0);
     }
+
+    #[test]
+    fn test_ratio_formats_percentage_and_raw_counts() {
+        let ratio = Ratio::new(40, 100);
+        assert_eq!(ratio.percentage(), 40.0);
+        assert_eq!(ratio.to_string(), "40.0% (40/100)");
+
+        assert_eq!(Ratio::new(0, 0).percentage(), 0.0);
+    }
+
+    #[test]
+    fn test_window_stats_from_series() {
+        let stats = WindowStats::from_series(&[10.0, 30.0, 20.0]);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.max, 30.0);
+        assert_eq!(stats.mean, 20.0);
+        assert_eq!(stats.current, 20.0); // last element, not the max
+    }
+
+    #[test]
+    fn test_summary_aggregates_lod_and_culling_across_the_window_not_just_latest_frame() {
+        let mut tracker = PerformanceTracker::new(60, 2.0);
+
+        // A normal frame...
+        tracker.start_frame();
+        tracker.end_render(100, 80, 20);
+        tracker.record_lod_stats(40, 30, 20, 10);
+        tracker.end_frame();
+
+        // ...then one anomalous frame with very different numbers.
+        tracker.start_frame();
+        tracker.end_render(100, 0, 100);
+        tracker.record_lod_stats(0, 0, 0, 100);
+        tracker.end_frame();
+
+        let summary = tracker.summary();
+
+        // The latest-frame-only fields still reflect just the anomalous frame...
+        assert_eq!(summary.atoms_culled, 100);
+        // ...but the aggregated stats and ratio see both frames, so the
+        // anomaly doesn't dominate the picture.
+        assert_eq!(summary.atoms_culled_stats.mean, 60.0);
+        assert_eq!(summary.atoms_culled_stats.min, 20.0);
+        assert_eq!(summary.atoms_culled_stats.max, 100.0);
+        assert_eq!(summary.culling_ratio, Ratio::new(120, 200));
+        assert_eq!(summary.lod_minimal_stats.mean, 55.0);
+    }
+
+    #[test]
+    fn test_budget_headroom_pct() {
+        let under_budget = PerfSummary {
+            target_frame_ms: 16.67,
+            avg_frame_ms: 8.335, // exactly half the budget
+            ..Default::default()
+        };
+        assert!((under_budget.budget_headroom_pct() - 50.0).abs() < 1e-9);
+
+        let over_budget = PerfSummary {
+            target_frame_ms: 16.67,
+            avg_frame_ms: 33.34, // double the budget
+            ..Default::default()
+        };
+        assert!(over_budget.budget_headroom_pct() < 0.0);
+    }
+
+    #[test]
+    fn test_frames_over_budget_counts_only_frames_past_the_target() {
+        let mut tracker = PerformanceTracker::new(60, 2.0);
+        tracker.set_target_frame_ms(5.0);
+
+        // A couple of fast frames, then one that blows well past the 5ms budget.
+        for sleep_ms in [1, 1, 20] {
+            tracker.start_frame();
+            thread::sleep(Duration::from_millis(sleep_ms));
+            tracker.end_frame();
+        }
+
+        let summary = tracker.summary();
+        assert_eq!(summary.frames_over_budget, 1);
+        assert_eq!(summary.target_frame_ms, 5.0);
+    }
+
+    #[test]
+    fn test_gpu_duration_is_recorded_and_surfaced_in_summary() {
+        let mut tracker = PerformanceTracker::new(60, 2.0);
+
+        tracker.start_frame();
+        tracker.record_gpu_duration(Duration::from_millis(4));
+        tracker.end_frame();
+
+        assert_eq!(tracker.latest().unwrap().gpu_time_ms(), 4.0);
+        assert_eq!(tracker.summary().gpu_time_ms, 4.0);
+    }
+
+    #[test]
+    fn test_parse_counter_layout_reads_prefixes() {
+        let layout = parse_counter_layout("fps,#render_ms,*atoms_culled");
+        assert_eq!(
+            layout,
+            vec![
+                CounterDisplay { name: "fps".to_string(), mode: CounterMode::AverageMax },
+                CounterDisplay { name: "render_ms".to_string(), mode: CounterMode::Graph },
+                CounterDisplay { name: "atoms_culled".to_string(), mode: CounterMode::ChangeIndicator },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_counter_layout_skips_empty_entries() {
+        let layout = parse_counter_layout("fps,,#render_ms,");
+        assert_eq!(layout.len(), 2);
+    }
+
+    #[test]
+    fn test_counter_average_max_and_normalized_series() {
+        let mut counter = Counter::new("render_ms", 4);
+        for v in [2.0, 4.0, 8.0] {
+            counter.push(v);
+        }
+
+        assert_eq!(counter.average(), 14.0 / 3.0);
+        assert_eq!(counter.max(), 8.0);
+        assert_eq!(counter.normalized_series(), vec![0.25, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_counter_change_indicator_reports_delta_since_last_call() {
+        let mut counter = Counter::new("fps", 4);
+        counter.push(60.0);
+
+        // First call has no prior baseline.
+        assert_eq!(counter.change_indicator(), (0, 0.0));
+
+        counter.push(50.0);
+        let (sign, magnitude) = counter.change_indicator();
+        assert_eq!(sign, -1);
+        assert!((magnitude - 10.0).abs() < 1e-9);
+
+        // Calling again immediately (no new sample) sees no further change.
+        assert_eq!(counter.change_indicator(), (0, 0.0));
+    }
+
+    #[test]
+    fn test_performance_tracker_populates_named_counters_each_frame() {
+        let mut tracker = PerformanceTracker::new(60, 2.0);
+
+        tracker.start_frame();
+        tracker.end_render(100, 80, 20);
+        tracker.record_lod_stats(10, 20, 30, 40);
+        tracker.end_frame();
+
+        assert_eq!(tracker.counter("atoms_culled").unwrap().average(), 20.0);
+        assert_eq!(tracker.counter("lod_minimal").unwrap().average(), 40.0);
+        assert!(tracker.counter("fps").is_some());
+        assert!(tracker.counter("does_not_exist").is_none());
+    }
+
+    #[test]
+    fn test_metrics_logger_writes_one_jsonl_line_per_frame() {
+        let path = std::env::temp_dir().join("axiom_test_perf_metrics.jsonl");
+
+        let mut tracker = PerformanceTracker::new(60, 2.0);
+        tracker.set_metrics_logger(MetricsLogger::new(&path, 1).unwrap());
+
+        for _ in 0..3 {
+            tracker.start_frame();
+            tracker.end_render(10, 8, 2);
+            tracker.end_frame();
+        }
+        // Drop the tracker (and its logger) so the buffered writer is flushed.
+        drop(tracker);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains("\"atoms_total\":10"));
+        assert!(lines[0].contains("\"timestamp_ms\":"));
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_performance_tracker_with_no_logger_has_zero_io_cost() {
+        // Just confirms the default tracker has no logger attached and
+        // end_frame works without one.
+        let mut tracker = PerformanceTracker::new(60, 2.0);
+        tracker.start_frame();
+        tracker.end_frame();
+        assert!(tracker.metrics_logger.is_none());
+    }
 }
@@ -5,9 +5,44 @@ use crate::atoms::{Atoms, Bonds};
 use crate::colors::{element_to_ball_stick_radius, element_to_cpk_color};
 use crate::errors::{AxiomError, Result};
 use bytemuck::{Pod, Zeroable};
+use serde::Serialize;
+use std::sync::Mutex;
 use wgpu;
 use wgpu::util::DeviceExt;
 
+/// Depth format used by both the headless and windowed pipelines so
+/// imposter spheres occlude each other correctly instead of compositing
+/// purely by draw order.
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+fn depth_stencil_state() -> wgpu::DepthStencilState {
+    wgpu::DepthStencilState {
+        format: DEPTH_FORMAT,
+        depth_write_enabled: true,
+        depth_compare: wgpu::CompareFunction::Less,
+        stencil: wgpu::StencilState::default(),
+        bias: wgpu::DepthBiasState::default(),
+    }
+}
+
+fn create_depth_texture(device: &wgpu::Device, width: u32, height: u32) -> wgpu::TextureView {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Depth Texture"),
+        size: wgpu::Extent3d {
+            width: width.max(1),
+            height: height.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: DEPTH_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
 /// Helper for buffer dimensions with proper padding
 struct BufferDimensions {
     #[allow(dead_code)]
@@ -34,11 +69,42 @@ impl BufferDimensions {
     }
 }
 
+/// Tone-mapping operator applied to the HDR render target before it is
+/// written out as `Rgba8Unorm`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToneMapOperator {
+    /// Narkowicz ACES fit: smooth highlight rolloff, no blown-out whites.
+    Aces,
+    /// Simple `c / (1 + c)` Reinhard operator.
+    Reinhard,
+    /// Clamp only (no tone mapping) - useful for debugging the raw HDR buffer.
+    None,
+}
+
+/// How atom spheres are drawn: ray-traced billboards or real mesh geometry.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SphereRenderMode {
+    /// Per-pixel ray-sphere intersection on a camera-facing quad (default).
+    Imposter,
+    /// Instanced icosphere mesh, drawn with `draw_indexed`.
+    Mesh,
+}
+
 /// Renderer configuration
 pub struct RendererConfig {
     pub width: u32,
     pub height: u32,
     pub headless: bool,
+    /// Exposure multiplier applied before tone mapping.
+    pub exposure: f32,
+    /// Tone-mapping operator used by the HDR post-process pass in `render`.
+    pub tone_map: ToneMapOperator,
+    /// Imposter billboards vs instanced icosphere mesh geometry.
+    pub sphere_mode: SphereRenderMode,
+    /// Icosahedron subdivision level used when `sphere_mode` is `Mesh`.
+    /// 0 is a plain icosahedron (20 triangles); each level quadruples the
+    /// triangle count.
+    pub mesh_subdivisions: u32,
 }
 
 impl Default for RendererConfig {
@@ -47,10 +113,38 @@ impl Default for RendererConfig {
             width: 1920,
             height: 1080,
             headless: true,
+            exposure: 1.0,
+            tone_map: ToneMapOperator::Aces,
+            sphere_mode: SphereRenderMode::Imposter,
+            mesh_subdivisions: 2,
         }
     }
 }
 
+/// wgpu validation/out-of-memory diagnostics accumulated over the renderer's
+/// lifetime, via `push_error_scope`/`pop_error_scope` around `Renderer::new`
+/// and `Renderer::render` - the same error-scope mechanism wgpu itself uses
+/// to report validation-layer messages, surfaced here instead of only going
+/// to stderr so a host app can show why a render came out wrong or failed.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GpuDiagnostics {
+    pub adapter_name: String,
+    pub backend: String,
+    pub validation_errors: Vec<String>,
+    pub oom_events: usize,
+    pub device_lost: bool,
+}
+
+/// Classify a captured `wgpu::Error` into the `GpuDiagnostics` it belongs to -
+/// out-of-memory errors get tallied, everything else (validation failures)
+/// is recorded verbatim so the frontend can show the driver's own message.
+fn record_gpu_error(diagnostics: &mut GpuDiagnostics, error: wgpu::Error) {
+    match error {
+        wgpu::Error::OutOfMemory { .. } => diagnostics.oom_events += 1,
+        other => diagnostics.validation_errors.push(other.to_string()),
+    }
+}
+
 /// Vertex data for GPU (per-instance data for each atom)
 #[repr(C)]
 #[derive(Copy, Clone, Pod, Zeroable)]
@@ -61,6 +155,162 @@ struct AtomVertex {
     _padding: f32, // Align to 16 bytes
 }
 
+/// Build per-instance GPU vertex data (position/radius/CPK color) from atoms.
+/// Shared by single-frame `render` and the buffer-reusing `render_sequence*`.
+fn atoms_to_vertices(atoms: &Atoms) -> Vec<AtomVertex> {
+    let mut vertices = Vec::with_capacity(atoms.len());
+    for i in 0..atoms.len() {
+        let atomic_num = atoms.elements[i];
+        let color = element_to_cpk_color(atomic_num);
+        let radius = element_to_ball_stick_radius(atomic_num);
+
+        vertices.push(AtomVertex {
+            position: [atoms.x[i], atoms.y[i], atoms.z[i]],
+            radius,
+            color,
+            _padding: 0.0,
+        });
+    }
+    vertices
+}
+
+/// Per-vertex data for the shared unit-sphere mesh used by `SphereRenderMode::Mesh`.
+/// Drawn instanced against the existing per-atom `AtomVertex` instance buffer.
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct MeshVertex {
+    position: [f32; 3],
+    _padding0: f32,
+    normal: [f32; 3],
+    _padding1: f32,
+}
+
+/// Build a unit-radius icosphere by subdividing an icosahedron `subdivisions`
+/// times, re-normalizing each new vertex so it stays on the unit sphere.
+/// Mirrors the shared-mesh / instance-buffer approach from the learn-wgpu
+/// model-loading tutorials, but generated procedurally since atoms have no
+/// on-disk mesh asset.
+fn generate_icosphere(subdivisions: u32) -> (Vec<MeshVertex>, Vec<u32>) {
+    let t = (1.0_f32 + 5.0_f32.sqrt()) / 2.0;
+    let mut positions: Vec<[f32; 3]> = vec![
+        [-1.0, t, 0.0], [1.0, t, 0.0], [-1.0, -t, 0.0], [1.0, -t, 0.0],
+        [0.0, -1.0, t], [0.0, 1.0, t], [0.0, -1.0, -t], [0.0, 1.0, -t],
+        [t, 0.0, -1.0], [t, 0.0, 1.0], [-t, 0.0, -1.0], [-t, 0.0, 1.0],
+    ];
+    for p in positions.iter_mut() {
+        let len = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+        *p = [p[0] / len, p[1] / len, p[2] / len];
+    }
+
+    let mut indices: Vec<[u32; 3]> = vec![
+        [0, 11, 5], [0, 5, 1], [0, 1, 7], [0, 7, 10], [0, 10, 11],
+        [1, 5, 9], [5, 11, 4], [11, 10, 2], [10, 7, 6], [7, 1, 8],
+        [3, 9, 4], [3, 4, 2], [3, 2, 6], [3, 6, 8], [3, 8, 9],
+        [4, 9, 5], [2, 4, 11], [6, 2, 10], [8, 6, 7], [9, 8, 1],
+    ];
+
+    let mut midpoint_cache: std::collections::HashMap<(u32, u32), u32> = std::collections::HashMap::new();
+    let mut midpoint = |a: u32, b: u32, positions: &mut Vec<[f32; 3]>| -> u32 {
+        let key = if a < b { (a, b) } else { (b, a) };
+        if let Some(&idx) = midpoint_cache.get(&key) {
+            return idx;
+        }
+        let pa = positions[a as usize];
+        let pb = positions[b as usize];
+        let mid = [(pa[0] + pb[0]) * 0.5, (pa[1] + pb[1]) * 0.5, (pa[2] + pb[2]) * 0.5];
+        let len = (mid[0] * mid[0] + mid[1] * mid[1] + mid[2] * mid[2]).sqrt();
+        let idx = positions.len() as u32;
+        positions.push([mid[0] / len, mid[1] / len, mid[2] / len]);
+        midpoint_cache.insert(key, idx);
+        idx
+    };
+
+    for _ in 0..subdivisions {
+        let mut next_indices = Vec::with_capacity(indices.len() * 4);
+        for face in &indices {
+            let [a, b, c] = *face;
+            let ab = midpoint(a, b, &mut positions);
+            let bc = midpoint(b, c, &mut positions);
+            let ca = midpoint(c, a, &mut positions);
+            next_indices.push([a, ab, ca]);
+            next_indices.push([b, bc, ab]);
+            next_indices.push([c, ca, bc]);
+            next_indices.push([ab, bc, ca]);
+        }
+        indices = next_indices;
+    }
+
+    // A unit sphere centered at the origin has surface normal == position.
+    let vertices = positions
+        .iter()
+        .map(|p| MeshVertex { position: *p, _padding0: 0.0, normal: *p, _padding1: 0.0 })
+        .collect();
+    let flat_indices = indices.into_iter().flatten().collect();
+    (vertices, flat_indices)
+}
+
+/// Build the instanced icosphere mesh pipeline. Per-vertex data comes from
+/// the shared unit-sphere mesh buffer; per-instance data reuses the existing
+/// `AtomVertex` layout (position/radius/color) from the imposter path.
+fn create_mesh_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    pipeline_layout: &wgpu::PipelineLayout,
+    color_format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Mesh Sphere Pipeline"),
+        layout: Some(pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_mesh_main"),
+            buffers: &[
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<MeshVertex>() as u64,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &[
+                        wgpu::VertexAttribute { offset: 0, shader_location: 0, format: wgpu::VertexFormat::Float32x3 },
+                        wgpu::VertexAttribute { offset: 16, shader_location: 1, format: wgpu::VertexFormat::Float32x3 },
+                    ],
+                },
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<AtomVertex>() as u64,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &[
+                        wgpu::VertexAttribute { offset: 0, shader_location: 2, format: wgpu::VertexFormat::Float32x3 },
+                        wgpu::VertexAttribute { offset: 12, shader_location: 3, format: wgpu::VertexFormat::Float32 },
+                        wgpu::VertexAttribute { offset: 16, shader_location: 4, format: wgpu::VertexFormat::Float32x3 },
+                    ],
+                },
+            ],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_mesh_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: color_format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            unclipped_depth: false,
+            conservative: false,
+        },
+        depth_stencil: Some(depth_stencil_state()),
+        multisample: wgpu::MultisampleState { count: 1, mask: !0, alpha_to_coverage_enabled: false },
+        multiview: None,
+        cache: None,
+    })
+}
+
 /// Camera uniform data
 #[repr(C)]
 #[derive(Copy, Clone, Pod, Zeroable)]
@@ -71,6 +321,22 @@ struct CameraUniform {
     _padding: f32,
 }
 
+/// Per-instance data for a GPU cylinder-imposter bond. The fragment shader
+/// ray-casts a capsule between `point_a`/`point_b` and splits the color at
+/// the midpoint so each half takes its atom's CPK color.
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct BondVertex {
+    point_a: [f32; 3],
+    radius: f32,
+    point_b: [f32; 3],
+    _padding0: f32,
+    color_a: [f32; 3],
+    _padding1: f32,
+    color_b: [f32; 3],
+    _padding2: f32,
+}
+
 /// Main renderer struct
 pub struct Renderer {
     device: wgpu::Device,
@@ -82,8 +348,346 @@ pub struct Renderer {
     camera_up: [f32; 3],
     // GPU resources
     render_pipeline: wgpu::RenderPipeline,
+    bond_render_pipeline: wgpu::RenderPipeline,
+    mesh_pipeline: wgpu::RenderPipeline,
+    mesh_vertex_buffer: wgpu::Buffer,
+    mesh_index_buffer: wgpu::Buffer,
+    mesh_index_count: u32,
     camera_buffer: wgpu::Buffer,
     camera_bind_group: wgpu::BindGroup,
+    // HDR post-process (headless stills only; None in windowed mode)
+    hdr_pipeline: Option<wgpu::RenderPipeline>,
+    hdr_mesh_pipeline: Option<wgpu::RenderPipeline>,
+    tonemap_pipeline: Option<wgpu::RenderPipeline>,
+    tonemap_bind_group_layout: Option<wgpu::BindGroupLayout>,
+    linear_sampler: Option<wgpu::Sampler>,
+    post_process_buffer: Option<wgpu::Buffer>,
+    // Windowed-mode resources (None when running headless)
+    surface: Option<wgpu::Surface<'static>>,
+    surface_format: Option<wgpu::TextureFormat>,
+    // Accumulated wgpu validation/OOM diagnostics (see `GpuDiagnostics`) -
+    // a Mutex since `render` only takes `&self` but still needs to record
+    // errors caught by its error scopes.
+    gpu_diagnostics: Mutex<GpuDiagnostics>,
+    // Flipped by the device-lost callback registered in `new` - kept outside
+    // `gpu_diagnostics` since it's set from a callback that can't reach
+    // back into `self`.
+    device_lost: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+/// Uniform driving the tone-mapping fullscreen pass.
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct PostProcessUniform {
+    exposure: f32,
+    operator: u32, // 0 = ACES, 1 = Reinhard, 2 = none
+    _padding: [f32; 2],
+}
+
+const HDR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+/// Build the HDR sphere pipeline (identical to the LDR one but targeting
+/// `HDR_FORMAT`) plus the fullscreen tone-mapping pipeline that resolves it.
+fn create_hdr_pipelines(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    sphere_pipeline_layout: &wgpu::PipelineLayout,
+) -> (wgpu::RenderPipeline, wgpu::RenderPipeline, wgpu::RenderPipeline, wgpu::BindGroupLayout, wgpu::Sampler, wgpu::Buffer) {
+    let hdr_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("HDR Sphere Pipeline"),
+        layout: Some(sphere_pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_main"),
+            buffers: &[wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<AtomVertex>() as u64,
+                step_mode: wgpu::VertexStepMode::Instance,
+                attributes: &[
+                    wgpu::VertexAttribute { offset: 0, shader_location: 0, format: wgpu::VertexFormat::Float32x3 },
+                    wgpu::VertexAttribute { offset: 12, shader_location: 1, format: wgpu::VertexFormat::Float32 },
+                    wgpu::VertexAttribute { offset: 16, shader_location: 2, format: wgpu::VertexFormat::Float32x3 },
+                ],
+            }],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: HDR_FORMAT,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            unclipped_depth: false,
+            conservative: false,
+        },
+        depth_stencil: Some(depth_stencil_state()),
+        multisample: wgpu::MultisampleState { count: 1, mask: !0, alpha_to_coverage_enabled: false },
+        multiview: None,
+        cache: None,
+    });
+
+    let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+        label: Some("HDR Resolve Sampler"),
+        mag_filter: wgpu::FilterMode::Nearest,
+        min_filter: wgpu::FilterMode::Nearest,
+        ..Default::default()
+    });
+
+    let post_process_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Post Process Uniform"),
+        size: std::mem::size_of::<PostProcessUniform>() as u64,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let tonemap_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("Tonemap Bind Group Layout"),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+        ],
+    });
+
+    let tonemap_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("Tonemap Pipeline Layout"),
+        bind_group_layouts: &[&tonemap_bind_group_layout],
+        push_constant_ranges: &[],
+    });
+
+    let tonemap_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Tonemap Pipeline"),
+        layout: Some(&tonemap_pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_fullscreen"),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_tonemap"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            unclipped_depth: false,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState { count: 1, mask: !0, alpha_to_coverage_enabled: false },
+        multiview: None,
+        cache: None,
+    });
+
+    let hdr_mesh_pipeline = create_mesh_pipeline(device, shader, sphere_pipeline_layout, HDR_FORMAT);
+
+    (hdr_pipeline, hdr_mesh_pipeline, tonemap_pipeline, tonemap_bind_group_layout, sampler, post_process_buffer)
+}
+
+/// Build the cylinder-imposter bond pipeline, sharing the camera bind
+/// group layout with the sphere pipeline so both passes can use the same
+/// bind group and interleave correctly against one depth buffer.
+fn create_bond_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    pipeline_layout: &wgpu::PipelineLayout,
+    color_format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Bond Render Pipeline"),
+        layout: Some(pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_bond_main"),
+            buffers: &[wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<BondVertex>() as u64,
+                step_mode: wgpu::VertexStepMode::Instance,
+                attributes: &[
+                    wgpu::VertexAttribute { offset: 0, shader_location: 0, format: wgpu::VertexFormat::Float32x3 },
+                    wgpu::VertexAttribute { offset: 12, shader_location: 1, format: wgpu::VertexFormat::Float32 },
+                    wgpu::VertexAttribute { offset: 16, shader_location: 2, format: wgpu::VertexFormat::Float32x3 },
+                    wgpu::VertexAttribute { offset: 32, shader_location: 3, format: wgpu::VertexFormat::Float32x3 },
+                    wgpu::VertexAttribute { offset: 48, shader_location: 4, format: wgpu::VertexFormat::Float32x3 },
+                ],
+            }],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_bond_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: color_format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            unclipped_depth: false,
+            conservative: false,
+        },
+        depth_stencil: Some(depth_stencil_state()),
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+        cache: None,
+    })
+}
+
+/// Orbit (arc-ball) camera controller for the windowed viewer.
+///
+/// Accumulates yaw/pitch from mouse drag deltas and re-derives
+/// `camera_position` from `target` each frame, so the camera always
+/// orbits a fixed point rather than drifting like a free-fly camera.
+pub struct CameraController {
+    pub target: [f32; 3],
+    pub up: [f32; 3],
+    yaw: f32,
+    pitch: f32,
+    radius: f32,
+    pub rotate_speed: f32,
+    pub pan_speed: f32,
+    pub zoom_speed: f32,
+    pub min_radius: f32,
+}
+
+impl CameraController {
+    /// Derive yaw/pitch/radius from an initial eye position looking at `target`.
+    pub fn new(position: [f32; 3], target: [f32; 3], up: [f32; 3]) -> Self {
+        let d = [
+            position[0] - target[0],
+            position[1] - target[1],
+            position[2] - target[2],
+        ];
+        let radius = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt().max(0.001);
+        let pitch = (d[1] / radius).clamp(-1.0, 1.0).asin();
+        let yaw = d[0].atan2(d[2]);
+
+        Self {
+            target,
+            up,
+            yaw,
+            pitch,
+            radius,
+            rotate_speed: 0.005,
+            pan_speed: 0.0025,
+            zoom_speed: 0.1,
+            min_radius: 0.5,
+        }
+    }
+
+    /// Mouse drag (left button): arc-ball rotation around `target`.
+    pub fn process_drag(&mut self, dx: f32, dy: f32) {
+        self.yaw += dx * self.rotate_speed;
+        self.pitch += dy * self.rotate_speed;
+
+        // Clamp to avoid the look-at degeneracy when forward aligns with `up`.
+        let limit = 89.0_f32.to_radians();
+        self.pitch = self.pitch.clamp(-limit, limit);
+    }
+
+    /// Mouse wheel: dolly the eye along the view vector (zoom).
+    pub fn process_scroll(&mut self, delta: f32) {
+        self.radius = (self.radius - delta * self.zoom_speed * self.radius).max(self.min_radius);
+    }
+
+    /// Middle-button drag: pan `target` across the view plane.
+    pub fn process_pan(&mut self, dx: f32, dy: f32) {
+        let pos = self.camera_position();
+        let forward = normalize(sub(self.target, pos));
+        let right = normalize(cross(forward, self.up));
+        let true_up = cross(right, forward);
+
+        let scale = self.pan_speed * self.radius;
+        for i in 0..3 {
+            self.target[i] += (-dx * right[i] + dy * true_up[i]) * scale;
+        }
+    }
+
+    /// Recompute the eye position from yaw/pitch/radius around `target`.
+    pub fn camera_position(&self) -> [f32; 3] {
+        [
+            self.target[0] + self.radius * self.pitch.cos() * self.yaw.sin(),
+            self.target[1] + self.radius * self.pitch.sin(),
+            self.target[2] + self.radius * self.pitch.cos() * self.yaw.cos(),
+        ]
+    }
+
+    /// Push the controller's current state into a `Renderer`'s camera uniform.
+    pub fn apply(&self, renderer: &mut Renderer) {
+        renderer.set_camera(self.camera_position(), self.target, self.up);
+    }
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let len = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if len > 1e-6 {
+        [v[0] / len, v[1] / len, v[2] / len]
+    } else {
+        v
+    }
 }
 
 impl Renderer {
@@ -127,6 +731,21 @@ impl Renderer {
             .await
             .map_err(|e| AxiomError::RenderError(format!("Failed to create device: {}", e)))?;
 
+        let adapter_info = adapter.get_info();
+
+        let device_lost = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        {
+            let device_lost = device_lost.clone();
+            device.set_device_lost_callback(move |_reason, _message| {
+                device_lost.store(true, std::sync::atomic::Ordering::Relaxed);
+            });
+        }
+
+        // Catch validation/OOM errors raised while building pipelines and
+        // buffers below, same error-scope mechanism used around `render`.
+        device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+
         // Default camera position (looking down -Z axis)
         let camera_position = [0.0, 0.0, 50.0];
         let camera_target = [0.0, 0.0, 0.0];
@@ -230,7 +849,7 @@ impl Renderer {
                 unclipped_depth: false,
                 conservative: false,
             },
-            depth_stencil: None,
+            depth_stencil: Some(depth_stencil_state()),
             multisample: wgpu::MultisampleState {
                 count: 1,
                 mask: !0,
@@ -240,6 +859,38 @@ impl Renderer {
             cache: None,
         });
 
+        let bond_render_pipeline = create_bond_pipeline(&device, &shader, &pipeline_layout, wgpu::TextureFormat::Rgba8Unorm);
+
+        let mesh_pipeline = create_mesh_pipeline(&device, &shader, &pipeline_layout, wgpu::TextureFormat::Rgba8Unorm);
+        let (mesh_vertices, mesh_indices) = generate_icosphere(config.mesh_subdivisions);
+        let mesh_vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Mesh Sphere Vertex Buffer"),
+            contents: bytemuck::cast_slice(&mesh_vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let mesh_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Mesh Sphere Index Buffer"),
+            contents: bytemuck::cast_slice(&mesh_indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        let mesh_index_count = mesh_indices.len() as u32;
+
+        let (hdr_pipeline, hdr_mesh_pipeline, tonemap_pipeline, tonemap_bind_group_layout, linear_sampler, post_process_buffer) =
+            create_hdr_pipelines(&device, &shader, &pipeline_layout);
+
+        let mut gpu_diagnostics = GpuDiagnostics {
+            adapter_name: adapter_info.name.clone(),
+            backend: format!("{:?}", adapter_info.backend),
+            ..GpuDiagnostics::default()
+        };
+        // Pop in reverse push order: Validation (innermost) then OutOfMemory.
+        if let Some(error) = pollster::block_on(device.pop_error_scope()) {
+            record_gpu_error(&mut gpu_diagnostics, error);
+        }
+        if let Some(error) = pollster::block_on(device.pop_error_scope()) {
+            record_gpu_error(&mut gpu_diagnostics, error);
+        }
+
         Ok(Self {
             device,
             queue,
@@ -248,8 +899,23 @@ impl Renderer {
             camera_target,
             camera_up,
             render_pipeline,
+            bond_render_pipeline,
+            mesh_pipeline,
+            mesh_vertex_buffer,
+            mesh_index_buffer,
+            mesh_index_count,
             camera_buffer,
             camera_bind_group,
+            hdr_pipeline: Some(hdr_pipeline),
+            hdr_mesh_pipeline: Some(hdr_mesh_pipeline),
+            tonemap_pipeline: Some(tonemap_pipeline),
+            tonemap_bind_group_layout: Some(tonemap_bind_group_layout),
+            linear_sampler: Some(linear_sampler),
+            post_process_buffer: Some(post_process_buffer),
+            surface: None,
+            surface_format: None,
+            gpu_diagnostics: Mutex::new(gpu_diagnostics),
+            device_lost,
         })
     }
 
@@ -258,170 +924,1066 @@ impl Renderer {
         pollster::block_on(Self::new(config))
     }
 
-    /// Set camera position
-    pub fn set_camera(&mut self, position: [f32; 3], target: [f32; 3], up: [f32; 3]) {
-        self.camera_position = position;
-        self.camera_target = target;
-        self.camera_up = up;
-    }
-
-    /// Reset camera to default
-    pub fn reset_camera(&mut self) {
-        self.camera_position = [0.0, 0.0, 50.0];
-        self.camera_target = [0.0, 0.0, 0.0];
-        self.camera_up = [0.0, 1.0, 0.0];
-    }
-
-    /// Build view matrix (look-at matrix)
-    fn build_view_matrix(&self) -> [[f32; 4]; 4] {
-        let pos = self.camera_position;
-        let target = self.camera_target;
-        let up = self.camera_up;
+    /// Initialize the renderer for windowed (interactive) presentation.
+    ///
+    /// Creates a `wgpu::Surface` from the given window so the adapter is
+    /// selected with a compatible surface and frames can be presented
+    /// directly instead of read back to a PNG buffer.
+    pub async fn new_windowed<W>(window: std::sync::Arc<W>, config: RendererConfig) -> Result<Self>
+    where
+        W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle + Send + Sync + 'static,
+    {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        });
 
-        // Forward vector (camera to target)
-        let f = [
-            target[0] - pos[0],
-            target[1] - pos[1],
-            target[2] - pos[2],
-        ];
-        let f_len = (f[0] * f[0] + f[1] * f[1] + f[2] * f[2]).sqrt();
-        let f = [f[0] / f_len, f[1] / f_len, f[2] / f_len];
+        // `SurfaceTarget::Window` itself takes a `Box<dyn WindowHandle>`; an
+        // `Arc<W>` is passed straight to `create_surface`, which accepts
+        // anything implementing `HasWindowHandle + HasDisplayHandle` (which
+        // `raw_window_handle` blanket-implements for `Arc<W>`) and converts
+        // it to `SurfaceTarget` itself.
+        let surface = instance
+            .create_surface(window)
+            .map_err(|e| AxiomError::RenderError(format!("Failed to create surface: {}", e)))?;
 
-        // Right vector (cross product: f × up)
-        let r = [
-            f[1] * up[2] - f[2] * up[1],
-            f[2] * up[0] - f[0] * up[2],
-            f[0] * up[1] - f[1] * up[0],
-        ];
-        let r_len = (r[0] * r[0] + r[1] * r[1] + r[2] * r[2]).sqrt();
-        let r = [r[0] / r_len, r[1] / r_len, r[2] / r_len];
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                compatible_surface: Some(&surface),
+                force_fallback_adapter: false,
+            })
+            .await
+            .ok_or_else(|| AxiomError::RenderError("Failed to find GPU adapter compatible with the window surface.".to_string()))?;
 
-        // True up vector (cross product: r × f)
-        let u = [
-            r[1] * f[2] - r[2] * f[1],
-            r[2] * f[0] - r[0] * f[2],
-            r[0] * f[1] - r[1] * f[0],
-        ];
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: Some("Axiom Renderer Device (windowed)"),
+                    required_features: wgpu::Features::empty(),
+                    required_limits: wgpu::Limits::default(),
+                    memory_hints: wgpu::MemoryHints::Performance,
+                },
+                None,
+            )
+            .await
+            .map_err(|e| AxiomError::RenderError(format!("Failed to create device: {}", e)))?;
 
-        // View matrix (inverse of camera transform)
-        [
-            [r[0], u[0], -f[0], 0.0],
-            [r[1], u[1], -f[1], 0.0],
-            [r[2], u[2], -f[2], 0.0],
-            [
-                -(r[0] * pos[0] + r[1] * pos[1] + r[2] * pos[2]),
-                -(u[0] * pos[0] + u[1] * pos[1] + u[2] * pos[2]),
-                f[0] * pos[0] + f[1] * pos[1] + f[2] * pos[2],
-                1.0,
-            ],
-        ]
-    }
+        let adapter_info = adapter.get_info();
+        let device_lost = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        {
+            let device_lost = device_lost.clone();
+            device.set_device_lost_callback(move |_reason, _message| {
+                device_lost.store(true, std::sync::atomic::Ordering::Relaxed);
+            });
+        }
 
-    /// Build perspective projection matrix
-    fn build_projection_matrix(&self) -> [[f32; 4]; 4] {
-        let aspect = self.config.width as f32 / self.config.height as f32;
-        let fov_y = 45.0_f32.to_radians();
-        let near = 0.1;
-        let far = 1000.0;
+        let surface_caps = surface.get_capabilities(&adapter);
+        let surface_format = surface_caps
+            .formats
+            .iter()
+            .copied()
+            .find(|f| f.is_srgb())
+            .unwrap_or(surface_caps.formats[0]);
+
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: config.width.max(1),
+            height: config.height.max(1),
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        surface.configure(&device, &surface_config);
 
-        let f = 1.0 / (fov_y / 2.0).tan();
+        let camera_position = [0.0, 0.0, 50.0];
+        let camera_target = [0.0, 0.0, 0.0];
+        let camera_up = [0.0, 1.0, 0.0];
 
-        [
-            [f / aspect, 0.0, 0.0, 0.0],
-            [0.0, f, 0.0, 0.0],
-            [0.0, 0.0, (far + near) / (near - far), -1.0],
-            [0.0, 0.0, (2.0 * far * near) / (near - far), 0.0],
-        ]
-    }
+        let shader_source = include_str!("shaders.wgsl");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Axiom Shader"),
+            source: wgpu::ShaderSource::Wgsl(shader_source.into()),
+        });
 
-    /// Multiply two 4x4 matrices
-    fn mat4_mul(a: [[f32; 4]; 4], b: [[f32; 4]; 4]) -> [[f32; 4]; 4] {
-        let mut result = [[0.0; 4]; 4];
-        for i in 0..4 {
-            for j in 0..4 {
-                for k in 0..4 {
-                    result[i][j] += a[i][k] * b[k][j];
-                }
-            }
-        }
-        result
-    }
+        let camera_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Camera Buffer"),
+            size: std::mem::size_of::<CameraUniform>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
 
-    /// Render atoms to PNG using GPU
-    pub fn render(&self, atoms: &Atoms) -> Result<Vec<u8>> {
-        let width = self.config.width;
-        let height = self.config.height;
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Camera Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
 
-        // VALIDATION: Prevent "Zero width not allowed" PNG encoding error
-        if width == 0 || height == 0 {
+        let camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Camera Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Render Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline (windowed)"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<AtomVertex>() as u64,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &[
+                        wgpu::VertexAttribute {
+                            offset: 0,
+                            shader_location: 0,
+                            format: wgpu::VertexFormat::Float32x3,
+                        },
+                        wgpu::VertexAttribute {
+                            offset: 12,
+                            shader_location: 1,
+                            format: wgpu::VertexFormat::Float32,
+                        },
+                        wgpu::VertexAttribute {
+                            offset: 16,
+                            shader_location: 2,
+                            format: wgpu::VertexFormat::Float32x3,
+                        },
+                    ],
+                }],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: Some(depth_stencil_state()),
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        let bond_render_pipeline = create_bond_pipeline(&device, &shader, &pipeline_layout, surface_format);
+
+        let mesh_pipeline = create_mesh_pipeline(&device, &shader, &pipeline_layout, surface_format);
+        let (mesh_vertices, mesh_indices) = generate_icosphere(config.mesh_subdivisions);
+        let mesh_vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Mesh Sphere Vertex Buffer"),
+            contents: bytemuck::cast_slice(&mesh_vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let mesh_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Mesh Sphere Index Buffer"),
+            contents: bytemuck::cast_slice(&mesh_indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        let mesh_index_count = mesh_indices.len() as u32;
+
+        Ok(Self {
+            device,
+            queue,
+            config,
+            camera_position,
+            camera_target,
+            camera_up,
+            render_pipeline,
+            bond_render_pipeline,
+            mesh_pipeline,
+            mesh_vertex_buffer,
+            mesh_index_buffer,
+            mesh_index_count,
+            camera_buffer,
+            camera_bind_group,
+            hdr_pipeline: None,
+            hdr_mesh_pipeline: None,
+            tonemap_pipeline: None,
+            tonemap_bind_group_layout: None,
+            linear_sampler: None,
+            post_process_buffer: None,
+            surface: Some(surface),
+            surface_format: Some(surface_format),
+            gpu_diagnostics: Mutex::new(GpuDiagnostics {
+                adapter_name: adapter_info.name.clone(),
+                backend: format!("{:?}", adapter_info.backend),
+                ..GpuDiagnostics::default()
+            }),
+            device_lost,
+        })
+    }
+
+    /// Reconfigure the window surface after a resize event. In headless mode
+    /// there is no surface, so only the stored render size changes.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        self.config.width = width;
+        self.config.height = height;
+
+        if let (Some(surface), Some(format)) = (&self.surface, self.surface_format) {
+            let surface_config = wgpu::SurfaceConfiguration {
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                format,
+                width,
+                height,
+                present_mode: wgpu::PresentMode::Fifo,
+                alpha_mode: wgpu::CompositeAlphaMode::Auto,
+                view_formats: vec![],
+                desired_maximum_frame_latency: 2,
+            };
+            surface.configure(&self.device, &surface_config);
+        }
+    }
+
+    /// Render one frame directly to the window surface (windowed mode only).
+    pub fn render_to_surface(&self, atoms: &Atoms) -> Result<()> {
+        let surface = self
+            .surface
+            .as_ref()
+            .ok_or_else(|| AxiomError::RenderError("render_to_surface called on a headless Renderer".to_string()))?;
+
+        let frame = surface
+            .get_current_texture()
+            .map_err(|e| AxiomError::RenderError(format!("Failed to acquire surface texture: {}", e)))?;
+        let view = frame.texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut vertices = Vec::with_capacity(atoms.len());
+        for i in 0..atoms.len() {
+            let atomic_num = atoms.elements[i];
+            vertices.push(AtomVertex {
+                position: [atoms.x[i], atoms.y[i], atoms.z[i]],
+                radius: element_to_ball_stick_radius(atomic_num),
+                color: element_to_cpk_color(atomic_num),
+                _padding: 0.0,
+            });
+        }
+
+        let view_matrix = self.build_view_matrix();
+        let proj = self.build_projection_matrix();
+        let view_proj = Self::mat4_mul(proj, view_matrix);
+        self.queue.write_buffer(
+            &self.camera_buffer,
+            0,
+            bytemuck::cast_slice(&[CameraUniform {
+                view_proj,
+                view: view_matrix,
+                position: self.camera_position,
+                _padding: 0.0,
+            }]),
+        );
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Windowed Render Encoder"),
+            });
+
+        let depth_view = create_depth_texture(&self.device, self.config.width, self.config.height);
+
+        if !vertices.is_empty() {
+            let vertex_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Vertex Buffer"),
+                contents: bytemuck::cast_slice(&vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Windowed Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color { r: 0.0, g: 0.0, b: 0.0, a: 1.0 }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+            render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+            render_pass.draw(0..6, 0..vertices.len() as u32);
+        }
+
+        self.queue.submit(Some(encoder.finish()));
+        frame.present();
+        Ok(())
+    }
+
+    /// Set camera position
+    pub fn set_camera(&mut self, position: [f32; 3], target: [f32; 3], up: [f32; 3]) {
+        self.camera_position = position;
+        self.camera_target = target;
+        self.camera_up = up;
+    }
+
+    /// Reset camera to default
+    pub fn reset_camera(&mut self) {
+        self.camera_position = [0.0, 0.0, 50.0];
+        self.camera_target = [0.0, 0.0, 0.0];
+        self.camera_up = [0.0, 1.0, 0.0];
+    }
+
+    /// Build view matrix (look-at matrix)
+    fn build_view_matrix(&self) -> [[f32; 4]; 4] {
+        let pos = self.camera_position;
+        let target = self.camera_target;
+        let up = self.camera_up;
+
+        // Forward vector (camera to target)
+        let f = [
+            target[0] - pos[0],
+            target[1] - pos[1],
+            target[2] - pos[2],
+        ];
+        let f_len = (f[0] * f[0] + f[1] * f[1] + f[2] * f[2]).sqrt();
+        let f = [f[0] / f_len, f[1] / f_len, f[2] / f_len];
+
+        // Right vector (cross product: f × up)
+        let r = [
+            f[1] * up[2] - f[2] * up[1],
+            f[2] * up[0] - f[0] * up[2],
+            f[0] * up[1] - f[1] * up[0],
+        ];
+        let r_len = (r[0] * r[0] + r[1] * r[1] + r[2] * r[2]).sqrt();
+        let r = [r[0] / r_len, r[1] / r_len, r[2] / r_len];
+
+        // True up vector (cross product: r × f)
+        let u = [
+            r[1] * f[2] - r[2] * f[1],
+            r[2] * f[0] - r[0] * f[2],
+            r[0] * f[1] - r[1] * f[0],
+        ];
+
+        // View matrix (inverse of camera transform)
+        [
+            [r[0], u[0], -f[0], 0.0],
+            [r[1], u[1], -f[1], 0.0],
+            [r[2], u[2], -f[2], 0.0],
+            [
+                -(r[0] * pos[0] + r[1] * pos[1] + r[2] * pos[2]),
+                -(u[0] * pos[0] + u[1] * pos[1] + u[2] * pos[2]),
+                f[0] * pos[0] + f[1] * pos[1] + f[2] * pos[2],
+                1.0,
+            ],
+        ]
+    }
+
+    /// Build perspective projection matrix
+    fn build_projection_matrix(&self) -> [[f32; 4]; 4] {
+        let aspect = self.config.width as f32 / self.config.height as f32;
+        let fov_y = 45.0_f32.to_radians();
+        let near = 0.1;
+        let far = 1000.0;
+
+        let f = 1.0 / (fov_y / 2.0).tan();
+
+        [
+            [f / aspect, 0.0, 0.0, 0.0],
+            [0.0, f, 0.0, 0.0],
+            [0.0, 0.0, (far + near) / (near - far), -1.0],
+            [0.0, 0.0, (2.0 * far * near) / (near - far), 0.0],
+        ]
+    }
+
+    /// Multiply two 4x4 matrices
+    fn mat4_mul(a: [[f32; 4]; 4], b: [[f32; 4]; 4]) -> [[f32; 4]; 4] {
+        let mut result = [[0.0; 4]; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                for k in 0..4 {
+                    result[i][j] += a[i][k] * b[k][j];
+                }
+            }
+        }
+        result
+    }
+
+    /// Render atoms to PNG using GPU.
+    ///
+    /// Spheres are shaded into an HDR (`Rgba16Float`) target, then resolved
+    /// to the final `Rgba8Unorm` image by a fullscreen tone-mapping pass
+    /// (`config.tone_map`, scaled by `config.exposure`) before readback.
+    /// `config.sphere_mode` selects between ray-traced imposter quads and
+    /// the instanced icosphere mesh.
+    pub fn render(&self, atoms: &Atoms) -> Result<Vec<u8>> {
+        let width = self.config.width;
+        let height = self.config.height;
+
+        // VALIDATION: Prevent "Zero width not allowed" PNG encoding error
+        if width == 0 || height == 0 {
+            return Err(AxiomError::RenderError(format!(
+                "Invalid render dimensions: {}x{} (width and height must be > 0)",
+                width, height
+            )));
+        }
+
+        // If no atoms, return blank image
+        if atoms.len() == 0 {
+            let img_buffer = image::RgbaImage::from_pixel(width, height, image::Rgba([0, 0, 0, 255]));
+            let mut png_bytes = Vec::new();
+            img_buffer
+                .write_to(
+                    &mut std::io::Cursor::new(&mut png_bytes),
+                    image::ImageFormat::Png,
+                )
+                .map_err(|e| AxiomError::RenderError(format!("PNG encoding failed: {}", e)))?;
+            return Ok(png_bytes);
+        }
+
+        // Catch validation/OOM errors from the GPU work below instead of letting
+        // wgpu log them to stderr and hand back a garbled or truncated frame.
+        self.device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        // Build vertex data from atoms
+        let vertices = atoms_to_vertices(atoms);
+
+        // Create vertex buffer
+        let vertex_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Vertex Buffer"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        // Update camera uniform
+        let view = self.build_view_matrix();
+        let proj = self.build_projection_matrix();
+        let view_proj = Self::mat4_mul(proj, view);
+
+        let camera_uniform = CameraUniform {
+            view_proj,
+            view,
+            position: self.camera_position,
+            _padding: 0.0,
+        };
+
+        self.queue.write_buffer(
+            &self.camera_buffer,
+            0,
+            bytemuck::cast_slice(&[camera_uniform]),
+        );
+
+        // Create the final Rgba8Unorm output texture (what gets read back to PNG)
+        let texture_desc = wgpu::TextureDescriptor {
+            label: Some("Render Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        };
+        let output_texture = self.device.create_texture(&texture_desc);
+        let output_view = output_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let depth_view = create_depth_texture(&self.device, width, height);
+
+        // Create command encoder
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Render Encoder"),
+            });
+
+        let (hdr_pipeline, hdr_mesh_pipeline, tonemap_pipeline, tonemap_bind_group_layout, linear_sampler, post_process_buffer) =
+            match (
+                &self.hdr_pipeline,
+                &self.hdr_mesh_pipeline,
+                &self.tonemap_pipeline,
+                &self.tonemap_bind_group_layout,
+                &self.linear_sampler,
+                &self.post_process_buffer,
+            ) {
+                (Some(h), Some(m), Some(t), Some(l), Some(s), Some(b)) => (h, m, t, l, s, b),
+                _ => {
+                    return Err(AxiomError::RenderError(
+                        "HDR tone-mapping pipelines are unavailable (windowed renderer)".to_string(),
+                    ))
+                }
+            };
+
+        // HDR intermediate target: spheres are shaded and lit here at full
+        // dynamic range, then resolved to LDR by the tone-mapping pass below.
+        let hdr_texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("HDR Render Texture"),
+            size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: HDR_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let hdr_view = hdr_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Sphere pass: render into the HDR target
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("HDR Sphere Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &hdr_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color { r: 0.0, g: 0.0, b: 0.0, a: 1.0 }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+            match self.config.sphere_mode {
+                SphereRenderMode::Imposter => {
+                    render_pass.set_pipeline(hdr_pipeline);
+                    render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+                    // 6 vertices per instance (2 triangles per quad)
+                    render_pass.draw(0..6, 0..vertices.len() as u32);
+                }
+                SphereRenderMode::Mesh => {
+                    render_pass.set_pipeline(hdr_mesh_pipeline);
+                    render_pass.set_vertex_buffer(0, self.mesh_vertex_buffer.slice(..));
+                    render_pass.set_vertex_buffer(1, vertex_buffer.slice(..));
+                    render_pass.set_index_buffer(self.mesh_index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                    render_pass.draw_indexed(0..self.mesh_index_count, 0, 0..vertices.len() as u32);
+                }
+            }
+        }
+
+        // Tone-mapping pass: resolve the HDR target into the Rgba8Unorm output
+        let operator = match self.config.tone_map {
+            ToneMapOperator::Aces => 0u32,
+            ToneMapOperator::Reinhard => 1u32,
+            ToneMapOperator::None => 2u32,
+        };
+        self.queue.write_buffer(
+            post_process_buffer,
+            0,
+            bytemuck::cast_slice(&[PostProcessUniform {
+                exposure: self.config.exposure,
+                operator,
+                _padding: [0.0, 0.0],
+            }]),
+        );
+
+        let tonemap_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Tonemap Bind Group"),
+            layout: tonemap_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&hdr_view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(linear_sampler) },
+                wgpu::BindGroupEntry { binding: 2, resource: post_process_buffer.as_entire_binding() },
+            ],
+        });
+
+        {
+            let mut tonemap_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Tonemap Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &output_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            tonemap_pass.set_pipeline(tonemap_pipeline);
+            tonemap_pass.set_bind_group(0, &tonemap_bind_group, &[]);
+            tonemap_pass.draw(0..3, 0..1);
+        }
+
+        // Copy texture to buffer
+        let buffer_dimensions = BufferDimensions::new(width, height);
+        let output_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Output Buffer"),
+            size: buffer_dimensions.padded_bytes_per_row as u64
+                * buffer_dimensions.height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &output_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &output_buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(buffer_dimensions.padded_bytes_per_row),
+                    rows_per_image: Some(buffer_dimensions.height),
+                },
+            },
+            texture_desc.size,
+        );
+
+        self.queue.submit(Some(encoder.finish()));
+
+        // Map buffer and read pixels
+        let buffer_slice = output_buffer.slice(..);
+        let (tx, rx) = std::sync::mpsc::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+            tx.send(result).unwrap();
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        rx.recv()
+            .unwrap()
+            .map_err(|e| AxiomError::RenderError(format!("Failed to map buffer: {:?}", e)))?;
+
+        let data = buffer_slice.get_mapped_range();
+
+        // Copy to image buffer (remove padding)
+        let mut img_buffer = image::RgbaImage::new(width, height);
+        for y in 0..height {
+            let start = (y * buffer_dimensions.padded_bytes_per_row) as usize;
+            let end = start + (width * 4) as usize;
+            let row = &data[start..end];
+            for x in 0..width {
+                let pixel_start = (x * 4) as usize;
+                img_buffer.put_pixel(
+                    x,
+                    y,
+                    image::Rgba([
+                        row[pixel_start],
+                        row[pixel_start + 1],
+                        row[pixel_start + 2],
+                        row[pixel_start + 3],
+                    ]),
+                );
+            }
+        }
+
+        drop(data);
+        output_buffer.unmap();
+
+        self.record_error_scopes();
+
+        // Encode to PNG
+        let mut png_bytes = Vec::new();
+        img_buffer
+            .write_to(
+                &mut std::io::Cursor::new(&mut png_bytes),
+                image::ImageFormat::Png,
+            )
+            .map_err(|e| AxiomError::RenderError(format!("PNG encoding failed: {}", e)))?;
+
+        Ok(png_bytes)
+    }
+
+    /// Render a sequence of frames (e.g. an MD trajectory or turntable
+    /// animation) to PNG bytes, collecting every frame in memory.
+    ///
+    /// See [`Renderer::render_sequence_streaming`] for the buffer-reuse
+    /// details; use the streaming variant directly for long exports where
+    /// holding every frame in memory at once isn't desirable.
+    pub fn render_sequence(&self, frames: &[Atoms]) -> Result<Vec<Vec<u8>>> {
+        let mut results = Vec::with_capacity(frames.len());
+        self.render_sequence_streaming(frames, |_index, png_bytes| {
+            results.push(png_bytes);
+            Ok(())
+        })?;
+        Ok(results)
+    }
+
+    /// Render a sequence of frames, invoking `on_frame(index, png_bytes)` as
+    /// soon as each frame is ready instead of collecting them all.
+    ///
+    /// Unlike [`Renderer::render`], which allocates a fresh vertex buffer,
+    /// output texture, and readback buffer on every call, this creates all
+    /// of those once (sized to the sequence's largest frame) and reuses them
+    /// across frames, rewriting only the changed instance data each
+    /// iteration (the camera uniform is written once for the whole
+    /// sequence) - the instancing-buffer reuse pattern used for
+    /// animation/trajectory playback.
+    pub fn render_sequence_streaming<F>(&self, frames: &[Atoms], mut on_frame: F) -> Result<()>
+    where
+        F: FnMut(usize, Vec<u8>) -> Result<()>,
+    {
+        let width = self.config.width;
+        let height = self.config.height;
+
+        if width == 0 || height == 0 {
             return Err(AxiomError::RenderError(format!(
                 "Invalid render dimensions: {}x{} (width and height must be > 0)",
                 width, height
             )));
         }
 
-        // If no atoms, return blank image
-        if atoms.len() == 0 {
-            let img_buffer = image::RgbaImage::from_pixel(width, height, image::Rgba([0, 0, 0, 255]));
-            let mut png_bytes = Vec::new();
-            img_buffer
-                .write_to(
-                    &mut std::io::Cursor::new(&mut png_bytes),
-                    image::ImageFormat::Png,
-                )
-                .map_err(|e| AxiomError::RenderError(format!("PNG encoding failed: {}", e)))?;
-            return Ok(png_bytes);
-        }
+        let (hdr_pipeline, hdr_mesh_pipeline, tonemap_pipeline, tonemap_bind_group_layout, linear_sampler, post_process_buffer) =
+            match (
+                &self.hdr_pipeline,
+                &self.hdr_mesh_pipeline,
+                &self.tonemap_pipeline,
+                &self.tonemap_bind_group_layout,
+                &self.linear_sampler,
+                &self.post_process_buffer,
+            ) {
+                (Some(h), Some(m), Some(t), Some(l), Some(s), Some(b)) => (h, m, t, l, s, b),
+                _ => {
+                    return Err(AxiomError::RenderError(
+                        "HDR tone-mapping pipelines are unavailable (windowed renderer)".to_string(),
+                    ))
+                }
+            };
+
+        // Size the reusable vertex buffer to the largest frame up front so no
+        // frame in the sequence needs a reallocation.
+        let max_atoms = frames.iter().map(|f| f.len()).max().unwrap_or(0).max(1);
+        let vertex_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Sequence Vertex Buffer"),
+            size: (max_atoms * std::mem::size_of::<AtomVertex>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
 
-        // Build vertex data from atoms
-        let mut vertices = Vec::new();
+        // Output/HDR/depth resources are created once and reused for every
+        // frame since resolution doesn't change over the sequence.
+        let texture_desc = wgpu::TextureDescriptor {
+            label: Some("Sequence Render Texture"),
+            size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        };
+        let output_texture = self.device.create_texture(&texture_desc);
+        let output_view = output_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let depth_view = create_depth_texture(&self.device, width, height);
 
-        for i in 0..atoms.len() {
-            let atomic_num = atoms.elements[i];
-            let color = element_to_cpk_color(atomic_num);
-            let radius = element_to_ball_stick_radius(atomic_num);
+        let hdr_texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Sequence HDR Render Texture"),
+            size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: HDR_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let hdr_view = hdr_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
-            vertices.push(AtomVertex {
-                position: [atoms.x[i], atoms.y[i], atoms.z[i]],
-                radius,
-                color,
-                _padding: 0.0,
-            });
-        }
+        let operator = match self.config.tone_map {
+            ToneMapOperator::Aces => 0u32,
+            ToneMapOperator::Reinhard => 1u32,
+            ToneMapOperator::None => 2u32,
+        };
+        self.queue.write_buffer(
+            post_process_buffer,
+            0,
+            bytemuck::cast_slice(&[PostProcessUniform {
+                exposure: self.config.exposure,
+                operator,
+                _padding: [0.0, 0.0],
+            }]),
+        );
 
-        // Create vertex buffer
-        let vertex_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Vertex Buffer"),
-            contents: bytemuck::cast_slice(&vertices),
-            usage: wgpu::BufferUsages::VERTEX,
+        let tonemap_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Sequence Tonemap Bind Group"),
+            layout: tonemap_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&hdr_view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(linear_sampler) },
+                wgpu::BindGroupEntry { binding: 2, resource: post_process_buffer.as_entire_binding() },
+            ],
         });
 
-        // Update camera uniform
+        let buffer_dimensions = BufferDimensions::new(width, height);
+        let output_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Sequence Output Buffer"),
+            size: buffer_dimensions.padded_bytes_per_row as u64 * buffer_dimensions.height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        // The camera is fixed for the whole sequence (turntable/orbit moves
+        // are expressed as separate render_sequence calls with set_camera
+        // between them), so the uniform only needs writing once.
         let view = self.build_view_matrix();
         let proj = self.build_projection_matrix();
         let view_proj = Self::mat4_mul(proj, view);
-
         let camera_uniform = CameraUniform {
             view_proj,
             view,
             position: self.camera_position,
             _padding: 0.0,
         };
+        self.queue.write_buffer(&self.camera_buffer, 0, bytemuck::cast_slice(&[camera_uniform]));
+
+        for (frame_index, atoms) in frames.iter().enumerate() {
+            let vertices = atoms_to_vertices(atoms);
+            if !vertices.is_empty() {
+                self.queue.write_buffer(&vertex_buffer, 0, bytemuck::cast_slice(&vertices));
+            }
+
+            let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Sequence Render Encoder"),
+            });
+
+            {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Sequence HDR Sphere Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &hdr_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color { r: 0.0, g: 0.0, b: 0.0, a: 1.0 }),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                        view: &depth_view,
+                        depth_ops: Some(wgpu::Operations { load: wgpu::LoadOp::Clear(1.0), store: wgpu::StoreOp::Discard }),
+                        stencil_ops: None,
+                    }),
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+                if !vertices.is_empty() {
+                    render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+                    match self.config.sphere_mode {
+                        SphereRenderMode::Imposter => {
+                            render_pass.set_pipeline(hdr_pipeline);
+                            render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+                            render_pass.draw(0..6, 0..vertices.len() as u32);
+                        }
+                        SphereRenderMode::Mesh => {
+                            render_pass.set_pipeline(hdr_mesh_pipeline);
+                            render_pass.set_vertex_buffer(0, self.mesh_vertex_buffer.slice(..));
+                            render_pass.set_vertex_buffer(1, vertex_buffer.slice(..));
+                            render_pass.set_index_buffer(self.mesh_index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                            render_pass.draw_indexed(0..self.mesh_index_count, 0, 0..vertices.len() as u32);
+                        }
+                    }
+                }
+            }
+
+            {
+                let mut tonemap_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Sequence Tonemap Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &output_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations { load: wgpu::LoadOp::Clear(wgpu::Color::BLACK), store: wgpu::StoreOp::Store },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                tonemap_pass.set_pipeline(tonemap_pipeline);
+                tonemap_pass.set_bind_group(0, &tonemap_bind_group, &[]);
+                tonemap_pass.draw(0..3, 0..1);
+            }
+
+            encoder.copy_texture_to_buffer(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &output_texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                wgpu::TexelCopyBufferInfo {
+                    buffer: &output_buffer,
+                    layout: wgpu::TexelCopyBufferLayout {
+                        offset: 0,
+                        bytes_per_row: Some(buffer_dimensions.padded_bytes_per_row),
+                        rows_per_image: Some(buffer_dimensions.height),
+                    },
+                },
+                texture_desc.size,
+            );
+
+            self.queue.submit(Some(encoder.finish()));
+
+            let buffer_slice = output_buffer.slice(..);
+            let (tx, rx) = std::sync::mpsc::channel();
+            buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+                tx.send(result).unwrap();
+            });
+            self.device.poll(wgpu::Maintain::Wait);
+            rx.recv()
+                .unwrap()
+                .map_err(|e| AxiomError::RenderError(format!("Failed to map buffer: {:?}", e)))?;
+
+            let data = buffer_slice.get_mapped_range();
+            let mut img_buffer = image::RgbaImage::new(width, height);
+            for y in 0..height {
+                let start = (y * buffer_dimensions.padded_bytes_per_row) as usize;
+                let end = start + (width * 4) as usize;
+                let row = &data[start..end];
+                for x in 0..width {
+                    let pixel_start = (x * 4) as usize;
+                    img_buffer.put_pixel(
+                        x,
+                        y,
+                        image::Rgba([
+                            row[pixel_start],
+                            row[pixel_start + 1],
+                            row[pixel_start + 2],
+                            row[pixel_start + 3],
+                        ]),
+                    );
+                }
+            }
+            drop(data);
+            output_buffer.unmap();
+
+            let mut png_bytes = Vec::new();
+            img_buffer
+                .write_to(&mut std::io::Cursor::new(&mut png_bytes), image::ImageFormat::Png)
+                .map_err(|e| AxiomError::RenderError(format!("PNG encoding failed: {}", e)))?;
+
+            on_frame(frame_index, png_bytes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Save rendered image to file
+    pub fn save_image(&self, atoms: &Atoms, path: &str) -> Result<()> {
+        let png_bytes = self.render(atoms)?;
+        std::fs::write(path, png_bytes)
+            .map_err(|e| AxiomError::RenderError(format!("Failed to write file: {}", e)))?;
+        Ok(())
+    }
+
+    /// Render atoms with bonds natively on the GPU.
+    ///
+    /// Atom spheres and bond capsules are drawn as two instanced passes
+    /// against the same depth buffer (bond pass uses `LoadOp::Load` so it
+    /// composites correctly with the spheres already written), instead of
+    /// falling back to the CPU renderer.
+    pub fn render_with_bonds(&self, atoms: &Atoms, bonds: &Bonds) -> Result<Vec<u8>> {
+        let width = self.config.width;
+        let height = self.config.height;
+
+        if width == 0 || height == 0 {
+            return Err(AxiomError::RenderError(format!(
+                "Invalid render dimensions: {}x{} (width and height must be > 0)",
+                width, height
+            )));
+        }
+
+        let mut atom_vertices = Vec::with_capacity(atoms.len());
+        for i in 0..atoms.len() {
+            let atomic_num = atoms.elements[i];
+            atom_vertices.push(AtomVertex {
+                position: [atoms.x[i], atoms.y[i], atoms.z[i]],
+                radius: element_to_ball_stick_radius(atomic_num),
+                color: element_to_cpk_color(atomic_num),
+                _padding: 0.0,
+            });
+        }
 
+        let bond_radius = 0.2_f32;
+        let mut bond_vertices = Vec::with_capacity(bonds.len());
+        for i in 0..bonds.len() {
+            let (a, b, _order) = bonds.get(i).unwrap();
+            let (a, b) = (a as usize, b as usize);
+            if a >= atoms.len() || b >= atoms.len() {
+                continue;
+            }
+            bond_vertices.push(BondVertex {
+                point_a: [atoms.x[a], atoms.y[a], atoms.z[a]],
+                radius: bond_radius,
+                point_b: [atoms.x[b], atoms.y[b], atoms.z[b]],
+                _padding0: 0.0,
+                color_a: element_to_cpk_color(atoms.elements[a]),
+                _padding1: 0.0,
+                color_b: element_to_cpk_color(atoms.elements[b]),
+                _padding2: 0.0,
+            });
+        }
+
+        let view_matrix = self.build_view_matrix();
+        let proj = self.build_projection_matrix();
+        let view_proj = Self::mat4_mul(proj, view_matrix);
         self.queue.write_buffer(
             &self.camera_buffer,
             0,
-            bytemuck::cast_slice(&[camera_uniform]),
+            bytemuck::cast_slice(&[CameraUniform {
+                view_proj,
+                view: view_matrix,
+                position: self.camera_position,
+                _padding: 0.0,
+            }]),
         );
 
-        // Create output texture
         let texture_desc = wgpu::TextureDescriptor {
-            label: Some("Render Texture"),
-            size: wgpu::Extent3d {
-                width,
-                height,
-                depth_or_array_layers: 1,
-            },
+            label: Some("Render Texture (with bonds)"),
+            size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
@@ -431,49 +1993,84 @@ impl Renderer {
         };
         let output_texture = self.device.create_texture(&texture_desc);
         let output_view = output_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let depth_view = create_depth_texture(&self.device, width, height);
 
-        // Create command encoder
         let mut encoder = self
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Render Encoder"),
+                label: Some("Render Encoder (with bonds)"),
             });
 
-        // Render pass
+        // Pass 1: atom spheres, clearing color and depth.
         {
+            let atom_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Atom Instance Buffer"),
+                contents: bytemuck::cast_slice(&atom_vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
+                label: Some("Atom Pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
                     view: &output_view,
                     resolve_target: None,
                     ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.0,
-                            g: 1.0,
-                            b: 0.0,
-                            a: 1.0,
-                        }),
+                        load: wgpu::LoadOp::Clear(wgpu::Color { r: 0.0, g: 0.0, b: 0.0, a: 1.0 }),
                         store: wgpu::StoreOp::Store,
                     },
                 })],
-                depth_stencil_attachment: None,
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth_view,
+                    depth_ops: Some(wgpu::Operations { load: wgpu::LoadOp::Clear(1.0), store: wgpu::StoreOp::Store }),
+                    stencil_ops: None,
+                }),
                 timestamp_writes: None,
                 occlusion_query_set: None,
             });
 
-            render_pass.set_pipeline(&self.render_pipeline);
+            if !atom_vertices.is_empty() {
+                render_pass.set_pipeline(&self.render_pipeline);
+                render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+                render_pass.set_vertex_buffer(0, atom_buffer.slice(..));
+                render_pass.draw(0..6, 0..atom_vertices.len() as u32);
+            }
+        }
+
+        // Pass 2: bond capsules, loading the depth buffer so spheres and
+        // bonds occlude each other correctly.
+        if !bond_vertices.is_empty() {
+            let bond_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Bond Instance Buffer"),
+                contents: bytemuck::cast_slice(&bond_vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Bond Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &output_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations { load: wgpu::LoadOp::Load, store: wgpu::StoreOp::Store },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth_view,
+                    depth_ops: Some(wgpu::Operations { load: wgpu::LoadOp::Load, store: wgpu::StoreOp::Discard }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_pipeline(&self.bond_render_pipeline);
             render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
-            render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
-            // 6 vertices per instance (2 triangles per quad)
-            render_pass.draw(0..6, 0..vertices.len() as u32);
+            render_pass.set_vertex_buffer(0, bond_buffer.slice(..));
+            render_pass.draw(0..6, 0..bond_vertices.len() as u32);
         }
 
-        // Copy texture to buffer
         let buffer_dimensions = BufferDimensions::new(width, height);
         let output_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Output Buffer"),
-            size: buffer_dimensions.padded_bytes_per_row as u64
-                * buffer_dimensions.height as u64,
+            label: Some("Output Buffer (with bonds)"),
+            size: buffer_dimensions.padded_bytes_per_row as u64 * buffer_dimensions.height as u64,
             usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
             mapped_at_creation: false,
         });
@@ -498,7 +2095,6 @@ impl Renderer {
 
         self.queue.submit(Some(encoder.finish()));
 
-        // Map buffer and read pixels
         let buffer_slice = output_buffer.slice(..);
         let (tx, rx) = std::sync::mpsc::channel();
         buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
@@ -510,8 +2106,6 @@ impl Renderer {
             .map_err(|e| AxiomError::RenderError(format!("Failed to map buffer: {:?}", e)))?;
 
         let data = buffer_slice.get_mapped_range();
-
-        // Copy to image buffer (remove padding)
         let mut img_buffer = image::RgbaImage::new(width, height);
         for y in 0..height {
             let start = (y * buffer_dimensions.padded_bytes_per_row) as usize;
@@ -522,12 +2116,7 @@ impl Renderer {
                 img_buffer.put_pixel(
                     x,
                     y,
-                    image::Rgba([
-                        row[pixel_start],
-                        row[pixel_start + 1],
-                        row[pixel_start + 2],
-                        row[pixel_start + 3],
-                    ]),
+                    image::Rgba([row[pixel_start], row[pixel_start + 1], row[pixel_start + 2], row[pixel_start + 3]]),
                 );
             }
         }
@@ -535,65 +2124,14 @@ impl Renderer {
         drop(data);
         output_buffer.unmap();
 
-        // Encode to PNG
         let mut png_bytes = Vec::new();
         img_buffer
-            .write_to(
-                &mut std::io::Cursor::new(&mut png_bytes),
-                image::ImageFormat::Png,
-            )
+            .write_to(&mut std::io::Cursor::new(&mut png_bytes), image::ImageFormat::Png)
             .map_err(|e| AxiomError::RenderError(format!("PNG encoding failed: {}", e)))?;
 
         Ok(png_bytes)
     }
 
-    /// Save rendered image to file
-    pub fn save_image(&self, atoms: &Atoms, path: &str) -> Result<()> {
-        let png_bytes = self.render(atoms)?;
-        std::fs::write(path, png_bytes)
-            .map_err(|e| AxiomError::RenderError(format!("Failed to write file: {}", e)))?;
-        Ok(())
-    }
-
-    /// Render atoms with bonds
-    pub fn render_with_bonds(&self, atoms: &Atoms, bonds: &Bonds) -> Result<Vec<u8>> {
-        // For GPU renderer, delegate to CPU renderer since GPU bond rendering not implemented yet
-        use crate::renderer_cpu::{Renderer as CPURenderer, RendererConfig as CPUConfig, BackgroundColor};
-
-        let bg_color = if self.config.headless {
-            BackgroundColor::White  // Default for headless
-        } else {
-            BackgroundColor::Black
-        };
-
-        let cpu_config = CPUConfig {
-            width: self.config.width,
-            height: self.config.height,
-            ssaa_factor: 2,  // Use SSAA for better quality
-            specular_enabled: true,
-            specular_power: 50.0,
-            background: bg_color,
-            ao_enabled: false,
-            ao_samples: 16,
-            ao_radius: 2.0,
-            ao_strength: 0.5,
-            // Performance optimizations (Phase 6)
-            enable_frustum_culling: true,
-            enable_lod: true,
-            lod_config: crate::lod::LODConfig::default(),
-            enable_octree: true,
-            octree_max_depth: 8,
-            octree_max_atoms_per_node: 32,
-        };
-
-        let mut cpu_renderer = CPURenderer::new(cpu_config)?;
-
-        // Copy camera settings from GPU renderer to CPU renderer
-        cpu_renderer.set_camera(self.camera_position, self.camera_target, self.camera_up);
-
-        cpu_renderer.render_with_bonds(atoms, bonds)
-    }
-
     /// Get device info (for debugging)
     pub fn device_info(&self) -> String {
         format!(
@@ -603,6 +2141,26 @@ impl Renderer {
             if self.config.headless { "headless" } else { "windowed" }
         )
     }
+
+    /// Pop the Validation/OutOfMemory error scopes pushed at the top of
+    /// `render` and fold any captured errors into `gpu_diagnostics`.
+    fn record_error_scopes(&self) {
+        let mut diagnostics = self.gpu_diagnostics.lock().unwrap();
+        if let Some(error) = pollster::block_on(self.device.pop_error_scope()) {
+            record_gpu_error(&mut diagnostics, error);
+        }
+        if let Some(error) = pollster::block_on(self.device.pop_error_scope()) {
+            record_gpu_error(&mut diagnostics, error);
+        }
+    }
+
+    /// Snapshot of wgpu validation/OOM diagnostics accumulated since this
+    /// renderer was created - see `GpuDiagnostics`.
+    pub fn gpu_diagnostics(&self) -> GpuDiagnostics {
+        let mut diagnostics = self.gpu_diagnostics.lock().unwrap().clone();
+        diagnostics.device_lost = self.device_lost.load(std::sync::atomic::Ordering::Relaxed);
+        diagnostics
+    }
 }
 
 #[cfg(test)]
@@ -615,6 +2173,7 @@ mod tests {
             width: 800,
             height: 600,
             headless: true,
+            ..Default::default()
         };
 
         let renderer = Renderer::new_blocking(config);
@@ -635,12 +2194,36 @@ mod tests {
         assert_eq!(renderer.camera_position, [0.0, 0.0, 50.0]);
     }
 
+    #[test]
+    fn test_camera_controller_orbit() {
+        let mut controller = CameraController::new([0.0, 0.0, 50.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        let start = controller.camera_position();
+        assert!((start[2] - 50.0).abs() < 0.01);
+
+        // A drag should move the eye around the target without changing radius.
+        controller.process_drag(200.0, 0.0);
+        let after = controller.camera_position();
+        assert_ne!(start, after);
+        let radius_after = (after[0] * after[0] + after[1] * after[1] + after[2] * after[2]).sqrt();
+        assert!((radius_after - 50.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_camera_controller_pitch_clamped() {
+        let mut controller = CameraController::new([0.0, 0.0, 50.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        controller.process_drag(0.0, 10_000.0);
+        let pos = controller.camera_position();
+        // Pitch clamp keeps the eye just short of directly above the target.
+        assert!(pos[1] < 50.0);
+    }
+
     #[test]
     fn test_render_test_image() {
         let config = RendererConfig {
             width: 100,
             height: 100,
             headless: true,
+            ..Default::default()
         };
         let renderer = Renderer::new_blocking(config).unwrap();
 
@@ -651,4 +2234,132 @@ mod tests {
         assert!(!png_bytes.is_empty(), "PNG should not be empty");
         assert!(png_bytes.starts_with(b"\x89PNG"), "Should be valid PNG");
     }
+
+    #[test]
+    fn test_gpu_diagnostics_populated_after_render() {
+        let config = RendererConfig {
+            width: 64,
+            height: 64,
+            headless: true,
+            ..Default::default()
+        };
+        let renderer = Renderer::new_blocking(config).unwrap();
+        assert!(!renderer.gpu_diagnostics().adapter_name.is_empty());
+
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 1);
+        renderer.render(&atoms).unwrap();
+
+        // A routine render shouldn't raise any validation/OOM errors.
+        let diagnostics = renderer.gpu_diagnostics();
+        assert!(diagnostics.validation_errors.is_empty());
+        assert_eq!(diagnostics.oom_events, 0);
+        assert!(!diagnostics.device_lost);
+    }
+
+    #[test]
+    fn test_render_with_tone_map_operator() {
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 1);
+
+        for tone_map in [ToneMapOperator::Aces, ToneMapOperator::Reinhard, ToneMapOperator::None] {
+            let config = RendererConfig {
+                width: 64,
+                height: 64,
+                headless: true,
+                exposure: 2.0,
+                tone_map,
+                ..Default::default()
+            };
+            let renderer = Renderer::new_blocking(config).unwrap();
+            let png_bytes = renderer.render(&atoms).unwrap();
+            assert!(png_bytes.starts_with(b"\x89PNG"), "Should be valid PNG for {:?}", tone_map);
+        }
+    }
+
+    #[test]
+    fn test_icosphere_generation_triangle_count_and_unit_radius() {
+        for subdivisions in 0..=2 {
+            let (vertices, indices) = generate_icosphere(subdivisions);
+            assert_eq!(indices.len() % 3, 0, "Indices should form complete triangles");
+            assert_eq!(indices.len(), 20 * 4usize.pow(subdivisions) * 3);
+            for v in &vertices {
+                let len = (v.position[0] * v.position[0]
+                    + v.position[1] * v.position[1]
+                    + v.position[2] * v.position[2])
+                    .sqrt();
+                assert!((len - 1.0).abs() < 1e-4, "Vertices should lie on the unit sphere");
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_with_mesh_sphere_mode() {
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 1);
+
+        let config = RendererConfig {
+            width: 64,
+            height: 64,
+            headless: true,
+            sphere_mode: SphereRenderMode::Mesh,
+            mesh_subdivisions: 1,
+            ..Default::default()
+        };
+        let renderer = Renderer::new_blocking(config).unwrap();
+        let png_bytes = renderer.render(&atoms).unwrap();
+        assert!(png_bytes.starts_with(b"\x89PNG"), "Should be valid PNG for mesh sphere mode");
+    }
+
+    #[test]
+    fn test_render_sequence() {
+        let config = RendererConfig {
+            width: 64,
+            height: 64,
+            headless: true,
+            ..Default::default()
+        };
+        let renderer = Renderer::new_blocking(config).unwrap();
+
+        // Frames of different sizes exercise the reusable vertex buffer's
+        // "sized to the largest frame" up-front allocation.
+        let mut frame_one = Atoms::new();
+        frame_one.push(0.0, 0.0, 0.0, 1);
+        let mut frame_two = Atoms::new();
+        frame_two.push(0.0, 0.0, 0.0, 1);
+        frame_two.push(1.0, 0.0, 0.0, 6);
+        let frames = vec![frame_one, frame_two];
+
+        let pngs = renderer.render_sequence(&frames).unwrap();
+        assert_eq!(pngs.len(), 2);
+        for png_bytes in &pngs {
+            assert!(png_bytes.starts_with(b"\x89PNG"), "Every sequence frame should be a valid PNG");
+        }
+    }
+
+    #[test]
+    fn test_render_sequence_streaming_calls_back_in_order() {
+        let config = RendererConfig {
+            width: 32,
+            height: 32,
+            headless: true,
+            ..Default::default()
+        };
+        let renderer = Renderer::new_blocking(config).unwrap();
+
+        let mut frame = Atoms::new();
+        frame.push(0.0, 0.0, 0.0, 1);
+        let frames = vec![frame.clone(), frame.clone(), frame];
+
+        let mut seen_indices = Vec::new();
+        renderer
+            .render_sequence_streaming(&frames, |index, png_bytes| {
+                seen_indices.push(index);
+                assert!(png_bytes.starts_with(b"\x89PNG"));
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(seen_indices, vec![0, 1, 2]);
+    }
 }
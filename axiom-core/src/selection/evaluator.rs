@@ -1,24 +1,81 @@
 // Selection evaluator - converts AST to atom indices
 
-use crate::atoms::Atoms;
+use crate::atoms::{Atoms, Bonds, PeriodicBox};
+use crate::bonds::build_adjacency;
 use crate::errors::{AxiomError, Result};
-use crate::selection::parser::SelectionAST;
-use std::collections::HashSet;
+use crate::selection::parser::{CompareOp, Field, SelectionAST};
+use crate::trajectory::TrajectoryFrame;
+use std::collections::{HashMap, HashSet, VecDeque};
 
-/// Evaluate a selection AST and return matching atom indices
+/// Evaluate a selection AST and return matching atom indices. A `group`
+/// term in the query is an error here - use `evaluate_selection_with_groups`
+/// for queries that need functional-group membership.
 pub fn evaluate_selection(atoms: &Atoms, ast: &SelectionAST) -> Result<Vec<usize>> {
-    let indices = evaluate_ast(atoms, ast)?;
+    let indices = evaluate_ast(atoms, ast, None, None)?;
     let mut result: Vec<usize> = indices.into_iter().collect();
     result.sort_unstable();
     Ok(result)
 }
 
-fn evaluate_ast(atoms: &Atoms, ast: &SelectionAST) -> Result<HashSet<usize>> {
+/// Evaluate a selection AST against a specific trajectory frame's moving
+/// coordinates, while reusing `topology` for identity metadata (elements,
+/// residue names, chains) that is shared across all frames.
+pub fn evaluate_selection_for_frame(
+    topology: &Atoms,
+    frame: &TrajectoryFrame,
+    ast: &SelectionAST,
+) -> Result<Vec<usize>> {
+    let atoms = frame.to_atoms(topology);
+    evaluate_selection(&atoms, ast)
+}
+
+/// Evaluate a selection AST that may reference functional groups (`group
+/// <name>`, or `within N of group <name>`), given groups already classified
+/// by `chemistry::classify_functional_groups`.
+pub fn evaluate_selection_with_groups(
+    atoms: &Atoms,
+    groups: &HashMap<String, HashSet<usize>>,
+    ast: &SelectionAST,
+) -> Result<Vec<usize>> {
+    evaluate_selection_with_context(atoms, Some(groups), None, ast)
+}
+
+/// Evaluate a selection AST that may reference the bond graph (`bonded
+/// <expr>`, `fragment <expr>`), given the structure's connectivity.
+pub fn evaluate_selection_with_bonds(atoms: &Atoms, bonds: &Bonds, ast: &SelectionAST) -> Result<Vec<usize>> {
+    evaluate_selection_with_context(atoms, None, Some(bonds), ast)
+}
+
+/// Evaluate a selection AST with both functional-group membership and bond
+/// connectivity available, for queries that combine `group`/`within N of
+/// group` terms with the graph operators `byres`/`bonded`/`fragment`.
+pub fn evaluate_selection_with_context(
+    atoms: &Atoms,
+    groups: Option<&HashMap<String, HashSet<usize>>>,
+    bonds: Option<&Bonds>,
+    ast: &SelectionAST,
+) -> Result<Vec<usize>> {
+    let indices = evaluate_ast(atoms, ast, groups, bonds)?;
+    let mut result: Vec<usize> = indices.into_iter().collect();
+    result.sort_unstable();
+    Ok(result)
+}
+
+fn evaluate_ast(
+    atoms: &Atoms,
+    ast: &SelectionAST,
+    groups: Option<&HashMap<String, HashSet<usize>>>,
+    bonds: Option<&Bonds>,
+) -> Result<HashSet<usize>> {
     match ast {
         SelectionAST::All => {
             Ok((0..atoms.len()).collect())
         }
 
+        SelectionAST::Invalid => Err(AxiomError::SelectionError(
+            "Cannot evaluate an Invalid selection node (produced by parse_selection_all's error recovery)".to_string(),
+        )),
+
         SelectionAST::Element(element_symbol) => {
             let element_num = symbol_to_atomic_number(element_symbol)?;
             Ok(atoms
@@ -30,6 +87,20 @@ fn evaluate_ast(atoms: &Atoms, ast: &SelectionAST) -> Result<HashSet<usize>> {
                 .collect())
         }
 
+        SelectionAST::ElementGlob(pattern) => {
+            Ok(atoms
+                .elements
+                .iter()
+                .enumerate()
+                .filter(|(_, &e)| {
+                    crate::elements::atomic_number_to_symbol(e)
+                        .map(|symbol| glob_match(pattern, symbol))
+                        .unwrap_or(false)
+                })
+                .map(|(i, _)| i)
+                .collect())
+        }
+
         SelectionAST::Resname(resname) => {
             if let Some(residue_names) = &atoms.residue_names {
                 Ok(residue_names
@@ -45,6 +116,21 @@ fn evaluate_ast(atoms: &Atoms, ast: &SelectionAST) -> Result<HashSet<usize>> {
             }
         }
 
+        SelectionAST::ResnameGlob(pattern) => {
+            if let Some(residue_names) = &atoms.residue_names {
+                Ok(residue_names
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, r)| glob_match(pattern, r))
+                    .map(|(i, _)| i)
+                    .collect())
+            } else {
+                Err(AxiomError::SelectionError(
+                    "No residue names available in structure".to_string(),
+                ))
+            }
+        }
+
         SelectionAST::Chain(chain) => {
             if let Some(chain_ids) = &atoms.chain_ids {
                 Ok(chain_ids
@@ -60,6 +146,21 @@ fn evaluate_ast(atoms: &Atoms, ast: &SelectionAST) -> Result<HashSet<usize>> {
             }
         }
 
+        SelectionAST::ChainGlob(pattern) => {
+            if let Some(chain_ids) = &atoms.chain_ids {
+                Ok(chain_ids
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| glob_match(pattern, c))
+                    .map(|(i, _)| i)
+                    .collect())
+            } else {
+                Err(AxiomError::SelectionError(
+                    "No chain IDs available in structure".to_string(),
+                ))
+            }
+        }
+
         SelectionAST::Resid(resid) => {
             if let Some(residue_indices) = &atoms.residue_indices {
                 Ok(residue_indices
@@ -117,68 +218,185 @@ fn evaluate_ast(atoms: &Atoms, ast: &SelectionAST) -> Result<HashSet<usize>> {
         }
 
         SelectionAST::Water => {
-            let result: HashSet<usize> = evaluate_ast(atoms, &SelectionAST::Resname("WAT".to_string()))?
+            let result: HashSet<usize> = evaluate_ast(atoms, &SelectionAST::Resname("WAT".to_string()), groups, bonds)?
                 .into_iter()
-                .chain(evaluate_ast(atoms, &SelectionAST::Resname("HOH".to_string()))?)
-                .chain(evaluate_ast(atoms, &SelectionAST::Resname("TIP".to_string()))?)
-                .chain(evaluate_ast(atoms, &SelectionAST::Resname("TIP3".to_string()))?)
+                .chain(evaluate_ast(atoms, &SelectionAST::Resname("HOH".to_string()), groups, bonds)?)
+                .chain(evaluate_ast(atoms, &SelectionAST::Resname("TIP".to_string()), groups, bonds)?)
+                .chain(evaluate_ast(atoms, &SelectionAST::Resname("TIP3".to_string()), groups, bonds)?)
                 .collect();
             Ok(result)
         }
 
         SelectionAST::Backbone => {
-            // Backbone atoms: N, CA, C, O
-            // This is a simplified version - ideally we'd check atom names
-            // For now, just return an error indicating we need atom names
-            Err(AxiomError::SelectionError(
-                "Backbone selection requires atom names (not yet implemented)".to_string(),
-            ))
+            if let Some(atom_names) = &atoms.atom_names {
+                let protein = evaluate_ast(atoms, &SelectionAST::Protein, groups, bonds)?;
+                Ok(protein
+                    .into_iter()
+                    .filter(|&i| is_backbone_name(&atom_names[i]))
+                    .collect())
+            } else {
+                Err(AxiomError::SelectionError(
+                    "Backbone selection requires atom names (none available in structure)".to_string(),
+                ))
+            }
         }
 
         SelectionAST::Sidechain => {
-            // Sidechain = protein - backbone
-            Err(AxiomError::SelectionError(
-                "Sidechain selection requires atom names (not yet implemented)".to_string(),
-            ))
+            if let Some(atom_names) = &atoms.atom_names {
+                let protein = evaluate_ast(atoms, &SelectionAST::Protein, groups, bonds)?;
+                Ok(protein
+                    .into_iter()
+                    .filter(|&i| {
+                        let name = &atom_names[i];
+                        !is_backbone_name(name) && !is_backbone_hydrogen_name(name)
+                    })
+                    .collect())
+            } else {
+                Err(AxiomError::SelectionError(
+                    "Sidechain selection requires atom names (none available in structure)".to_string(),
+                ))
+            }
         }
 
-        SelectionAST::Within(dist_cutoff, selection) => {
-            let reference_indices = evaluate_ast(atoms, selection)?;
+        SelectionAST::Name(name) => {
+            if let Some(atom_names) = &atoms.atom_names {
+                Ok(atom_names
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, n)| n.eq_ignore_ascii_case(name))
+                    .map(|(i, _)| i)
+                    .collect())
+            } else {
+                Err(AxiomError::SelectionError(
+                    "No atom names available in structure".to_string(),
+                ))
+            }
+        }
 
-            // Calculate distances from each atom to nearest reference atom
-            let mut result = HashSet::new();
+        SelectionAST::NameRegex(pattern) => {
+            if let Some(atom_names) = &atoms.atom_names {
+                Ok(atom_names
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, n)| glob_match(pattern, n))
+                    .map(|(i, _)| i)
+                    .collect())
+            } else {
+                Err(AxiomError::SelectionError(
+                    "No atom names available in structure".to_string(),
+                ))
+            }
+        }
+
+        SelectionAST::Group(name) => {
+            match groups.and_then(|g| g.get(name)) {
+                Some(members) => Ok(members.clone()),
+                None => Err(AxiomError::SelectionError(format!(
+                    "Unknown functional group '{}' (or 'group' used without evaluate_selection_with_groups)",
+                    name
+                ))),
+            }
+        }
 
+        SelectionAST::Compare { field, op, value } => {
+            let mut result = HashSet::new();
             for i in 0..atoms.len() {
-                let pos_i = [atoms.x[i], atoms.y[i], atoms.z[i]];
+                let field_value = field_value(atoms, i, *field)?;
+                if apply_compare(field_value, *op, *value) {
+                    result.insert(i);
+                }
+            }
+            Ok(result)
+        }
 
-                for &ref_idx in &reference_indices {
-                    let pos_ref = [atoms.x[ref_idx], atoms.y[ref_idx], atoms.z[ref_idx]];
-                    let dist = distance(&pos_i, &pos_ref);
+        SelectionAST::Byres(selection) => {
+            let selected = evaluate_ast(atoms, selection, groups, bonds)?;
+            if let Some(residue_indices) = &atoms.residue_indices {
+                let resids: HashSet<u32> = selected.iter().map(|&i| residue_indices[i]).collect();
+                Ok(residue_indices
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, r)| resids.contains(r))
+                    .map(|(i, _)| i)
+                    .collect())
+            } else {
+                Err(AxiomError::SelectionError(
+                    "'byres' requires residue indices (none available in structure)".to_string(),
+                ))
+            }
+        }
+
+        SelectionAST::Bonded(selection) => {
+            let selected = evaluate_ast(atoms, selection, groups, bonds)?;
+            let bonds = bonds.ok_or_else(|| {
+                AxiomError::SelectionError(
+                    "'bonded' requires a bond graph (use evaluate_selection_with_bonds)".to_string(),
+                )
+            })?;
+            let adjacency = build_adjacency(atoms, bonds);
+
+            let mut result = selected.clone();
+            for &i in &selected {
+                for &(neighbor, _order) in &adjacency[i] {
+                    result.insert(neighbor);
+                }
+            }
+            Ok(result)
+        }
 
-                    if dist <= *dist_cutoff {
-                        result.insert(i);
-                        break;
+        SelectionAST::Fragment(selection) => {
+            let selected = evaluate_ast(atoms, selection, groups, bonds)?;
+            let bonds = bonds.ok_or_else(|| {
+                AxiomError::SelectionError(
+                    "'fragment' requires a bond graph (use evaluate_selection_with_bonds)".to_string(),
+                )
+            })?;
+            let adjacency = build_adjacency(atoms, bonds);
+
+            let mut result = HashSet::new();
+            for &start in &selected {
+                if result.contains(&start) {
+                    continue;
+                }
+                let mut queue = VecDeque::new();
+                queue.push_back(start);
+                result.insert(start);
+                while let Some(current) = queue.pop_front() {
+                    for &(neighbor, _order) in &adjacency[current] {
+                        if result.insert(neighbor) {
+                            queue.push_back(neighbor);
+                        }
                     }
                 }
             }
+            Ok(result)
+        }
+
+        SelectionAST::Within(dist_cutoff, selection) => {
+            let reference_indices = evaluate_ast(atoms, selection, groups, bonds)?;
+
+            let result = match (atoms.is_periodic(), &atoms.periodic_box) {
+                (true, Some(pbox)) => within_periodic(atoms, pbox, *dist_cutoff, &reference_indices),
+                _ => within_open(atoms, *dist_cutoff, &reference_indices),
+            };
 
             Ok(result)
         }
 
         SelectionAST::And(left, right) => {
-            let left_indices = evaluate_ast(atoms, left)?;
-            let right_indices = evaluate_ast(atoms, right)?;
+            let left_indices = evaluate_ast(atoms, left, groups, bonds)?;
+            let right_indices = evaluate_ast(atoms, right, groups, bonds)?;
             Ok(left_indices.intersection(&right_indices).copied().collect())
         }
 
         SelectionAST::Or(left, right) => {
-            let left_indices = evaluate_ast(atoms, left)?;
-            let right_indices = evaluate_ast(atoms, right)?;
+            let left_indices = evaluate_ast(atoms, left, groups, bonds)?;
+            let right_indices = evaluate_ast(atoms, right, groups, bonds)?;
             Ok(left_indices.union(&right_indices).copied().collect())
         }
 
         SelectionAST::Not(selection) => {
-            let selected = evaluate_ast(atoms, selection)?;
+            let selected = evaluate_ast(atoms, selection, groups, bonds)?;
             Ok((0..atoms.len())
                 .filter(|i| !selected.contains(i))
                 .collect())
@@ -186,54 +404,195 @@ fn evaluate_ast(atoms: &Atoms, ast: &SelectionAST) -> Result<HashSet<usize>> {
     }
 }
 
-/// Convert element symbol to atomic number
-fn symbol_to_atomic_number(symbol: &str) -> Result<u8> {
-    let num = match symbol.to_uppercase().as_str() {
-        "H" => 1,
-        "HE" => 2,
-        "LI" => 3,
-        "BE" => 4,
-        "B" => 5,
-        "C" => 6,
-        "N" => 7,
-        "O" => 8,
-        "F" => 9,
-        "NE" => 10,
-        "NA" => 11,
-        "MG" => 12,
-        "AL" => 13,
-        "SI" => 14,
-        "P" => 15,
-        "S" => 16,
-        "CL" => 17,
-        "AR" => 18,
-        "K" => 19,
-        "CA" => 20,
-        "SC" => 21,
-        "TI" => 22,
-        "V" => 23,
-        "CR" => 24,
-        "MN" => 25,
-        "FE" => 26,
-        "CO" => 27,
-        "NI" => 28,
-        "CU" => 29,
-        "ZN" => 30,
-        "GA" => 31,
-        "GE" => 32,
-        "AS" => 33,
-        "SE" => 34,
-        "BR" => 35,
-        "KR" => 36,
-        // Add more as needed...
-        _ => {
-            return Err(AxiomError::SelectionError(format!(
-                "Unknown element symbol: {}",
-                symbol
-            )))
+/// Brute-force `within` for non-periodic structures: distance from every
+/// atom to the nearest reference atom, no minimum-image wrapping.
+fn within_open(atoms: &Atoms, dist_cutoff: f32, reference_indices: &HashSet<usize>) -> HashSet<usize> {
+    let mut result = HashSet::new();
+
+    for i in 0..atoms.len() {
+        let pos_i = [atoms.x[i], atoms.y[i], atoms.z[i]];
+
+        for &ref_idx in reference_indices {
+            let pos_ref = [atoms.x[ref_idx], atoms.y[ref_idx], atoms.z[ref_idx]];
+            let dist = distance(&pos_i, &pos_ref);
+
+            if dist <= dist_cutoff {
+                result.insert(i);
+                break;
+            }
         }
+    }
+
+    result
+}
+
+/// Lengths of the three box vectors (the columns of the orthogonalization
+/// matrix), used to size the cell-list grid.
+fn box_vector_lengths(pbox: &PeriodicBox) -> [f32; 3] {
+    let m = pbox.matrix;
+    [
+        (m[0][0] * m[0][0] + m[1][0] * m[1][0] + m[2][0] * m[2][0]).sqrt(),
+        (m[0][1] * m[0][1] + m[1][1] * m[1][1] + m[2][1] * m[2][1]).sqrt(),
+        (m[0][2] * m[0][2] + m[1][2] * m[1][2] + m[2][2] * m[2][2]).sqrt(),
+    ]
+}
+
+/// Periodic `within` using the minimum-image convention, accelerated with a
+/// cell list: reference atoms are binned into a grid whose cell edge is at
+/// least `dist_cutoff`, so each query atom only needs to check its own cell
+/// and the 26 neighbors (wrapping under PBC) instead of every reference atom.
+fn within_periodic(
+    atoms: &Atoms,
+    pbox: &PeriodicBox,
+    dist_cutoff: f32,
+    reference_indices: &HashSet<usize>,
+) -> HashSet<usize> {
+    let lengths = box_vector_lengths(pbox);
+    let cell_count = |length: f32| -> i64 {
+        if dist_cutoff <= 0.0 {
+            1
+        } else {
+            (length / dist_cutoff).floor().max(1.0) as i64
+        }
+    };
+    let (nx, ny, nz) = (cell_count(lengths[0]), cell_count(lengths[1]), cell_count(lengths[2]));
+
+    let cell_of = |pos: [f32; 3]| -> (i64, i64, i64) {
+        let f = pbox.cartesian_to_fractional(pos);
+        let wrap = |v: f32, n: i64| -> i64 {
+            let unit = v - v.floor(); // wrap into [0, 1)
+            ((unit * n as f32) as i64).clamp(0, n - 1)
+        };
+        (wrap(f[0], nx), wrap(f[1], ny), wrap(f[2], nz))
     };
-    Ok(num)
+
+    let mut bins: HashMap<(i64, i64, i64), Vec<usize>> = HashMap::new();
+    for &ref_idx in reference_indices {
+        let pos = [atoms.x[ref_idx], atoms.y[ref_idx], atoms.z[ref_idx]];
+        bins.entry(cell_of(pos)).or_default().push(ref_idx);
+    }
+
+    let wrap_index = |i: i64, n: i64| -> i64 { ((i % n) + n) % n };
+
+    let mut result = HashSet::new();
+    for i in 0..atoms.len() {
+        let pos_i = [atoms.x[i], atoms.y[i], atoms.z[i]];
+        let (cx, cy, cz) = cell_of(pos_i);
+
+        'search: for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let key = (wrap_index(cx + dx, nx), wrap_index(cy + dy, ny), wrap_index(cz + dz, nz));
+
+                    if let Some(candidates) = bins.get(&key) {
+                        for &ref_idx in candidates {
+                            let pos_ref = [atoms.x[ref_idx], atoms.y[ref_idx], atoms.z[ref_idx]];
+                            let d = pbox.minimum_image(pos_i, pos_ref);
+                            let dist = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+
+                            if dist <= dist_cutoff {
+                                result.insert(i);
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Standard protein backbone atom names (N-CA-C-O, plus the C-terminal OXT).
+const BACKBONE_ATOM_NAMES: [&str; 5] = ["N", "CA", "C", "O", "OXT"];
+
+/// Amide hydrogens attached to the backbone nitrogen. `Atoms` carries no
+/// bond connectivity, so "attached to backbone" is approximated by name,
+/// the same way VMD's built-in `backbone`/`sidechain` macros work.
+const BACKBONE_HYDROGEN_NAMES: [&str; 4] = ["H", "H1", "H2", "H3"];
+
+fn is_backbone_name(name: &str) -> bool {
+    BACKBONE_ATOM_NAMES.iter().any(|&b| name.eq_ignore_ascii_case(b))
+}
+
+fn is_backbone_hydrogen_name(name: &str) -> bool {
+    BACKBONE_HYDROGEN_NAMES.iter().any(|&b| name.eq_ignore_ascii_case(b))
+}
+
+/// Match `text` against a glob `pattern` (`*` = any run of characters,
+/// `?` = any single character), case-insensitively. There is no `regex`
+/// crate in this tree, so `nameregex` queries are served by this
+/// hand-written matcher rather than true regular expressions.
+/// Glob-match `text` against `pattern`: `*` matches any run (including
+/// empty), `?` matches exactly one character, and `|` separates whole
+/// alternatives (e.g. `"A|B"` matches either `"A"` or `"B"`) - `text` only
+/// needs to match one of them. Case-insensitive, like the rest of the
+/// selection DSL's string comparisons.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    pattern.split('|').any(|alt| {
+        let alt: Vec<char> = alt.to_ascii_uppercase().chars().collect();
+        let text: Vec<char> = text.to_ascii_uppercase().chars().collect();
+        glob_match_chars(&alt, &text)
+    })
+}
+
+fn glob_match_chars(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') => {
+            glob_match_chars(&pattern[1..], text)
+                || (!text.is_empty() && glob_match_chars(pattern, &text[1..]))
+        }
+        Some('?') => !text.is_empty() && glob_match_chars(&pattern[1..], &text[1..]),
+        Some(&c) => !text.is_empty() && text[0] == c && glob_match_chars(&pattern[1..], &text[1..]),
+    }
+}
+
+/// Convert element symbol (or a bare atomic number, e.g. "26" for iron) to
+/// atomic number. Delegates symbol lookup to the crate-wide `elements`
+/// table, which covers every naturally occurring element rather than just
+/// the first few rows of the periodic table.
+fn symbol_to_atomic_number(symbol: &str) -> Result<u8> {
+    if let Ok(num) = symbol.parse::<u8>() {
+        return Ok(num);
+    }
+
+    crate::elements::symbol_to_atomic_number(symbol).ok_or_else(|| {
+        AxiomError::SelectionError(format!("Unknown element symbol: {}", symbol))
+    })
+}
+
+/// Read atom `i`'s value for a `Compare` field. `X`/`Y`/`Z` always succeed
+/// (every `Atoms` carries coordinates); `Mass` is derived from the element
+/// rather than stored; the rest error out when the structure doesn't carry
+/// that optional column, matching how `Name`/`Backbone`/etc. above treat
+/// missing optional per-atom data.
+fn field_value(atoms: &Atoms, i: usize, field: Field) -> Result<f32> {
+    match field {
+        Field::Beta => atoms.b_factors.as_ref().map(|v| v[i]).ok_or_else(|| {
+            AxiomError::SelectionError("No B-factors available in structure".to_string())
+        }),
+        Field::Occupancy => atoms.occupancies.as_ref().map(|v| v[i]).ok_or_else(|| {
+            AxiomError::SelectionError("No occupancies available in structure".to_string())
+        }),
+        Field::Mass => Ok(crate::elements::atomic_mass(atoms.elements[i])),
+        Field::Charge => atoms.charges.as_ref().map(|v| v[i]).ok_or_else(|| {
+            AxiomError::SelectionError("No charges available in structure".to_string())
+        }),
+        Field::X => Ok(atoms.x[i]),
+        Field::Y => Ok(atoms.y[i]),
+        Field::Z => Ok(atoms.z[i]),
+    }
+}
+
+fn apply_compare(field_value: f32, op: CompareOp, target: f32) -> bool {
+    match op {
+        CompareOp::LessThan => field_value < target,
+        CompareOp::GreaterThan => field_value > target,
+        CompareOp::LessEq => field_value <= target,
+        CompareOp::GreaterEq => field_value >= target,
+    }
 }
 
 /// Calculate Euclidean distance between two 3D points
@@ -295,6 +654,14 @@ mod tests {
         assert_eq!(indices, vec![0]);
     }
 
+    #[test]
+    fn test_evaluate_element_atomic_number() {
+        let atoms = create_test_structure();
+        let ast = SelectionAST::Element("8".to_string()); // atomic number for oxygen
+        let indices = evaluate_selection(&atoms, &ast).unwrap();
+        assert_eq!(indices, vec![0]);
+    }
+
     #[test]
     fn test_evaluate_resname() {
         let atoms = create_test_structure();
@@ -303,6 +670,73 @@ mod tests {
         assert_eq!(indices, vec![0, 1, 2]);
     }
 
+    #[test]
+    fn test_evaluate_compare_x_greater_than() {
+        let atoms = create_test_structure();
+        let ast = SelectionAST::Compare { field: Field::X, op: CompareOp::GreaterThan, value: 5.0 };
+        let indices = evaluate_selection(&atoms, &ast).unwrap();
+        assert_eq!(indices, vec![3]); // the carbon at (10, 10, 10)
+    }
+
+    #[test]
+    fn test_evaluate_compare_mass_derives_from_element() {
+        let atoms = create_test_structure();
+        // Oxygen's mass (~16) is the only one above 14.
+        let ast = SelectionAST::Compare { field: Field::Mass, op: CompareOp::GreaterThan, value: 14.0 };
+        let indices = evaluate_selection(&atoms, &ast).unwrap();
+        assert_eq!(indices, vec![0]); // carbon (12.011) and hydrogen stay below 14
+    }
+
+    #[test]
+    fn test_evaluate_compare_beta_requires_b_factors() {
+        let atoms = create_test_structure();
+        let ast = SelectionAST::Compare { field: Field::Beta, op: CompareOp::GreaterThan, value: 30.0 };
+        let err = evaluate_selection(&atoms, &ast).unwrap_err();
+        assert!(matches!(err, AxiomError::SelectionError(_)));
+    }
+
+    #[test]
+    fn test_evaluate_compare_beta_with_b_factors() {
+        let mut atoms = create_test_structure();
+        atoms.b_factors = Some(vec![10.0, 20.0, 50.0, 60.0]);
+        let ast = SelectionAST::Compare { field: Field::Beta, op: CompareOp::GreaterEq, value: 50.0 };
+        let indices = evaluate_selection(&atoms, &ast).unwrap();
+        assert_eq!(indices, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_evaluate_resname_glob() {
+        let atoms = create_test_structure();
+        let ast = SelectionAST::ResnameGlob("LI*".to_string());
+        let indices = evaluate_selection(&atoms, &ast).unwrap();
+        assert_eq!(indices, vec![3]); // LIG
+    }
+
+    #[test]
+    fn test_evaluate_element_glob_single_char_wildcard() {
+        let atoms = create_test_structure();
+        // Every element symbol in the fixture (O, H, H, C) is one letter,
+        // so "?" should match all four atoms.
+        let ast = SelectionAST::ElementGlob("?".to_string());
+        let indices = evaluate_selection(&atoms, &ast).unwrap();
+        assert_eq!(indices, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_evaluate_chain_glob_alternation() {
+        let atoms = create_test_structure();
+        let ast = SelectionAST::ChainGlob("A|B".to_string());
+        let indices = evaluate_selection(&atoms, &ast).unwrap();
+        assert_eq!(indices, vec![0, 1, 2, 3]); // all atoms are chain A
+    }
+
+    #[test]
+    fn test_evaluate_invalid_node_errors() {
+        let atoms = create_test_structure();
+        let err = evaluate_selection(&atoms, &SelectionAST::Invalid).unwrap_err();
+        assert!(matches!(err, AxiomError::SelectionError(_)));
+    }
+
     #[test]
     fn test_evaluate_within() {
         let atoms = create_test_structure();
@@ -316,6 +750,21 @@ mod tests {
         assert_eq!(indices, vec![3]);
     }
 
+    #[test]
+    fn test_evaluate_within_periodic_wraps_across_boundary() {
+        let mut atoms = Atoms::new();
+        atoms.set_periodic_box([[10.0, 0.0, 0.0], [0.0, 10.0, 0.0], [0.0, 0.0, 10.0]]);
+
+        // Reference atom near one face, query atom near the opposite face -
+        // only 1Å apart through the periodic image, 9Å through the interior.
+        atoms.push(0.5, 5.0, 5.0, 6); // 0: reference (carbon)
+        atoms.push(9.5, 5.0, 5.0, 1); // 1: query (hydrogen), wraps to within 1Å
+
+        let ast = SelectionAST::Within(2.0, Box::new(SelectionAST::Element("C".to_string())));
+        let indices = evaluate_selection(&atoms, &ast).unwrap();
+        assert_eq!(indices, vec![0, 1]);
+    }
+
     #[test]
     fn test_evaluate_and() {
         let atoms = create_test_structure();
@@ -334,4 +783,163 @@ mod tests {
         let indices = evaluate_selection(&atoms, &ast).unwrap();
         assert_eq!(indices, vec![3]); // Only the carbon (LIG)
     }
+
+    fn create_test_residue() -> Atoms {
+        let mut atoms = Atoms::new();
+
+        // A single alanine residue: N, CA, C, O backbone + CB sidechain + amide H
+        atoms.push(0.0, 0.0, 0.0, 7); // 0: N
+        atoms.push(1.0, 0.0, 0.0, 6); // 1: CA
+        atoms.push(2.0, 0.0, 0.0, 6); // 2: C
+        atoms.push(3.0, 0.0, 0.0, 8); // 3: O
+        atoms.push(1.0, 1.0, 0.0, 6); // 4: CB (sidechain)
+        atoms.push(-1.0, 0.0, 0.0, 1); // 5: H (backbone amide hydrogen)
+
+        atoms.atom_names = Some(vec![
+            "N".to_string(),
+            "CA".to_string(),
+            "C".to_string(),
+            "O".to_string(),
+            "CB".to_string(),
+            "H".to_string(),
+        ]);
+        atoms.residue_names = Some(vec!["ALA".to_string(); 6]);
+        atoms.residue_indices = Some(vec![1; 6]);
+
+        atoms
+    }
+
+    #[test]
+    fn test_evaluate_name() {
+        let atoms = create_test_residue();
+        let ast = SelectionAST::Name("CA".to_string());
+        let indices = evaluate_selection(&atoms, &ast).unwrap();
+        assert_eq!(indices, vec![1]);
+    }
+
+    #[test]
+    fn test_evaluate_nameregex() {
+        let atoms = create_test_residue();
+        let ast = SelectionAST::NameRegex("C*".to_string());
+        let indices = evaluate_selection(&atoms, &ast).unwrap();
+        assert_eq!(indices, vec![1, 2, 4]); // CA, C, CB
+    }
+
+    #[test]
+    fn test_evaluate_backbone() {
+        let atoms = create_test_residue();
+        let ast = SelectionAST::Backbone;
+        let indices = evaluate_selection(&atoms, &ast).unwrap();
+        assert_eq!(indices, vec![0, 1, 2, 3]); // N, CA, C, O
+    }
+
+    #[test]
+    fn test_evaluate_sidechain() {
+        let atoms = create_test_residue();
+        let ast = SelectionAST::Sidechain;
+        let indices = evaluate_selection(&atoms, &ast).unwrap();
+        assert_eq!(indices, vec![4]); // CB only; amide H excluded as a backbone hydrogen
+    }
+
+    #[test]
+    fn test_evaluate_group_requires_groups_map() {
+        let atoms = create_test_structure();
+        let ast = SelectionAST::Group("hydroxyl".to_string());
+        assert!(evaluate_selection(&atoms, &ast).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_selection_with_groups() {
+        let atoms = create_test_structure();
+        let mut hydroxyl = HashSet::new();
+        hydroxyl.insert(0);
+        let mut groups = HashMap::new();
+        groups.insert("hydroxyl".to_string(), hydroxyl);
+
+        let ast = SelectionAST::Group("hydroxyl".to_string());
+        let indices = evaluate_selection_with_groups(&atoms, &groups, &ast).unwrap();
+        assert_eq!(indices, vec![0]);
+    }
+
+    /// Water molecule (O-H-H) with an explicit bond graph, for the
+    /// `bonded`/`fragment` tests below.
+    fn create_bonded_water() -> (Atoms, Bonds) {
+        let atoms = create_test_structure();
+
+        let mut bonds = Bonds::new();
+        bonds.push(0, 1, 1);
+        bonds.push(0, 2, 1);
+
+        (atoms, bonds)
+    }
+
+    #[test]
+    fn test_evaluate_bonded_requires_bonds() {
+        let atoms = create_test_structure();
+        let ast = SelectionAST::Bonded(Box::new(SelectionAST::Element("O".to_string())));
+        assert!(evaluate_selection(&atoms, &ast).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_bonded_adds_the_hydrogens() {
+        let (atoms, bonds) = create_bonded_water();
+        let ast = SelectionAST::Bonded(Box::new(SelectionAST::Element("O".to_string())));
+        let indices = evaluate_selection_with_bonds(&atoms, &bonds, &ast).unwrap();
+        assert_eq!(indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_evaluate_fragment_returns_whole_molecule() {
+        let (atoms, bonds) = create_bonded_water();
+        let ast = SelectionAST::Fragment(Box::new(SelectionAST::Element("O".to_string())));
+        let indices = evaluate_selection_with_bonds(&atoms, &bonds, &ast).unwrap();
+        assert_eq!(indices, vec![0, 1, 2]); // whole water molecule; carbon stays out (no bond to it)
+    }
+
+    #[test]
+    fn test_evaluate_bonded_vs_fragment_on_a_chain() {
+        // A-B-C-D path: "bonded" on B only reaches its direct neighbors (A, C),
+        // while "fragment" reaches the entire connected component (A, B, C, D).
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 6); // 0: A
+        atoms.push(1.0, 0.0, 0.0, 6); // 1: B
+        atoms.push(2.0, 0.0, 0.0, 6); // 2: C
+        atoms.push(3.0, 0.0, 0.0, 6); // 3: D
+        atoms.atom_names = Some(vec!["A".to_string(), "B".to_string(), "C".to_string(), "D".to_string()]);
+
+        let mut bonds = Bonds::new();
+        bonds.push(0, 1, 1);
+        bonds.push(1, 2, 1);
+        bonds.push(2, 3, 1);
+
+        let bonded_ast = SelectionAST::Bonded(Box::new(SelectionAST::Name("B".to_string())));
+        let bonded = evaluate_selection_with_bonds(&atoms, &bonds, &bonded_ast).unwrap();
+        assert_eq!(bonded, vec![0, 1, 2]);
+
+        let fragment_ast = SelectionAST::Fragment(Box::new(SelectionAST::Name("B".to_string())));
+        let fragment = evaluate_selection_with_bonds(&atoms, &bonds, &fragment_ast).unwrap();
+        assert_eq!(fragment, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_evaluate_byres_requires_residue_indices() {
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 8);
+        let ast = SelectionAST::Byres(Box::new(SelectionAST::Element("O".to_string())));
+        assert!(evaluate_selection(&atoms, &ast).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_byres_expands_to_whole_residue() {
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 6); // 0: C, resid 1
+        atoms.push(1.0, 0.0, 0.0, 7); // 1: N, resid 1
+        atoms.push(2.0, 0.0, 0.0, 6); // 2: C, resid 2
+        atoms.push(3.0, 0.0, 0.0, 8); // 3: O, resid 2
+        atoms.residue_indices = Some(vec![1, 1, 2, 2]);
+
+        let ast = SelectionAST::Byres(Box::new(SelectionAST::Element("O".to_string())));
+        let indices = evaluate_selection(&atoms, &ast).unwrap();
+        assert_eq!(indices, vec![2, 3]);
+    }
 }
@@ -4,10 +4,14 @@
 pub mod parser;
 pub mod evaluator;
 
-pub use parser::{parse_selection, SelectionAST, SelectionToken};
-pub use evaluator::evaluate_selection;
+pub use parser::{parse_selection, parse_selection_all, SelectionAST, SelectionToken, SelectionDiagnostic, SpannedToken, Field, CompareOp};
+pub use evaluator::{
+    evaluate_selection, evaluate_selection_for_frame, evaluate_selection_with_groups,
+    evaluate_selection_with_bonds, evaluate_selection_with_context,
+};
 
-use crate::atoms::Atoms;
+use crate::atoms::{Atoms, Bonds};
+use crate::chemistry::classify_functional_groups;
 use crate::errors::Result;
 
 /// Main entry point for selection queries
@@ -29,6 +33,38 @@ pub fn select(atoms: &Atoms, query: &str) -> Result<Vec<usize>> {
     evaluate_selection(atoms, &ast)
 }
 
+/// Same as `select`, but also resolves terms that need the bond graph:
+/// `group <name>` / `within 5 of group hydroxyl` (classifying functional
+/// groups from `bonds` first), and the bond-graph operators `bonded` and
+/// `fragment`.
+///
+/// # Examples
+/// ```
+/// use axiom_core::selection::select_with_bonds;
+/// use axiom_core::atoms::{Atoms, Bonds};
+///
+/// let mut atoms = Atoms::new();
+/// atoms.push(0.0, 0.0, 0.0, 6);  // carbon
+/// atoms.push(0.97, 0.0, 0.0, 8); // hydroxyl oxygen
+/// atoms.push(1.94, 0.0, 0.0, 1); // hydroxyl hydrogen
+///
+/// let mut bonds = Bonds::new();
+/// bonds.atom1 = vec![0, 1];
+/// bonds.atom2 = vec![1, 2];
+/// bonds.order = vec![1, 1];
+///
+/// let indices = select_with_bonds(&atoms, &bonds, "group hydroxyl").unwrap();
+/// assert_eq!(indices, vec![1]);
+///
+/// let indices = select_with_bonds(&atoms, &bonds, "fragment element O").unwrap();
+/// assert_eq!(indices, vec![0, 1, 2]);
+/// ```
+pub fn select_with_bonds(atoms: &Atoms, bonds: &Bonds, query: &str) -> Result<Vec<usize>> {
+    let ast = parse_selection(query)?;
+    let groups = classify_functional_groups(atoms, bonds);
+    evaluate_selection_with_context(atoms, Some(&groups), Some(bonds), &ast)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,4 +109,36 @@ mod tests {
         let indices = select(&atoms, "resname WAT").unwrap();
         assert_eq!(indices, vec![0, 1, 2]);
     }
+
+    #[test]
+    fn test_select_with_bonds_group() {
+        use crate::atoms::Bonds;
+
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 6);  // 0: carbon
+        atoms.push(1.43, 0.0, 0.0, 8); // 1: hydroxyl oxygen
+        atoms.push(1.43, 0.97, 0.0, 1); // 2: hydroxyl hydrogen
+
+        let mut bonds = Bonds::new();
+        bonds.atom1 = vec![0, 1];
+        bonds.atom2 = vec![1, 2];
+        bonds.order = vec![1, 1];
+
+        let indices = select_with_bonds(&atoms, &bonds, "group hydroxyl").unwrap();
+        assert_eq!(indices, vec![1]);
+    }
+
+    #[test]
+    fn test_select_with_bonds_fragment() {
+        use crate::atoms::Bonds;
+
+        let atoms = create_test_atoms(); // water: O-H-H
+        let mut bonds = Bonds::new();
+        bonds.atom1 = vec![0, 0];
+        bonds.atom2 = vec![1, 2];
+        bonds.order = vec![1, 1];
+
+        let indices = select_with_bonds(&atoms, &bonds, "fragment element O").unwrap();
+        assert_eq!(indices, vec![0, 1, 2]);
+    }
 }
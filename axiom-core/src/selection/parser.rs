@@ -1,13 +1,22 @@
 // Selection query parser
 // Grammar:
 //   selection := term (bool_op term)*
-//   term := keyword [comparison value] | "within" number "of" selection | "(" selection ")"
+//   term := keyword [comparison value] | numeric_field num_comparison number
+//         | "within" number "of" selection | "(" selection ")"
 //   keyword := "all" | "element" | "resname" | "chain" | "resid" | "protein" | "water" | "backbone"
-//   bool_op := "and" | "or" | "not"
+//   numeric_field := "beta" | "occupancy" | "mass" | "charge" | "x" | "y" | "z"
+//   bool_op := "and" | "or" | "not" | "byres" | "bonded" | "fragment"
 //   comparison := "=" | "!="
-//   value := string | number | range
+//   num_comparison := "<" | ">" | "<=" | ">="
+//   value := string | quoted_glob | number | range
+//
+// A bare (unquoted) string value is matched exactly, e.g. `resname WAT`.
+// A quoted value, e.g. `resname "HI*"`, is matched as a glob pattern:
+// `*` any run, `?` any one character, `|` alternation between whole
+// patterns (`chain "A|B"`). Supported for `element`, `resname`, and `chain`.
 
 use crate::errors::{AxiomError, Result};
+use std::fmt;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum SelectionToken {
@@ -21,6 +30,21 @@ pub enum SelectionToken {
     Water,
     Backbone,
     Sidechain,
+    Name,
+    NameRegex,
+    Group,
+    Byres,
+    Bonded,
+    Fragment,
+
+    // Numeric field keywords (used with a comparison operator, e.g. `beta > 30`)
+    Beta,
+    Occupancy,
+    Mass,
+    Charge,
+    X,
+    Y,
+    Z,
 
     // Boolean operators
     And,
@@ -34,9 +58,16 @@ pub enum SelectionToken {
     // Comparisons
     Equals,
     NotEquals,
+    LessThan,
+    GreaterThan,
+    LessEq,
+    GreaterEq,
 
     // Values
     String(String),
+    /// A quoted value, e.g. `"HI*"` - unlike a bare `String`, this signals
+    /// glob semantics (`*`/`?`/`|`) to the parser rather than an exact match.
+    QuotedString(String),
     Number(f32),
     Range(u32, u32),
 
@@ -45,97 +76,255 @@ pub enum SelectionToken {
     RightParen,
 }
 
+/// A token plus the byte range in the original query string it was scanned
+/// from, so parse errors can point back at the exact offending substring
+/// instead of just naming it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedToken {
+    pub token: SelectionToken,
+    pub span: (usize, usize),
+}
+
+/// A parser error with enough context for a front-end to underline the
+/// offending text: the full query, the byte span of the bad token, a short
+/// label (e.g. "unexpected token"), and the expected-vs-found message.
+/// `Display` renders the classic caret-style diagnostic:
+///
+/// ```text
+/// resnam WAT
+/// ^^^^^^
+/// unexpected token: expected a keyword, found "resnam"
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectionDiagnostic {
+    pub input: String,
+    pub span: (usize, usize),
+    pub label: String,
+    pub message: String,
+}
+
+impl SelectionDiagnostic {
+    fn new(input: &str, span: (usize, usize), label: impl Into<String>, message: impl Into<String>) -> Self {
+        SelectionDiagnostic {
+            input: input.to_string(),
+            span,
+            label: label.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SelectionDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (start, end) = self.span;
+        let start = start.min(self.input.len());
+        let caret_len = end.saturating_sub(start).max(1);
+        writeln!(f, "{}", self.input)?;
+        writeln!(f, "{}{}", " ".repeat(start), "^".repeat(caret_len))?;
+        write!(f, "{}: {}", self.label, self.message)
+    }
+}
+
+/// Per-atom numeric field a `Compare` node reads from. `Mass` isn't stored
+/// on `Atoms` directly - it's derived from each atom's element via
+/// `elements::atomic_mass`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Field {
+    Beta,
+    Occupancy,
+    Mass,
+    Charge,
+    X,
+    Y,
+    Z,
+}
+
+/// Numeric comparison operator for a `Compare` node. No `Equals`/`NotEquals`
+/// variants: exact `==`/`!=` on stored `f32` fields (mass, charge, beta, ...)
+/// is essentially unusable for real data (`mass = 12.0` never matches
+/// carbon's stored `12.011`), so `parse_compare` rejects those operators at
+/// parse time instead of accepting a comparison that can't match.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    LessThan,
+    GreaterThan,
+    LessEq,
+    GreaterEq,
+}
+
 #[derive(Debug, Clone)]
 pub enum SelectionAST {
     All,
     Element(String),     // Element symbol (O, H, C, etc.)
     Resname(String),     // Residue name (WAT, ALA, etc.)
     Chain(String),       // Chain ID (A, B, etc.)
+    ElementGlob(String), // Glob pattern over element symbols (`*`/`?`/`|`), from a quoted value e.g. element "C?"
+    ResnameGlob(String), // Glob pattern over residue names, from a quoted value e.g. resname "HI*"
+    ChainGlob(String),   // Glob pattern over chain IDs, from a quoted value e.g. chain "A|B"
     Resid(u32),          // Residue index
     ResidRange(u32, u32), // Residue range
     Protein,             // Built-in macro for protein atoms
     Water,               // Built-in macro for water
     Backbone,            // Built-in macro for backbone atoms
     Sidechain,           // Built-in macro for sidechain atoms
+    Name(String),        // Exact atom name (CA, N, OXT, etc.)
+    NameRegex(String),   // Glob pattern over atom names (`*` any run, `?` any one char)
+    Group(String),       // Functional group membership (carboxyl, hydroxyl, etc.)
+    Compare { field: Field, op: CompareOp, value: f32 }, // Numeric field comparison (beta > 30, x <= 10.0, ...)
+    Byres(Box<SelectionAST>),    // Expand to every atom sharing a residue index with the selection
+    Bonded(Box<SelectionAST>),  // Add atoms directly bonded to the selection (one BFS shell)
+    Fragment(Box<SelectionAST>), // Expand to the full connected component reachable through bonds
     Within(f32, Box<SelectionAST>), // Spatial query: within distance of selection
     And(Box<SelectionAST>, Box<SelectionAST>),
     Or(Box<SelectionAST>, Box<SelectionAST>),
     Not(Box<SelectionAST>),
+    /// Placeholder produced by `parse_selection_all`'s error-recovering
+    /// parser in place of a term it couldn't parse. Never produced by the
+    /// strict `parse_selection`; evaluating one is an error.
+    Invalid,
 }
 
-/// Tokenize a selection string
-fn tokenize(input: &str) -> Result<Vec<SelectionToken>> {
+/// Tokenize a selection string, recording the byte span each token was
+/// scanned from so the parser can later underline it in error messages.
+fn tokenize(input: &str) -> Result<Vec<SpannedToken>> {
     let mut tokens = Vec::new();
-    let mut chars = input.chars().peekable();
+    let mut chars = input.char_indices().peekable();
 
-    while let Some(&ch) = chars.peek() {
+    while let Some(&(start, ch)) = chars.peek() {
         match ch {
             ' ' | '\t' | '\n' => {
                 chars.next();
             }
             '(' => {
-                tokens.push(SelectionToken::LeftParen);
+                tokens.push(SpannedToken { token: SelectionToken::LeftParen, span: (start, start + 1) });
                 chars.next();
             }
             ')' => {
-                tokens.push(SelectionToken::RightParen);
+                tokens.push(SpannedToken { token: SelectionToken::RightParen, span: (start, start + 1) });
                 chars.next();
             }
             '=' => {
                 chars.next();
-                if chars.peek() == Some(&'=') {
+                let mut end = start + 1;
+                if let Some(&(_, '=')) = chars.peek() {
                     chars.next(); // Skip second '=' if present
+                    end += 1;
                 }
-                tokens.push(SelectionToken::Equals);
+                tokens.push(SpannedToken { token: SelectionToken::Equals, span: (start, end) });
             }
             '!' => {
                 chars.next();
-                if chars.peek() == Some(&'=') {
+                if let Some(&(_, '=')) = chars.peek() {
                     chars.next();
-                    tokens.push(SelectionToken::NotEquals);
+                    tokens.push(SpannedToken { token: SelectionToken::NotEquals, span: (start, start + 2) });
                 } else {
-                    return Err(AxiomError::SelectionError(format!("Unexpected character: !")));
+                    return Err(AxiomError::SelectionSyntaxError(SelectionDiagnostic::new(
+                        input,
+                        (start, start + 1),
+                        "unexpected character",
+                        "expected '!=', found a bare '!'".to_string(),
+                    )));
+                }
+            }
+            '<' => {
+                chars.next();
+                let mut end = start + 1;
+                let mut token = SelectionToken::LessThan;
+                if let Some(&(_, '=')) = chars.peek() {
+                    chars.next();
+                    end += 1;
+                    token = SelectionToken::LessEq;
+                }
+                tokens.push(SpannedToken { token, span: (start, end) });
+            }
+            '>' => {
+                chars.next();
+                let mut end = start + 1;
+                let mut token = SelectionToken::GreaterThan;
+                if let Some(&(_, '=')) = chars.peek() {
+                    chars.next();
+                    end += 1;
+                    token = SelectionToken::GreaterEq;
+                }
+                tokens.push(SpannedToken { token, span: (start, end) });
+            }
+            '"' => {
+                chars.next(); // consume opening quote
+                let mut value = String::new();
+                let mut end = None;
+                for (i, ch) in chars.by_ref() {
+                    if ch == '"' {
+                        end = Some(i + 1);
+                        break;
+                    }
+                    value.push(ch);
+                }
+                match end {
+                    Some(end) => {
+                        tokens.push(SpannedToken { token: SelectionToken::QuotedString(value), span: (start, end) });
+                    }
+                    None => {
+                        return Err(AxiomError::SelectionSyntaxError(SelectionDiagnostic::new(
+                            input,
+                            (start, input.len()),
+                            "unterminated quoted string",
+                            "expected a closing '\"'".to_string(),
+                        )));
+                    }
                 }
             }
             '0'..='9' | '-' => {
                 // Parse number or range
                 let mut num_str = String::new();
-                while let Some(&ch) = chars.peek() {
+                let mut end = start;
+                while let Some(&(i, ch)) = chars.peek() {
                     if ch.is_ascii_digit() || ch == '.' || ch == '-' {
                         num_str.push(ch);
+                        end = i + ch.len_utf8();
                         chars.next();
                     } else {
                         break;
                     }
                 }
+                let span = (start, end);
 
                 // Check for range (e.g., "10-20")
                 if num_str.contains('-') && !num_str.starts_with('-') {
                     let parts: Vec<&str> = num_str.split('-').collect();
                     if parts.len() == 2 {
-                        let start: u32 = parts[0].parse().map_err(|_| {
-                            AxiomError::SelectionError(format!("Invalid range: {}", num_str))
+                        let range_start: u32 = parts[0].parse().map_err(|_| {
+                            AxiomError::SelectionSyntaxError(SelectionDiagnostic::new(
+                                input, span, "invalid range", format!("invalid range: {}", num_str),
+                            ))
                         })?;
-                        let end: u32 = parts[1].parse().map_err(|_| {
-                            AxiomError::SelectionError(format!("Invalid range: {}", num_str))
+                        let range_end: u32 = parts[1].parse().map_err(|_| {
+                            AxiomError::SelectionSyntaxError(SelectionDiagnostic::new(
+                                input, span, "invalid range", format!("invalid range: {}", num_str),
+                            ))
                         })?;
-                        tokens.push(SelectionToken::Range(start, end));
+                        tokens.push(SpannedToken { token: SelectionToken::Range(range_start, range_end), span });
                     } else {
-                        return Err(AxiomError::SelectionError(format!("Invalid range: {}", num_str)));
+                        return Err(AxiomError::SelectionSyntaxError(SelectionDiagnostic::new(
+                            input, span, "invalid range", format!("invalid range: {}", num_str),
+                        )));
                     }
                 } else {
                     let num: f32 = num_str.parse().map_err(|_| {
-                        AxiomError::SelectionError(format!("Invalid number: {}", num_str))
+                        AxiomError::SelectionSyntaxError(SelectionDiagnostic::new(
+                            input, span, "invalid number", format!("invalid number: {}", num_str),
+                        ))
                     })?;
-                    tokens.push(SelectionToken::Number(num));
+                    tokens.push(SpannedToken { token: SelectionToken::Number(num), span });
                 }
             }
             _ => {
-                // Parse word
+                // Parse word (atom-name glob patterns may include `*`/`?`)
                 let mut word = String::new();
-                while let Some(&ch) = chars.peek() {
-                    if ch.is_alphanumeric() || ch == '_' {
+                let mut end = start;
+                while let Some(&(i, ch)) = chars.peek() {
+                    if ch.is_alphanumeric() || ch == '_' || ch == '*' || ch == '?' {
                         word.push(ch);
+                        end = i + ch.len_utf8();
                         chars.next();
                     } else {
                         break;
@@ -152,6 +341,19 @@ fn tokenize(input: &str) -> Result<Vec<SelectionToken>> {
                     "water" => SelectionToken::Water,
                     "backbone" => SelectionToken::Backbone,
                     "sidechain" => SelectionToken::Sidechain,
+                    "name" => SelectionToken::Name,
+                    "nameregex" => SelectionToken::NameRegex,
+                    "group" => SelectionToken::Group,
+                    "byres" => SelectionToken::Byres,
+                    "bonded" => SelectionToken::Bonded,
+                    "fragment" => SelectionToken::Fragment,
+                    "beta" => SelectionToken::Beta,
+                    "occupancy" => SelectionToken::Occupancy,
+                    "mass" => SelectionToken::Mass,
+                    "charge" => SelectionToken::Charge,
+                    "x" => SelectionToken::X,
+                    "y" => SelectionToken::Y,
+                    "z" => SelectionToken::Z,
                     "and" => SelectionToken::And,
                     "or" => SelectionToken::Or,
                     "not" => SelectionToken::Not,
@@ -159,7 +361,7 @@ fn tokenize(input: &str) -> Result<Vec<SelectionToken>> {
                     "of" => SelectionToken::Of,
                     _ => SelectionToken::String(word),
                 };
-                tokens.push(token);
+                tokens.push(SpannedToken { token, span: (start, end) });
             }
         }
     }
@@ -170,38 +372,185 @@ fn tokenize(input: &str) -> Result<Vec<SelectionToken>> {
 /// Parse tokens into an AST
 pub fn parse_selection(input: &str) -> Result<SelectionAST> {
     let tokens = tokenize(input)?;
-    let mut parser = Parser::new(tokens);
+    let mut parser = Parser::new(tokens, input, false);
     parser.parse()
 }
 
+/// Error-recovering counterpart to `parse_selection`: instead of bailing on
+/// the first bad term, it records a diagnostic, skips forward to the next
+/// recovery point (`and`, `or`, `)`, or end of input), and keeps parsing -
+/// so a query with several mistakes reports all of them in one pass instead
+/// of just the first. Failed terms are replaced with `SelectionAST::Invalid`
+/// placeholders, so the returned AST keeps the query's overall and/or shape.
+///
+/// Returns `None` only when the input can't be tokenized at all; even empty
+/// input yields an `Invalid` placeholder and a diagnostic. Otherwise returns the
+/// best-effort AST alongside every diagnostic collected along the way (an
+/// empty `Vec` means the query was actually clean).
+pub fn parse_selection_all(input: &str) -> (Option<SelectionAST>, Vec<SelectionDiagnostic>) {
+    let tokens = match tokenize(input) {
+        Ok(tokens) => tokens,
+        Err(AxiomError::SelectionSyntaxError(diag)) => return (None, vec![diag]),
+        Err(_) => return (None, Vec::new()),
+    };
+
+    let mut parser = Parser::new(tokens, input, true);
+    let ast = parser.parse().ok();
+
+    if parser.pos < parser.tokens.len() {
+        let span = parser.current_span();
+        let diag = SelectionDiagnostic::new(
+            input,
+            span,
+            "unexpected trailing input",
+            format!("unexpected trailing token {:?} after a complete selection", parser.current()),
+        );
+        parser.errors.push(diag);
+    }
+
+    (ast, parser.errors)
+}
+
 struct Parser {
-    tokens: Vec<SelectionToken>,
+    tokens: Vec<SpannedToken>,
     pos: usize,
+    input: String,
+    /// When true, a failed term is recorded in `errors` and replaced with
+    /// `SelectionAST::Invalid` instead of aborting the whole parse.
+    recovering: bool,
+    errors: Vec<SelectionDiagnostic>,
 }
 
 impl Parser {
-    fn new(tokens: Vec<SelectionToken>) -> Self {
-        Parser { tokens, pos: 0 }
+    fn new(tokens: Vec<SpannedToken>, input: &str, recovering: bool) -> Self {
+        Parser { tokens, pos: 0, input: input.to_string(), recovering, errors: Vec::new() }
     }
 
     fn current(&self) -> Option<&SelectionToken> {
-        self.tokens.get(self.pos)
+        self.tokens.get(self.pos).map(|t| &t.token)
+    }
+
+    /// Byte span to underline for an error at the current position: the
+    /// current token's span, or an empty span at the end of input if we've
+    /// run out of tokens (e.g. "within 5 of" with nothing after "of").
+    fn current_span(&self) -> (usize, usize) {
+        match self.tokens.get(self.pos) {
+            Some(t) => t.span,
+            None => (self.input.len(), self.input.len()),
+        }
     }
 
     fn advance(&mut self) {
         self.pos += 1;
     }
 
+    /// Build a caret-style diagnostic pointing at the current token.
+    fn error(&self, label: &str, message: String) -> AxiomError {
+        AxiomError::SelectionSyntaxError(SelectionDiagnostic::new(&self.input, self.current_span(), label, message))
+    }
+
+    /// Consume a leading `=`/`!=` before a string selector's value, e.g.
+    /// `element != C`. Returns `true` if the selector's result should be
+    /// negated (`!=`); an absent operator (`element C`) is treated the same
+    /// as `=`.
+    fn consume_optional_equality(&mut self) -> bool {
+        match self.current() {
+            Some(SelectionToken::Equals) => {
+                self.advance();
+                false
+            }
+            Some(SelectionToken::NotEquals) => {
+                self.advance();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Parse `field <op> <number>` for one of the numeric field keywords
+    /// (`beta`, `occupancy`, `mass`, `charge`, `x`, `y`, `z`).
+    fn parse_compare(&mut self, field: Field) -> Result<SelectionAST> {
+        self.advance(); // consume the field keyword
+        let op = match self.current() {
+            Some(SelectionToken::Equals) | Some(SelectionToken::NotEquals) => {
+                return Err(self.error(
+                    "unsupported numeric comparison",
+                    format!(
+                        "'{:?}' is a floating-point field - '=' and '!=' rarely match stored values exactly; use '<', '>', '<=', or '>=' instead",
+                        field
+                    ),
+                ))
+            }
+            Some(SelectionToken::LessThan) => CompareOp::LessThan,
+            Some(SelectionToken::GreaterThan) => CompareOp::GreaterThan,
+            Some(SelectionToken::LessEq) => CompareOp::LessEq,
+            Some(SelectionToken::GreaterEq) => CompareOp::GreaterEq,
+            _ => {
+                return Err(self.error(
+                    "expected comparison operator",
+                    format!("expected one of '<', '>', '<=', '>=' after '{:?}'", field),
+                ))
+            }
+        };
+        self.advance();
+
+        match self.current() {
+            Some(SelectionToken::Number(n)) => {
+                let value = *n;
+                self.advance();
+                Ok(SelectionAST::Compare { field, op, value })
+            }
+            _ => Err(self.error(
+                "expected numeric value",
+                format!("expected a number after the comparison operator for '{:?}'", field),
+            )),
+        }
+    }
+
+    /// Skip forward to the next recovery point - `and`, `or`, `)`, or end of
+    /// input - after a failed term. Always makes forward progress: it either
+    /// consumes the tokens up to (but not including) a boundary, or it was
+    /// already sitting on one and leaves it for the caller (`parse_and`,
+    /// `parse_or`, or the enclosing `(...)`) to consume, which advances in
+    /// turn - so recovery can never spin in place.
+    fn recover(&mut self) {
+        while let Some(tok) = self.current() {
+            match tok {
+                SelectionToken::And | SelectionToken::Or | SelectionToken::RightParen => break,
+                _ => self.advance(),
+            }
+        }
+    }
+
+    /// Parse a single term, recovering from a parse error when `recovering`
+    /// is set: the diagnostic is recorded, the parser skips to the next
+    /// recovery point, and a `SelectionAST::Invalid` placeholder stands in
+    /// for the failed term so the surrounding `and`/`or` structure survives.
+    /// In strict mode (`recovering == false`) this is identical to calling
+    /// `parse_term` directly.
+    fn parse_term_recovering(&mut self) -> Result<SelectionAST> {
+        match self.parse_term() {
+            Ok(ast) => Ok(ast),
+            Err(err) if self.recovering => {
+                if let AxiomError::SelectionSyntaxError(diag) = err {
+                    self.errors.push(diag);
+                }
+                self.recover();
+                Ok(SelectionAST::Invalid)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
     fn expect(&mut self, expected: SelectionToken) -> Result<()> {
         if self.current() == Some(&expected) {
             self.advance();
             Ok(())
         } else {
-            Err(AxiomError::SelectionError(format!(
-                "Expected {:?}, got {:?}",
-                expected,
-                self.current()
-            )))
+            Err(self.error(
+                "unexpected token",
+                format!("expected {:?}, found {:?}", expected, self.current()),
+            ))
         }
     }
 
@@ -235,12 +584,28 @@ impl Parser {
     }
 
     fn parse_not(&mut self) -> Result<SelectionAST> {
-        if self.current() == Some(&SelectionToken::Not) {
-            self.advance();
-            let expr = self.parse_not()?;
-            Ok(SelectionAST::Not(Box::new(expr)))
-        } else {
-            self.parse_term()
+        match self.current() {
+            Some(SelectionToken::Not) => {
+                self.advance();
+                let expr = self.parse_not()?;
+                Ok(SelectionAST::Not(Box::new(expr)))
+            }
+            Some(SelectionToken::Byres) => {
+                self.advance();
+                let expr = self.parse_not()?;
+                Ok(SelectionAST::Byres(Box::new(expr)))
+            }
+            Some(SelectionToken::Bonded) => {
+                self.advance();
+                let expr = self.parse_not()?;
+                Ok(SelectionAST::Bonded(Box::new(expr)))
+            }
+            Some(SelectionToken::Fragment) => {
+                self.advance();
+                let expr = self.parse_not()?;
+                Ok(SelectionAST::Fragment(Box::new(expr)))
+            }
+            _ => self.parse_term_recovering(),
         }
     }
 
@@ -249,7 +614,29 @@ impl Parser {
             Some(SelectionToken::LeftParen) => {
                 self.advance();
                 let expr = self.parse()?;
-                self.expect(SelectionToken::RightParen)?;
+                match self.current() {
+                    Some(SelectionToken::RightParen) => {
+                        self.advance();
+                    }
+                    _ if self.recovering => {
+                        // Unbalanced parentheses: recover by treating end of
+                        // input (or whatever follows) as an implicit close,
+                        // while still recording the mismatch.
+                        let span = self.current_span();
+                        self.errors.push(SelectionDiagnostic::new(
+                            &self.input,
+                            span,
+                            "unbalanced parentheses",
+                            "expected ')' to close '(' - treating end of input as an implicit close".to_string(),
+                        ));
+                    }
+                    _ => {
+                        return Err(self.error(
+                            "unexpected token",
+                            format!("expected {:?}, found {:?}", SelectionToken::RightParen, self.current()),
+                        ));
+                    }
+                }
                 Ok(expr)
             }
             Some(SelectionToken::All) => {
@@ -274,34 +661,106 @@ impl Parser {
             }
             Some(SelectionToken::Element) => {
                 self.advance();
-                if let Some(SelectionToken::String(element)) = self.current() {
-                    let element = element.clone();
+                let negate = self.consume_optional_equality();
+                let ast = match self.current() {
+                    Some(SelectionToken::String(element)) => {
+                        let element = element.clone();
+                        self.advance();
+                        SelectionAST::Element(element)
+                    }
+                    // A quoted value is a glob pattern, e.g. element "C?".
+                    Some(SelectionToken::QuotedString(pattern)) => {
+                        let pattern = pattern.clone();
+                        self.advance();
+                        SelectionAST::ElementGlob(pattern)
+                    }
+                    // Also accept a bare atomic number, e.g. "element 26" for iron.
+                    Some(SelectionToken::Number(n)) => {
+                        let atomic_number = *n as u8;
+                        self.advance();
+                        SelectionAST::Element(atomic_number.to_string())
+                    }
+                    _ => return Err(self.error("expected element symbol", "expected an element symbol or atomic number after 'element'".to_string())),
+                };
+                Ok(if negate { SelectionAST::Not(Box::new(ast)) } else { ast })
+            }
+            Some(SelectionToken::Name) => {
+                self.advance();
+                let negate = self.consume_optional_equality();
+                if let Some(SelectionToken::String(name)) = self.current() {
+                    let name = name.clone();
                     self.advance();
-                    Ok(SelectionAST::Element(element))
+                    let ast = SelectionAST::Name(name);
+                    Ok(if negate { SelectionAST::Not(Box::new(ast)) } else { ast })
                 } else {
-                    Err(AxiomError::SelectionError("Expected element symbol after 'element'".to_string()))
+                    Err(self.error("expected atom name", "expected an atom name after 'name'".to_string()))
                 }
             }
-            Some(SelectionToken::Resname) => {
+            Some(SelectionToken::NameRegex) => {
                 self.advance();
-                if let Some(SelectionToken::String(resname)) = self.current() {
-                    let resname = resname.clone();
+                if let Some(SelectionToken::String(pattern)) = self.current() {
+                    let pattern = pattern.clone();
                     self.advance();
-                    Ok(SelectionAST::Resname(resname))
+                    Ok(SelectionAST::NameRegex(pattern))
                 } else {
-                    Err(AxiomError::SelectionError("Expected residue name after 'resname'".to_string()))
+                    Err(self.error("expected glob pattern", "expected a glob pattern after 'nameregex'".to_string()))
                 }
             }
-            Some(SelectionToken::Chain) => {
+            Some(SelectionToken::Group) => {
                 self.advance();
-                if let Some(SelectionToken::String(chain)) = self.current() {
-                    let chain = chain.clone();
+                if let Some(SelectionToken::String(name)) = self.current() {
+                    let name = name.clone();
                     self.advance();
-                    Ok(SelectionAST::Chain(chain))
+                    Ok(SelectionAST::Group(name))
                 } else {
-                    Err(AxiomError::SelectionError("Expected chain ID after 'chain'".to_string()))
+                    Err(self.error("expected group name", "expected a functional group name after 'group'".to_string()))
                 }
             }
+            Some(SelectionToken::Resname) => {
+                self.advance();
+                let negate = self.consume_optional_equality();
+                let ast = match self.current() {
+                    Some(SelectionToken::String(resname)) => {
+                        let resname = resname.clone();
+                        self.advance();
+                        SelectionAST::Resname(resname)
+                    }
+                    // A quoted value is a glob pattern, e.g. resname "HI*".
+                    Some(SelectionToken::QuotedString(pattern)) => {
+                        let pattern = pattern.clone();
+                        self.advance();
+                        SelectionAST::ResnameGlob(pattern)
+                    }
+                    _ => return Err(self.error("expected residue name", "expected a residue name after 'resname'".to_string())),
+                };
+                Ok(if negate { SelectionAST::Not(Box::new(ast)) } else { ast })
+            }
+            Some(SelectionToken::Chain) => {
+                self.advance();
+                let negate = self.consume_optional_equality();
+                let ast = match self.current() {
+                    Some(SelectionToken::String(chain)) => {
+                        let chain = chain.clone();
+                        self.advance();
+                        SelectionAST::Chain(chain)
+                    }
+                    // A quoted value is a glob pattern, e.g. chain "A|B".
+                    Some(SelectionToken::QuotedString(pattern)) => {
+                        let pattern = pattern.clone();
+                        self.advance();
+                        SelectionAST::ChainGlob(pattern)
+                    }
+                    _ => return Err(self.error("expected chain id", "expected a chain ID after 'chain'".to_string())),
+                };
+                Ok(if negate { SelectionAST::Not(Box::new(ast)) } else { ast })
+            }
+            Some(SelectionToken::Beta) => self.parse_compare(Field::Beta),
+            Some(SelectionToken::Occupancy) => self.parse_compare(Field::Occupancy),
+            Some(SelectionToken::Mass) => self.parse_compare(Field::Mass),
+            Some(SelectionToken::Charge) => self.parse_compare(Field::Charge),
+            Some(SelectionToken::X) => self.parse_compare(Field::X),
+            Some(SelectionToken::Y) => self.parse_compare(Field::Y),
+            Some(SelectionToken::Z) => self.parse_compare(Field::Z),
             Some(SelectionToken::Resid) => {
                 self.advance();
                 match self.current() {
@@ -316,7 +775,7 @@ impl Parser {
                         self.advance();
                         Ok(SelectionAST::ResidRange(start, end))
                     }
-                    _ => Err(AxiomError::SelectionError("Expected residue number or range after 'resid'".to_string())),
+                    _ => Err(self.error("expected residue number", "expected a residue number or range after 'resid'".to_string())),
                 }
             }
             Some(SelectionToken::Within) => {
@@ -328,10 +787,18 @@ impl Parser {
                     let selection = self.parse()?;
                     Ok(SelectionAST::Within(distance, Box::new(selection)))
                 } else {
-                    Err(AxiomError::SelectionError("Expected distance after 'within'".to_string()))
+                    Err(self.error("expected distance", "expected a numeric distance after 'within'".to_string()))
                 }
             }
-            _ => Err(AxiomError::SelectionError(format!("Unexpected token: {:?}", self.current()))),
+            _ => {
+                let (start, end) = self.current_span();
+                let found = if self.current().is_some() {
+                    format!("{:?}", &self.input[start..end])
+                } else {
+                    "end of input".to_string()
+                };
+                Err(self.error("unexpected token", format!("expected a keyword, found {}", found)))
+            }
         }
     }
 }
@@ -343,14 +810,16 @@ mod tests {
     #[test]
     fn test_tokenize_simple() {
         let tokens = tokenize("element O").unwrap();
-        assert_eq!(tokens, vec![SelectionToken::Element, SelectionToken::String("O".to_string())]);
+        let kinds: Vec<SelectionToken> = tokens.into_iter().map(|t| t.token).collect();
+        assert_eq!(kinds, vec![SelectionToken::Element, SelectionToken::String("O".to_string())]);
     }
 
     #[test]
     fn test_tokenize_complex() {
         let tokens = tokenize("element O and resname WAT").unwrap();
+        let kinds: Vec<SelectionToken> = tokens.into_iter().map(|t| t.token).collect();
         assert_eq!(
-            tokens,
+            kinds,
             vec![
                 SelectionToken::Element,
                 SelectionToken::String("O".to_string()),
@@ -361,6 +830,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tokenize_captures_spans() {
+        let tokens = tokenize("element O").unwrap();
+        assert_eq!(tokens[0].span, (0, 7)); // "element"
+        assert_eq!(tokens[1].span, (8, 9)); // "O"
+    }
+
+    #[test]
+    fn test_tokenize_quoted_string() {
+        let tokens = tokenize(r#"resname "HI*""#).unwrap();
+        let kinds: Vec<SelectionToken> = tokens.into_iter().map(|t| t.token).collect();
+        assert_eq!(
+            kinds,
+            vec![SelectionToken::Resname, SelectionToken::QuotedString("HI*".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_unterminated_quoted_string_errors() {
+        let err = tokenize(r#"resname "HI*"#).unwrap_err();
+        assert!(matches!(err, AxiomError::SelectionSyntaxError(_)));
+    }
+
+    #[test]
+    fn test_parse_resname_glob() {
+        let ast = parse_selection(r#"resname "HI*""#).unwrap();
+        if let SelectionAST::ResnameGlob(pattern) = ast {
+            assert_eq!(pattern, "HI*");
+        } else {
+            panic!("Expected ResnameGlob AST node, got {:?}", ast);
+        }
+    }
+
+    #[test]
+    fn test_parse_element_glob() {
+        let ast = parse_selection(r#"element "C?""#).unwrap();
+        assert!(matches!(ast, SelectionAST::ElementGlob(ref p) if p == "C?"));
+    }
+
+    #[test]
+    fn test_parse_chain_glob() {
+        let ast = parse_selection(r#"chain "A|B""#).unwrap();
+        assert!(matches!(ast, SelectionAST::ChainGlob(ref p) if p == "A|B"));
+    }
+
+    #[test]
+    fn test_parse_bare_resname_stays_exact() {
+        // Unquoted values keep today's exact-match semantics.
+        let ast = parse_selection("resname WAT").unwrap();
+        assert!(matches!(ast, SelectionAST::Resname(ref r) if r == "WAT"));
+    }
+
+    #[test]
+    fn test_parse_error_unexpected_token_points_at_bad_word() {
+        let err = parse_selection("resnam WAT").unwrap_err();
+        if let AxiomError::SelectionSyntaxError(diag) = err {
+            assert_eq!(diag.span, (0, 6)); // "resnam"
+            let rendered = diag.to_string();
+            assert!(rendered.contains("resnam WAT"));
+            assert!(rendered.contains("^^^^^^"));
+        } else {
+            panic!("Expected SelectionSyntaxError, got {:?}", err);
+        }
+    }
+
+    #[test]
+    fn test_parse_error_missing_value_points_past_keyword() {
+        let err = parse_selection("within 5 of").unwrap_err();
+        if let AxiomError::SelectionSyntaxError(diag) = err {
+            // Ran out of tokens right after "within 5 of", so the caret
+            // should point at the (empty) end of the input.
+            assert_eq!(diag.span, (11, 11));
+        } else {
+            panic!("Expected SelectionSyntaxError, got {:?}", err);
+        }
+    }
+
     #[test]
     fn test_parse_all() {
         let ast = parse_selection("all").unwrap();
@@ -377,12 +923,74 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_element_atomic_number() {
+        let ast = parse_selection("element 26").unwrap();
+        if let SelectionAST::Element(e) = ast {
+            assert_eq!(e, "26");
+        } else {
+            panic!("Expected Element AST node");
+        }
+    }
+
     #[test]
     fn test_parse_and() {
         let ast = parse_selection("element O and resname WAT").unwrap();
         matches!(ast, SelectionAST::And(_, _));
     }
 
+    #[test]
+    fn test_parse_name() {
+        let ast = parse_selection("name CA").unwrap();
+        if let SelectionAST::Name(n) = ast {
+            assert_eq!(n, "CA");
+        } else {
+            panic!("Expected Name AST node");
+        }
+    }
+
+    #[test]
+    fn test_parse_nameregex() {
+        let ast = parse_selection("nameregex C*").unwrap();
+        if let SelectionAST::NameRegex(p) = ast {
+            assert_eq!(p, "C*");
+        } else {
+            panic!("Expected NameRegex AST node");
+        }
+    }
+
+    #[test]
+    fn test_parse_group() {
+        let ast = parse_selection("group carboxyl").unwrap();
+        if let SelectionAST::Group(name) = ast {
+            assert_eq!(name, "carboxyl");
+        } else {
+            panic!("Expected Group AST node");
+        }
+    }
+
+    #[test]
+    fn test_parse_byres() {
+        let ast = parse_selection("byres element O").unwrap();
+        if let SelectionAST::Byres(inner) = ast {
+            assert!(matches!(*inner, SelectionAST::Element(_)));
+        } else {
+            panic!("Expected Byres AST node");
+        }
+    }
+
+    #[test]
+    fn test_parse_bonded() {
+        let ast = parse_selection("bonded element O").unwrap();
+        assert!(matches!(ast, SelectionAST::Bonded(_)));
+    }
+
+    #[test]
+    fn test_parse_fragment() {
+        let ast = parse_selection("fragment element O").unwrap();
+        assert!(matches!(ast, SelectionAST::Fragment(_)));
+    }
+
     #[test]
     fn test_parse_within() {
         let ast = parse_selection("within 5 of resname LIG").unwrap();
@@ -392,4 +1000,126 @@ mod tests {
             panic!("Expected Within AST node");
         }
     }
+
+    #[test]
+    fn test_parse_compare_beta_greater_than() {
+        let ast = parse_selection("beta > 30").unwrap();
+        if let SelectionAST::Compare { field, op, value } = ast {
+            assert_eq!(field, Field::Beta);
+            assert_eq!(op, CompareOp::GreaterThan);
+            assert_eq!(value, 30.0);
+        } else {
+            panic!("Expected Compare AST node");
+        }
+    }
+
+    #[test]
+    fn test_parse_compare_occupancy_less_eq() {
+        let ast = parse_selection("occupancy <= 0.5").unwrap();
+        if let SelectionAST::Compare { field, op, value } = ast {
+            assert_eq!(field, Field::Occupancy);
+            assert_eq!(op, CompareOp::LessEq);
+            assert_eq!(value, 0.5);
+        } else {
+            panic!("Expected Compare AST node");
+        }
+    }
+
+    #[test]
+    fn test_parse_compare_rejects_equals_for_numeric_field() {
+        let err = parse_selection("mass = 12.0").unwrap_err();
+        assert!(matches!(err, AxiomError::SelectionSyntaxError(_)));
+
+        let err = parse_selection("charge != 0.0").unwrap_err();
+        assert!(matches!(err, AxiomError::SelectionSyntaxError(_)));
+    }
+
+    #[test]
+    fn test_parse_compare_and_macro() {
+        let ast = parse_selection("z < 10 and protein").unwrap();
+        if let SelectionAST::And(left, right) = ast {
+            assert!(matches!(*left, SelectionAST::Compare { field: Field::Z, op: CompareOp::LessThan, .. }));
+            assert!(matches!(*right, SelectionAST::Protein));
+        } else {
+            panic!("Expected And AST node");
+        }
+    }
+
+    #[test]
+    fn test_parse_element_not_equals() {
+        let ast = parse_selection("element != C").unwrap();
+        if let SelectionAST::Not(inner) = ast {
+            if let SelectionAST::Element(e) = *inner {
+                assert_eq!(e, "C");
+            } else {
+                panic!("Expected Element AST node inside Not");
+            }
+        } else {
+            panic!("Expected Not AST node");
+        }
+    }
+
+    #[test]
+    fn test_parse_compare_missing_operator_reports_error() {
+        let err = parse_selection("beta protein").unwrap_err();
+        if let AxiomError::SelectionSyntaxError(diag) = err {
+            assert!(diag.message.contains("comparison operator"));
+        } else {
+            panic!("Expected SelectionSyntaxError, got {:?}", err);
+        }
+    }
+
+    #[test]
+    fn test_parse_compare_missing_value_reports_error() {
+        let err = parse_selection("mass >").unwrap_err();
+        if let AxiomError::SelectionSyntaxError(diag) = err {
+            assert!(diag.message.contains("numeric value"));
+        } else {
+            panic!("Expected SelectionSyntaxError, got {:?}", err);
+        }
+    }
+
+    #[test]
+    fn test_parse_selection_all_reports_every_error_in_one_pass() {
+        let (ast, errors) = parse_selection_all("resname and chain and within of all");
+        assert_eq!(errors.len(), 3);
+        let ast = ast.expect("recovery should still produce a best-effort AST");
+        // Structurally still And(And(Invalid, Invalid), Invalid).
+        if let SelectionAST::And(left, right) = ast {
+            assert!(matches!(*right, SelectionAST::Invalid));
+            if let SelectionAST::And(l2, r2) = *left {
+                assert!(matches!(*l2, SelectionAST::Invalid));
+                assert!(matches!(*r2, SelectionAST::Invalid));
+            } else {
+                panic!("Expected nested And AST node");
+            }
+        } else {
+            panic!("Expected And AST node, got {:?}", ast);
+        }
+    }
+
+    #[test]
+    fn test_parse_selection_all_clean_query_has_no_errors() {
+        let (ast, errors) = parse_selection_all("element O and resname WAT");
+        assert!(errors.is_empty());
+        assert!(ast.is_some());
+    }
+
+    #[test]
+    fn test_parse_selection_all_recovers_unbalanced_parens() {
+        let (ast, errors) = parse_selection_all("(element O and resname WAT");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("implicit close"));
+        assert!(ast.is_some());
+    }
+
+    #[test]
+    fn test_parse_selection_all_makes_progress_on_repeated_bad_tokens() {
+        // Guard against infinite loops in recovery: a run of unparsable
+        // tokens with no recovery boundary at all should still terminate
+        // promptly (one error for the failed term, one for the leftover
+        // trailing tokens) rather than hang.
+        let (_, errors) = parse_selection_all(")))");
+        assert_eq!(errors.len(), 2);
+    }
 }
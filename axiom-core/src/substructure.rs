@@ -0,0 +1,359 @@
+// VF2 subgraph isomorphism over the atom+bond graph: match a small query
+// molecule (the "needle") against a loaded structure (the "haystack"),
+// returning every mapping of needle-atom-index -> haystack-atom-index.
+
+use crate::atoms::{Atoms, Bonds};
+use crate::bonds::build_adjacency;
+
+/// Atomic number used as a wildcard element: a needle atom with this
+/// element matches any haystack element.
+pub const WILDCARD_ELEMENT: u8 = 0;
+
+/// One needle-atom-index -> haystack-atom-index mapping.
+pub type SubstructureMapping = Vec<usize>;
+
+/// Find every mapping of `needle` onto `haystack` such that every needle
+/// atom's element matches (or is `WILDCARD_ELEMENT`), and every needle bond
+/// maps onto a haystack bond of the same order between the mapped atoms.
+pub fn find_substructures(
+    haystack: &Atoms,
+    haystack_bonds: &Bonds,
+    needle: &Atoms,
+    needle_bonds: &Bonds,
+) -> Vec<SubstructureMapping> {
+    let haystack_adjacency = build_adjacency(haystack, haystack_bonds);
+    let needle_adjacency = build_adjacency(needle, needle_bonds);
+    let needle_order = order_needle_atoms(needle, &needle_adjacency);
+
+    let mut matches = Vec::new();
+    let mut mapping = vec![usize::MAX; needle.len()];
+    let mut used_haystack_atoms = vec![false; haystack.len()];
+
+    vf2_extend(
+        haystack,
+        &haystack_adjacency,
+        needle,
+        &needle_adjacency,
+        &needle_order,
+        0,
+        &mut mapping,
+        &mut used_haystack_atoms,
+        &mut matches,
+    );
+
+    matches
+}
+
+/// True if `needle` matches anywhere inside `haystack` (stops at the first
+/// match, so it's cheaper than `find_substructures` when only presence
+/// matters).
+pub fn contains_substructure(
+    haystack: &Atoms,
+    haystack_bonds: &Bonds,
+    needle: &Atoms,
+    needle_bonds: &Bonds,
+) -> bool {
+    let haystack_adjacency = build_adjacency(haystack, haystack_bonds);
+    let needle_adjacency = build_adjacency(needle, needle_bonds);
+    let needle_order = order_needle_atoms(needle, &needle_adjacency);
+
+    let mut matches = Vec::new();
+    let mut mapping = vec![usize::MAX; needle.len()];
+    let mut used_haystack_atoms = vec![false; haystack.len()];
+
+    vf2_extend_until_first(
+        haystack,
+        &haystack_adjacency,
+        needle,
+        &needle_adjacency,
+        &needle_order,
+        0,
+        &mut mapping,
+        &mut used_haystack_atoms,
+        &mut matches,
+    );
+
+    !matches.is_empty()
+}
+
+/// Order needle atoms by rarity of element (how few needle atoms share that
+/// element - the most common element, usually carbon, goes last) so the
+/// search commits to distinctive heteroatoms first and prunes sooner. Ties
+/// broken by degree (highest-degree atoms first). The order is fixed before
+/// the search starts; it does not depend on the mapping built so far.
+fn order_needle_atoms(needle: &Atoms, needle_adjacency: &[Vec<(usize, u8)>]) -> Vec<usize> {
+    let mut element_counts = std::collections::HashMap::new();
+    for &element in &needle.elements {
+        *element_counts.entry(element).or_insert(0usize) += 1;
+    }
+
+    let mut order: Vec<usize> = (0..needle.len()).collect();
+    order.sort_by_key(|&i| {
+        let rarity = element_counts[&needle.elements[i]];
+        (rarity, std::cmp::Reverse(needle_adjacency[i].len()))
+    });
+    order
+}
+
+/// Recursively extend `mapping`, trying every feasible haystack atom for
+/// the next needle atom in `needle_order`, and collecting every complete
+/// mapping found.
+#[allow(clippy::too_many_arguments)]
+fn vf2_extend(
+    haystack: &Atoms,
+    haystack_adjacency: &[Vec<(usize, u8)>],
+    needle: &Atoms,
+    needle_adjacency: &[Vec<(usize, u8)>],
+    needle_order: &[usize],
+    depth: usize,
+    mapping: &mut Vec<usize>,
+    used_haystack_atoms: &mut Vec<bool>,
+    matches: &mut Vec<SubstructureMapping>,
+) {
+    if depth == needle_order.len() {
+        matches.push(mapping.clone());
+        return;
+    }
+
+    let needle_atom = needle_order[depth];
+
+    for haystack_atom in 0..haystack.len() {
+        if used_haystack_atoms[haystack_atom] {
+            continue;
+        }
+        if !is_feasible(
+            haystack,
+            haystack_adjacency,
+            needle,
+            needle_adjacency,
+            needle_atom,
+            haystack_atom,
+            mapping,
+        ) {
+            continue;
+        }
+
+        mapping[needle_atom] = haystack_atom;
+        used_haystack_atoms[haystack_atom] = true;
+
+        vf2_extend(
+            haystack,
+            haystack_adjacency,
+            needle,
+            needle_adjacency,
+            needle_order,
+            depth + 1,
+            mapping,
+            used_haystack_atoms,
+            matches,
+        );
+
+        mapping[needle_atom] = usize::MAX;
+        used_haystack_atoms[haystack_atom] = false;
+    }
+}
+
+/// Same recursion as `vf2_extend`, but stops as soon as one complete
+/// mapping has been found.
+#[allow(clippy::too_many_arguments)]
+fn vf2_extend_until_first(
+    haystack: &Atoms,
+    haystack_adjacency: &[Vec<(usize, u8)>],
+    needle: &Atoms,
+    needle_adjacency: &[Vec<(usize, u8)>],
+    needle_order: &[usize],
+    depth: usize,
+    mapping: &mut Vec<usize>,
+    used_haystack_atoms: &mut Vec<bool>,
+    matches: &mut Vec<SubstructureMapping>,
+) {
+    if !matches.is_empty() {
+        return;
+    }
+
+    if depth == needle_order.len() {
+        matches.push(mapping.clone());
+        return;
+    }
+
+    let needle_atom = needle_order[depth];
+
+    for haystack_atom in 0..haystack.len() {
+        if !matches.is_empty() {
+            return;
+        }
+        if used_haystack_atoms[haystack_atom] {
+            continue;
+        }
+        if !is_feasible(
+            haystack,
+            haystack_adjacency,
+            needle,
+            needle_adjacency,
+            needle_atom,
+            haystack_atom,
+            mapping,
+        ) {
+            continue;
+        }
+
+        mapping[needle_atom] = haystack_atom;
+        used_haystack_atoms[haystack_atom] = true;
+
+        vf2_extend_until_first(
+            haystack,
+            haystack_adjacency,
+            needle,
+            needle_adjacency,
+            needle_order,
+            depth + 1,
+            mapping,
+            used_haystack_atoms,
+            matches,
+        );
+
+        mapping[needle_atom] = usize::MAX;
+        used_haystack_atoms[haystack_atom] = false;
+    }
+}
+
+/// Element- and bond-compatibility feasibility check for mapping
+/// `needle_atom` onto `haystack_atom` given the mapping built so far: the
+/// element must match (respecting the wildcard), and every already-mapped
+/// neighbor of `needle_atom` must connect to `haystack_atom` in the
+/// haystack via a bond of the same order (the VF2 "syntactic feasibility"
+/// test, specialized to this single-edge-type graph).
+fn is_feasible(
+    haystack: &Atoms,
+    haystack_adjacency: &[Vec<(usize, u8)>],
+    needle: &Atoms,
+    needle_adjacency: &[Vec<(usize, u8)>],
+    needle_atom: usize,
+    haystack_atom: usize,
+    mapping: &[usize],
+) -> bool {
+    let needle_element = needle.elements[needle_atom];
+    if needle_element != WILDCARD_ELEMENT && needle_element != haystack.elements[haystack_atom] {
+        return false;
+    }
+
+    for &(needle_neighbor, order) in &needle_adjacency[needle_atom] {
+        let mapped_neighbor = mapping[needle_neighbor];
+        if mapped_neighbor == usize::MAX {
+            continue; // Not yet mapped - nothing to check until it is.
+        }
+
+        let bond_exists = haystack_adjacency[haystack_atom]
+            .iter()
+            .any(|&(n, haystack_order)| n == mapped_neighbor && haystack_order == order);
+        if !bond_exists {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atoms::Atoms;
+    use crate::bonds::compute_bonds_default;
+
+    fn ethane() -> (Atoms, Bonds) {
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 6);     // 0: C1
+        atoms.push(1.54, 0.0, 0.0, 6);    // 1: C2
+        atoms.push(-0.5, 0.87, 0.0, 1);   // 2: H on C1
+        atoms.push(-0.5, -0.87, 0.0, 1);  // 3: H on C1
+        atoms.push(-0.5, 0.0, 0.87, 1);   // 4: H on C1
+        atoms.push(2.04, 0.87, 0.0, 1);   // 5: H on C2
+        atoms.push(2.04, -0.87, 0.0, 1);  // 6: H on C2
+        atoms.push(2.04, 0.0, 0.87, 1);   // 7: H on C2
+
+        let bonds = compute_bonds_default(&atoms);
+        (atoms, bonds)
+    }
+
+    fn methyl_fragment() -> (Atoms, Bonds) {
+        // CH3: one carbon bonded to three hydrogens.
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 6);    // 0: C
+        atoms.push(-0.5, 0.87, 0.0, 1);  // 1: H
+        atoms.push(-0.5, -0.87, 0.0, 1); // 2: H
+        atoms.push(-0.5, 0.0, 0.87, 1);  // 3: H
+
+        let bonds = compute_bonds_default(&atoms);
+        (atoms, bonds)
+    }
+
+    #[test]
+    fn test_methyl_fragment_matches_both_carbons_in_ethane() {
+        let (haystack, haystack_bonds) = ethane();
+        let (needle, needle_bonds) = methyl_fragment();
+
+        let matches = find_substructures(&haystack, &haystack_bonds, &needle, &needle_bonds);
+
+        // Each match's needle-carbon (index 0) should map to a haystack
+        // carbon, and there should be one match rooted at each ethane
+        // carbon (permutations of which H maps to which count separately).
+        let matched_carbons: std::collections::HashSet<usize> = matches
+            .iter()
+            .map(|mapping| mapping[0])
+            .collect();
+        assert_eq!(matched_carbons, std::collections::HashSet::from([0, 1]));
+        assert!(!matches.is_empty());
+    }
+
+    #[test]
+    fn test_contains_substructure_methyl_in_ethane() {
+        let (haystack, haystack_bonds) = ethane();
+        let (needle, needle_bonds) = methyl_fragment();
+        assert!(contains_substructure(&haystack, &haystack_bonds, &needle, &needle_bonds));
+    }
+
+    #[test]
+    fn test_water_fragment_in_cluster() {
+        // A cluster of two separate water molecules far apart.
+        let mut haystack = Atoms::new();
+        haystack.push(0.0, 0.0, 0.0, 8);    // 0: O (water 1)
+        haystack.push(0.96, 0.0, 0.0, 1);   // 1: H
+        haystack.push(-0.24, 0.93, 0.0, 1); // 2: H
+
+        haystack.push(10.0, 0.0, 0.0, 8);    // 3: O (water 2)
+        haystack.push(10.96, 0.0, 0.0, 1);   // 4: H
+        haystack.push(9.76, 0.93, 0.0, 1);   // 5: H
+
+        let haystack_bonds = compute_bonds_default(&haystack);
+
+        let mut needle = Atoms::new();
+        needle.push(0.0, 0.0, 0.0, 8);    // 0: O
+        needle.push(0.96, 0.0, 0.0, 1);   // 1: H
+        needle.push(-0.24, 0.93, 0.0, 1); // 2: H
+        let needle_bonds = compute_bonds_default(&needle);
+
+        let matches = find_substructures(&haystack, &haystack_bonds, &needle, &needle_bonds);
+        let matched_oxygens: std::collections::HashSet<usize> = matches
+            .iter()
+            .map(|mapping| mapping[0])
+            .collect();
+        assert_eq!(matched_oxygens, std::collections::HashSet::from([0, 3]));
+    }
+
+    #[test]
+    fn test_no_match_for_incompatible_needle() {
+        let (haystack, haystack_bonds) = ethane();
+
+        // A needle carbon bonded to four hydrogens doesn't exist in ethane
+        // (each carbon there only has three).
+        let mut needle = Atoms::new();
+        needle.push(0.0, 0.0, 0.0, 6);
+        needle.push(-0.5, 0.87, 0.0, 1);
+        needle.push(-0.5, -0.87, 0.0, 1);
+        needle.push(-0.5, 0.0, 0.87, 1);
+        needle.push(0.5, 0.0, -0.87, 1);
+        let needle_bonds = compute_bonds_default(&needle);
+
+        assert!(!contains_substructure(&haystack, &haystack_bonds, &needle, &needle_bonds));
+    }
+}
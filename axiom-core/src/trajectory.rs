@@ -0,0 +1,565 @@
+// Multi-frame trajectory support
+//
+// A `Trajectory` bundles one shared topology (elements, residue names,
+// chains - identity information that does not change as a simulation
+// progresses) with a sequence of per-frame coordinate snapshots. Frames are
+// produced by streaming `FrameReader`s that understand the frame boundaries
+// of a specific file format (multi-MODEL PDB, concatenated multi-frame GRO)
+// and capture the topology as a side effect of their first yielded frame, so
+// identity metadata is stored once rather than per frame (later frames'
+// identity fields are read and discarded).
+
+use crate::atoms::Atoms;
+use crate::errors::{AxiomError, Result};
+use crate::parsers::gro::atom_name_to_element;
+use crate::parsers::pdb::{extract_element_from_atom_name, symbol_to_atomic_number};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::path::Path;
+
+/// One snapshot of coordinates (and optionally velocities/forces) from a
+/// trajectory, mirroring the frame model used by MD toolchains: step, time,
+/// box and x/v/f are each independently present or absent per frame.
+#[derive(Debug, Clone)]
+pub struct TrajectoryFrame {
+    pub step: u64,
+    pub time: f32,
+    pub box_vectors: Option<[[f32; 3]; 3]>,
+    pub positions: (Vec<f32>, Vec<f32>, Vec<f32>),
+    pub velocities: Option<(Vec<f32>, Vec<f32>, Vec<f32>)>,
+    pub forces: Option<(Vec<f32>, Vec<f32>, Vec<f32>)>,
+}
+
+impl TrajectoryFrame {
+    /// Number of atoms in this frame
+    pub fn len(&self) -> usize {
+        self.positions.0.len()
+    }
+
+    /// Check if this frame has no atoms
+    pub fn is_empty(&self) -> bool {
+        self.positions.0.is_empty()
+    }
+
+    /// Materialize this frame as a standalone `Atoms`, cloning `topology`
+    /// for identity metadata (elements, residue names, chains) and
+    /// overwriting the coordinates with this frame's own positions.
+    pub fn to_atoms(&self, topology: &Atoms) -> Atoms {
+        let mut atoms = topology.clone();
+        atoms.x = self.positions.0.clone();
+        atoms.y = self.positions.1.clone();
+        atoms.z = self.positions.2.clone();
+        atoms
+    }
+}
+
+/// Streaming iterator over trajectory frames.
+///
+/// Blanket-implemented for any `Iterator<Item = Result<TrajectoryFrame>>`,
+/// so concrete readers need only implement `Iterator`.
+pub trait FrameReader: Iterator<Item = Result<TrajectoryFrame>> {}
+
+impl<T> FrameReader for T where T: Iterator<Item = Result<TrajectoryFrame>> {}
+
+/// Streams frames from a multi-MODEL PDB file, one `MODEL`/`ENDMDL` block
+/// per frame. Topology (elements, residue names, chains) is captured from
+/// the first frame's `ATOM`/`HETATM` records.
+pub struct PdbModelFrameReader<R: BufRead> {
+    lines: Lines<R>,
+    topology: Option<Atoms>,
+    next_step: u64,
+}
+
+impl<R: BufRead> PdbModelFrameReader<R> {
+    pub fn new(reader: R) -> Self {
+        PdbModelFrameReader {
+            lines: reader.lines(),
+            topology: None,
+            next_step: 0,
+        }
+    }
+
+    /// Topology captured from the first frame; only available once at
+    /// least one frame has been read.
+    pub fn topology(&self) -> Option<&Atoms> {
+        self.topology.as_ref()
+    }
+}
+
+impl<R: BufRead> Iterator for PdbModelFrameReader<R> {
+    type Item = Result<TrajectoryFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut x = Vec::new();
+            let mut y = Vec::new();
+            let mut z = Vec::new();
+            let mut elements = Vec::new();
+            let mut residue_names = Vec::new();
+            let mut chain_ids = Vec::new();
+            let mut residue_indices = Vec::new();
+            let mut read_any_line = false;
+            let mut step = self.next_step;
+
+            loop {
+                let line = match self.lines.next() {
+                    Some(Ok(line)) => line,
+                    Some(Err(e)) => return Some(Err(e.into())),
+                    None => break,
+                };
+                read_any_line = true;
+
+                if line.starts_with("MODEL") {
+                    if let Some(serial) = line.get(10..14).and_then(|s| s.trim().parse::<u64>().ok()) {
+                        step = serial.saturating_sub(1);
+                    }
+                    continue;
+                }
+
+                // ENDMDL (end of this frame) and END (end of file) both
+                // close out the current frame.
+                if line.starts_with("END") {
+                    break;
+                }
+
+                if !line.starts_with("ATOM") && !line.starts_with("HETATM") {
+                    continue;
+                }
+
+                if line.len() < 54 {
+                    return Some(Err(AxiomError::ParseError(
+                        "ATOM/HETATM record too short (need at least 54 chars)".to_string(),
+                    )));
+                }
+
+                let resname = line.get(17..20)
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_else(|| "UNK".to_string());
+                let chain = line.get(21..22)
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_else(|| " ".to_string());
+                let resid: u32 = line.get(22..26).unwrap_or("    ").trim().parse().unwrap_or(0);
+
+                let parse_col = |range: std::ops::Range<usize>| -> Option<f32> {
+                    line.get(range).and_then(|s| s.trim().parse::<f32>().ok())
+                };
+
+                let (xv, yv, zv) = match (parse_col(30..38), parse_col(38..46), parse_col(46..54)) {
+                    (Some(a), Some(b), Some(c)) => (a, b, c),
+                    _ => {
+                        return Some(Err(AxiomError::ParseError(
+                            "Invalid coordinate in ATOM/HETATM record".to_string(),
+                        )))
+                    }
+                };
+
+                let element_symbol = if line.len() >= 78 {
+                    let elem = line.get(76..78).unwrap_or("").trim();
+                    if !elem.is_empty() {
+                        elem.to_string()
+                    } else {
+                        extract_element_from_atom_name(&line).to_string()
+                    }
+                } else {
+                    extract_element_from_atom_name(&line).to_string()
+                };
+
+                x.push(xv);
+                y.push(yv);
+                z.push(zv);
+                elements.push(symbol_to_atomic_number(&element_symbol));
+                residue_names.push(resname);
+                chain_ids.push(chain);
+                residue_indices.push(resid);
+            }
+
+            if !read_any_line {
+                return None;
+            }
+            if x.is_empty() {
+                // Empty MODEL/ENDMDL block (or stray blank lines) - try the next one.
+                continue;
+            }
+
+            self.next_step = step + 1;
+
+            if self.topology.is_none() {
+                let mut topo = Atoms::with_capacity(x.len());
+                topo.x = x.clone();
+                topo.y = y.clone();
+                topo.z = z.clone();
+                topo.elements = elements;
+                topo.residue_names = Some(residue_names);
+                topo.chain_ids = Some(chain_ids);
+                topo.residue_indices = Some(residue_indices);
+                self.topology = Some(topo);
+            }
+
+            return Some(Ok(TrajectoryFrame {
+                step,
+                time: 0.0,
+                box_vectors: None,
+                positions: (x, y, z),
+                velocities: None,
+                forces: None,
+            }));
+        }
+    }
+}
+
+/// Streams frames from a file made of concatenated GRO blocks (title,
+/// atom-count, fixed-width atom lines, box-vectors line - repeated once per
+/// frame), the format GROMACS trajectory-conversion tools emit. Topology is
+/// captured from the first frame's atom names.
+pub struct GroMultiFrameReader<R: BufRead> {
+    lines: Lines<R>,
+    topology: Option<Atoms>,
+    next_step: u64,
+}
+
+impl<R: BufRead> GroMultiFrameReader<R> {
+    pub fn new(reader: R) -> Self {
+        GroMultiFrameReader {
+            lines: reader.lines(),
+            topology: None,
+            next_step: 0,
+        }
+    }
+
+    /// Topology captured from the first frame; only available once at
+    /// least one frame has been read.
+    pub fn topology(&self) -> Option<&Atoms> {
+        self.topology.as_ref()
+    }
+}
+
+/// Extract `t=<time>` and `step=<step>` from a GRO title line, e.g.
+/// "Protein in water t=   0.00000 step= 0". Missing fields default to 0.0 /
+/// a sequential counter.
+fn parse_gro_title_metadata(title: &str) -> (f32, Option<u64>) {
+    let time = title
+        .split("t=")
+        .nth(1)
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|tok| tok.parse::<f32>().ok())
+        .unwrap_or(0.0);
+
+    let step = title
+        .split("step=")
+        .nth(1)
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|tok| tok.parse::<u64>().ok());
+
+    (time, step)
+}
+
+/// Parse a GRO box-vectors line (3 or 9 whitespace-separated nm values,
+/// GROMACS order: v1x v2y v3z v1y v1z v2x v2z v3x v3y), converting to
+/// Angstroms. Shared with the single-frame `parsers::gro` reader.
+pub(crate) fn parse_box_line(line: &str) -> Option<[[f32; 3]; 3]> {
+    let values: Vec<f32> = line
+        .split_whitespace()
+        .filter_map(|tok| tok.parse::<f32>().ok())
+        .collect();
+
+    match values.len() {
+        3 => Some([
+            [values[0] * 10.0, 0.0, 0.0],
+            [0.0, values[1] * 10.0, 0.0],
+            [0.0, 0.0, values[2] * 10.0],
+        ]),
+        9 => Some([
+            [values[0] * 10.0, values[3] * 10.0, values[4] * 10.0],
+            [values[5] * 10.0, values[1] * 10.0, values[6] * 10.0],
+            [values[7] * 10.0, values[8] * 10.0, values[2] * 10.0],
+        ]),
+        _ => None,
+    }
+}
+
+impl<R: BufRead> Iterator for GroMultiFrameReader<R> {
+    type Item = Result<TrajectoryFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let title = match self.lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(e)) => return Some(Err(e.into())),
+            None => return None,
+        };
+
+        let (time, parsed_step) = parse_gro_title_metadata(&title);
+
+        let num_atoms: usize = match self.lines.next() {
+            Some(Ok(line)) => match line.trim().parse() {
+                Ok(n) => n,
+                Err(_) => return Some(Err(AxiomError::ParseError("Invalid atom count".to_string()))),
+            },
+            Some(Err(e)) => return Some(Err(e.into())),
+            None => return Some(Err(AxiomError::ParseError("Missing atom count".to_string()))),
+        };
+
+        let mut x = Vec::with_capacity(num_atoms);
+        let mut y = Vec::with_capacity(num_atoms);
+        let mut z = Vec::with_capacity(num_atoms);
+        let mut vx = Vec::with_capacity(num_atoms);
+        let mut vy = Vec::with_capacity(num_atoms);
+        let mut vz = Vec::with_capacity(num_atoms);
+        let mut elements = Vec::with_capacity(num_atoms);
+        let mut has_velocities = num_atoms > 0;
+
+        for _ in 0..num_atoms {
+            let line = match self.lines.next() {
+                Some(Ok(line)) => line,
+                Some(Err(e)) => return Some(Err(e.into())),
+                None => return Some(Err(AxiomError::ParseError("Unexpected end of frame".to_string()))),
+            };
+
+            if line.len() < 44 {
+                return Some(Err(AxiomError::ParseError(
+                    "Atom line too short (need at least 44 chars for coordinates)".to_string(),
+                )));
+            }
+
+            let atom_name = line.get(10..15).unwrap_or("").trim();
+
+            let parse_col = |range: std::ops::Range<usize>| -> Option<f32> {
+                line.get(range).and_then(|s| s.trim().parse::<f32>().ok())
+            };
+
+            let (xv, yv, zv) = match (parse_col(20..28), parse_col(28..36), parse_col(36..44)) {
+                (Some(a), Some(b), Some(c)) => (a * 10.0, b * 10.0, c * 10.0),
+                _ => return Some(Err(AxiomError::ParseError("Invalid coordinate".to_string()))),
+            };
+            x.push(xv);
+            y.push(yv);
+            z.push(zv);
+            elements.push(atom_name_to_element(atom_name));
+
+            if has_velocities && line.len() >= 68 {
+                match (parse_col(44..52), parse_col(52..60), parse_col(60..68)) {
+                    (Some(a), Some(b), Some(c)) => {
+                        vx.push(a);
+                        vy.push(b);
+                        vz.push(c);
+                    }
+                    _ => has_velocities = false,
+                }
+            } else {
+                has_velocities = false;
+            }
+        }
+
+        let velocities = if has_velocities { Some((vx, vy, vz)) } else { None };
+
+        let box_vectors = match self.lines.next() {
+            Some(Ok(line)) => parse_box_line(&line),
+            Some(Err(e)) => return Some(Err(e.into())),
+            None => None,
+        };
+
+        let step = parsed_step.unwrap_or(self.next_step);
+        self.next_step = step + 1;
+
+        if self.topology.is_none() {
+            let mut topo = Atoms::with_capacity(num_atoms);
+            topo.x = x.clone();
+            topo.y = y.clone();
+            topo.z = z.clone();
+            topo.elements = elements;
+            self.topology = Some(topo);
+        }
+
+        Some(Ok(TrajectoryFrame {
+            step,
+            time,
+            box_vectors,
+            positions: (x, y, z),
+            velocities,
+            forces: None,
+        }))
+    }
+}
+
+/// An eagerly-loaded multi-frame trajectory: one shared topology (element,
+/// residue, chain identity) plus a sequence of per-frame coordinate
+/// snapshots.
+#[derive(Debug, Clone)]
+pub struct Trajectory {
+    pub topology: Atoms,
+    pub frames: Vec<TrajectoryFrame>,
+}
+
+impl Trajectory {
+    /// Number of frames
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Check if the trajectory has no frames
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Materialize a single frame as a standalone `Atoms`
+    pub fn frame_atoms(&self, index: usize) -> Option<Atoms> {
+        self.frames.get(index).map(|frame| frame.to_atoms(&self.topology))
+    }
+
+    /// Load a trajectory from a multi-MODEL PDB file
+    pub fn from_pdb_models<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path.as_ref())
+            .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+        Self::from_pdb_models_reader(BufReader::new(file))
+    }
+
+    /// Load a trajectory from a multi-MODEL PDB reader
+    pub fn from_pdb_models_reader<R: BufRead>(reader: R) -> Result<Self> {
+        let mut reader = PdbModelFrameReader::new(reader);
+        let mut frames = Vec::new();
+        for frame in &mut reader {
+            frames.push(frame?);
+        }
+        let topology = reader.topology().cloned().ok_or(AxiomError::EmptyStructure)?;
+        Ok(Trajectory { topology, frames })
+    }
+
+    /// Load a trajectory from a concatenated multi-frame GRO file
+    pub fn from_gro_frames<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path.as_ref())
+            .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+        Self::from_gro_frames_reader(BufReader::new(file))
+    }
+
+    /// Load a trajectory from a concatenated multi-frame GRO reader
+    pub fn from_gro_frames_reader<R: BufRead>(reader: R) -> Result<Self> {
+        let mut reader = GroMultiFrameReader::new(reader);
+        let mut frames = Vec::new();
+        for frame in &mut reader {
+            frames.push(frame?);
+        }
+        let topology = reader.topology().cloned().ok_or(AxiomError::EmptyStructure)?;
+        Ok(Trajectory { topology, frames })
+    }
+
+    /// Evaluate a selection query against one frame's moving coordinates,
+    /// while reusing the shared topology's identity metadata (elements,
+    /// residue names, chains).
+    pub fn select_frame(&self, frame_index: usize, query: &str) -> Result<Vec<usize>> {
+        let frame = self.frames.get(frame_index)
+            .ok_or(AxiomError::InvalidIndex(frame_index))?;
+        let ast = crate::selection::parse_selection(query)?;
+        crate::selection::evaluator::evaluate_selection_for_frame(&self.topology, frame, &ast)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, Cursor};
+
+    fn multi_model_pdb() -> &'static str {
+        "\
+MODEL        1
+ATOM      1  O   WAT A   1       0.000   0.000   0.000  1.00  0.00           O
+ATOM      2  H1  WAT A   1       0.757   0.586   0.000  1.00  0.00           H
+ENDMDL
+MODEL        2
+ATOM      1  O   WAT A   1       1.000   0.000   0.000  1.00  0.00           O
+ATOM      2  H1  WAT A   1       1.757   0.586   0.000  1.00  0.00           H
+ENDMDL
+END
+"
+    }
+
+    #[test]
+    fn test_pdb_model_frame_reader_yields_all_models() {
+        let cursor = Cursor::new(multi_model_pdb());
+        let mut reader = PdbModelFrameReader::new(BufReader::new(cursor));
+
+        let frame0 = reader.next().unwrap().unwrap();
+        assert_eq!(frame0.len(), 2);
+        assert_eq!(frame0.positions.0[0], 0.0);
+
+        let frame1 = reader.next().unwrap().unwrap();
+        assert_eq!(frame1.len(), 2);
+        assert_eq!(frame1.positions.0[0], 1.0);
+
+        assert!(reader.next().is_none());
+
+        let topology = reader.topology().unwrap();
+        assert_eq!(topology.len(), 2);
+        assert_eq!(topology.element(0), Some(8));
+        assert_eq!(topology.element(1), Some(1));
+    }
+
+    #[test]
+    fn test_trajectory_from_pdb_models() {
+        let cursor = Cursor::new(multi_model_pdb());
+        let trajectory = Trajectory::from_pdb_models_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(trajectory.len(), 2);
+        let frame0_atoms = trajectory.frame_atoms(0).unwrap();
+        assert_eq!(frame0_atoms.position(0), Some([0.0, 0.0, 0.0]));
+        let frame1_atoms = trajectory.frame_atoms(1).unwrap();
+        assert_eq!(frame1_atoms.position(0), Some([1.0, 0.0, 0.0]));
+
+        // Topology (element identity) is shared across both frames.
+        assert_eq!(frame0_atoms.element(0), frame1_atoms.element(0));
+    }
+
+    #[test]
+    fn test_trajectory_select_frame() {
+        let cursor = Cursor::new(multi_model_pdb());
+        let trajectory = Trajectory::from_pdb_models_reader(BufReader::new(cursor)).unwrap();
+
+        let indices = trajectory.select_frame(0, "element O").unwrap();
+        assert_eq!(indices, vec![0]);
+    }
+
+    fn multi_frame_gro() -> &'static str {
+        "\
+Water t=   0.00000 step= 0
+    2
+    1WAT     OW    1   0.000   0.000   0.000
+    1WAT    HW1    2   0.076   0.059   0.000
+   1.0   1.0   1.0
+Water t=   1.00000 step= 1
+    2
+    1WAT     OW    1   0.100   0.000   0.000
+    1WAT    HW1    2   0.176   0.059   0.000
+   1.0   1.0   1.0
+"
+    }
+
+    #[test]
+    fn test_gro_multi_frame_reader_yields_all_frames() {
+        let cursor = Cursor::new(multi_frame_gro());
+        let mut reader = GroMultiFrameReader::new(BufReader::new(cursor));
+
+        let frame0 = reader.next().unwrap().unwrap();
+        assert_eq!(frame0.step, 0);
+        assert_eq!(frame0.time, 0.0);
+        assert_eq!(frame0.positions.0[0], 0.0);
+        assert!(frame0.box_vectors.is_some());
+
+        let frame1 = reader.next().unwrap().unwrap();
+        assert_eq!(frame1.step, 1);
+        assert_eq!(frame1.time, 1.0);
+        assert_eq!(frame1.positions.0[0], 1.0);
+
+        assert!(reader.next().is_none());
+
+        let topology = reader.topology().unwrap();
+        assert_eq!(topology.len(), 2);
+        assert_eq!(topology.element(0), Some(8));
+    }
+
+    #[test]
+    fn test_trajectory_from_gro_frames() {
+        let cursor = Cursor::new(multi_frame_gro());
+        let trajectory = Trajectory::from_gro_frames_reader(BufReader::new(cursor)).unwrap();
+
+        assert_eq!(trajectory.len(), 2);
+        assert_eq!(trajectory.frame_atoms(1).unwrap().position(0), Some([1.0, 0.0, 0.0]));
+    }
+}
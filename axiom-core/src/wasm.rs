@@ -0,0 +1,66 @@
+// wasm-bindgen entry points so structure parsers can run client-side in a
+// browser instead of requiring a native binary.
+//
+// `Atoms`/`Bonds` already derive `Serialize`/`Deserialize`, so every wrapper
+// here just feeds the input string into the existing `parse_*_reader` path
+// (reading from a `Cursor` instead of a file) and hands the result to
+// `serde-wasm-bindgen` to cross the JS boundary. Parse failures are surfaced
+// as thrown JS exceptions carrying the `AxiomError` message rather than a
+// Rust `Result`, since `#[wasm_bindgen]` functions can't return `Result`
+// with a non-`JsValue` error type.
+
+use crate::errors::AxiomError;
+use crate::parsers;
+use std::io::{BufReader, Cursor};
+use wasm_bindgen::prelude::*;
+
+/// Convert an `AxiomError` into the JS exception thrown by every wrapper
+/// below, preserving its `Display` message.
+fn to_js_error(err: AxiomError) -> JsValue {
+    JsValue::from_str(&err.to_string())
+}
+
+/// Convert a parsed value into the `JsValue` returned to the browser, or a
+/// thrown JS exception if serialization itself fails.
+fn to_js_value<T: serde::Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    serde_wasm_bindgen::to_value(value).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Parse an XYZ file's text content into `Atoms`.
+#[wasm_bindgen]
+pub fn parse_xyz_str(text: &str) -> Result<JsValue, JsValue> {
+    let atoms = parsers::xyz::parse_xyz_reader(BufReader::new(Cursor::new(text))).map_err(to_js_error)?;
+    to_js_value(&atoms)
+}
+
+/// Parse a PDB file's text content into `Atoms` with bonds.
+#[wasm_bindgen]
+pub fn parse_pdb_str(text: &str) -> Result<JsValue, JsValue> {
+    let (atoms, bonds) =
+        parsers::pdb::parse_pdb_with_bonds_reader(BufReader::new(Cursor::new(text))).map_err(to_js_error)?;
+    to_js_value(&(atoms, bonds))
+}
+
+/// Parse a GROMACS GRO file's text content into `Atoms`.
+#[wasm_bindgen]
+pub fn parse_gro_str(text: &str) -> Result<JsValue, JsValue> {
+    let atoms = parsers::gro::parse_gro_reader(BufReader::new(Cursor::new(text))).map_err(to_js_error)?;
+    to_js_value(&atoms)
+}
+
+/// Parse a LAMMPS data file's text content into `Atoms` with bonds.
+#[wasm_bindgen]
+pub fn parse_lammps_str(text: &str) -> Result<JsValue, JsValue> {
+    let (atoms, bonds) =
+        parsers::lammps::parse_lammps_data_with_bonds_reader(BufReader::new(Cursor::new(text)))
+            .map_err(to_js_error)?;
+    to_js_value(&(atoms, bonds))
+}
+
+/// Parse a CIF file's text content into `Atoms` with bonds.
+#[wasm_bindgen]
+pub fn parse_cif_str(text: &str) -> Result<JsValue, JsValue> {
+    let (atoms, bonds) =
+        parsers::cif::parse_cif_with_bonds_reader(BufReader::new(Cursor::new(text))).map_err(to_js_error)?;
+    to_js_value(&(atoms, bonds))
+}
@@ -0,0 +1,127 @@
+// GROMOS96 (G96) file writer
+//
+// G96 is block-structured: each section is a `NAME` line, its data lines,
+// and a terminating `END` line. We write the blocks needed for a plain
+// coordinate file - `TITLE`, `POSITION`, and `BOX` - mirroring the fields
+// the GRO writer produces (coordinates in nm, residue/atom names, a
+// trailing box derived from `Atoms::periodic_box`).
+
+use crate::atoms::Atoms;
+use crate::errors::{AxiomError, Result};
+use crate::parsers::pdb::atomic_number_to_symbol;
+use crate::writers::resolve_indices;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Write `atoms` (optionally restricted to `indices`) to a G96 file at `path`.
+pub fn write_g96<P: AsRef<Path>>(atoms: &Atoms, indices: Option<&[usize]>, path: P) -> Result<()> {
+    let file = File::create(path.as_ref())
+        .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+    let mut writer = BufWriter::new(file);
+    write_g96_writer(atoms, indices, &mut writer)
+}
+
+/// Write `atoms` (optionally restricted to `indices`) as G96 to any `Write`.
+pub fn write_g96_writer<W: Write>(atoms: &Atoms, indices: Option<&[usize]>, writer: &mut W) -> Result<()> {
+    if atoms.is_empty() {
+        return Err(AxiomError::EmptyStructure);
+    }
+
+    let selected = resolve_indices(atoms, indices);
+
+    writeln!(writer, "TITLE")?;
+    writeln!(writer, "Generated by axiom-core")?;
+    writeln!(writer, "END")?;
+
+    writeln!(writer, "POSITION")?;
+    for (serial, &i) in selected.iter().enumerate() {
+        let resname = atoms
+            .residue_names
+            .as_ref()
+            .map(|r| r[i].as_str())
+            .unwrap_or("UNK");
+        let resid = atoms.residue_indices.as_ref().map(|r| r[i]).unwrap_or(1);
+        let name = atoms
+            .atom_names
+            .as_ref()
+            .map(|n| n[i].as_str())
+            .unwrap_or_else(|| atomic_number_to_symbol(atoms.elements[i]));
+
+        writeln!(
+            writer,
+            "{:>5} {:<5} {:<5}{:>6}{:>15.9}{:>15.9}{:>15.9}",
+            resid,
+            resname,
+            name,
+            serial + 1,
+            atoms.x[i] / 10.0,
+            atoms.y[i] / 10.0,
+            atoms.z[i] / 10.0,
+        )?;
+    }
+    writeln!(writer, "END")?;
+
+    writeln!(writer, "BOX")?;
+    if let Some(pbox) = &atoms.periodic_box {
+        let (a, b, c, _, _, _) = pbox.lengths_angles();
+        writeln!(writer, "{:>15.9}{:>15.9}{:>15.9}", a / 10.0, b / 10.0, c / 10.0)?;
+    } else {
+        writeln!(writer, "{:>15.9}{:>15.9}{:>15.9}", 0.0, 0.0, 0.0)?;
+    }
+    writeln!(writer, "END")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn water() -> Atoms {
+        let mut atoms = Atoms::new();
+        atoms.push(1.26, 1.26, 1.26, 8);
+        atoms.push(1.90, 1.26, 1.26, 1);
+        atoms.push(0.62, 1.26, 1.26, 1);
+        atoms.residue_names = Some(vec!["WAT".to_string(); 3]);
+        atoms.residue_indices = Some(vec![1; 3]);
+        atoms.atom_names = Some(vec!["OW".to_string(), "HW1".to_string(), "HW2".to_string()]);
+        atoms
+    }
+
+    #[test]
+    fn test_write_g96_blocks_are_well_formed() {
+        let atoms = water();
+        let mut buf = Vec::new();
+        write_g96_writer(&atoms, None, &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+
+        assert!(text.starts_with("TITLE\n"));
+        assert_eq!(text.matches("END").count(), 3); // TITLE, POSITION, BOX
+        assert!(text.contains("POSITION\n"));
+        assert!(text.contains("BOX\n"));
+    }
+
+    #[test]
+    fn test_write_g96_position_count_matches_selection() {
+        let atoms = water();
+        let mut buf = Vec::new();
+        write_g96_writer(&atoms, Some(&[0, 1]), &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+
+        let position_block: Vec<&str> = text
+            .lines()
+            .skip_while(|l| *l != "POSITION")
+            .skip(1)
+            .take_while(|l| *l != "END")
+            .collect();
+        assert_eq!(position_block.len(), 2);
+    }
+
+    #[test]
+    fn test_write_g96_empty_structure_errors() {
+        let atoms = Atoms::new();
+        let mut buf = Vec::new();
+        assert!(write_g96_writer(&atoms, None, &mut buf).is_err());
+    }
+}
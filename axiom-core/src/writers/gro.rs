@@ -0,0 +1,160 @@
+// GROMACS GRO file writer - the inverse of `parsers::gro`
+//
+// Coordinates are stored in `Atoms` as Angstroms and converted to nm
+// (divide by 10) for the GRO file. The trailing box line is derived from
+// `Atoms::periodic_box` when present; a zero box line is written otherwise,
+// matching GROMACS's convention for an unspecified box.
+
+use crate::atoms::Atoms;
+use crate::errors::{AxiomError, Result};
+use crate::parsers::pdb::atomic_number_to_symbol;
+use crate::writers::{resolve_indices, truncate_chars};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Write `atoms` (optionally restricted to `indices`) to a GRO file at `path`.
+pub fn write_gro<P: AsRef<Path>>(atoms: &Atoms, indices: Option<&[usize]>, path: P) -> Result<()> {
+    let file = File::create(path.as_ref())
+        .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+    let mut writer = BufWriter::new(file);
+    write_gro_writer(atoms, indices, &mut writer)
+}
+
+/// Write `atoms` (optionally restricted to `indices`) as GRO to any `Write`.
+pub fn write_gro_writer<W: Write>(atoms: &Atoms, indices: Option<&[usize]>, writer: &mut W) -> Result<()> {
+    if atoms.is_empty() {
+        return Err(AxiomError::EmptyStructure);
+    }
+
+    let selected = resolve_indices(atoms, indices);
+
+    writeln!(writer, "Generated by axiom-core")?;
+    writeln!(writer, "{:>5}", selected.len())?;
+
+    for (serial, &i) in selected.iter().enumerate() {
+        let resname = atoms
+            .residue_names
+            .as_ref()
+            .map(|r| r[i].as_str())
+            .unwrap_or("UNK");
+        let resid = atoms.residue_indices.as_ref().map(|r| r[i]).unwrap_or(1);
+        let name = atoms
+            .atom_names
+            .as_ref()
+            .map(|n| n[i].as_str())
+            .unwrap_or_else(|| atomic_number_to_symbol(atoms.elements[i]));
+
+        writeln!(
+            writer,
+            "{:>5}{:<5}{:>5}{:>5}{:>8.3}{:>8.3}{:>8.3}",
+            resid % 100_000,
+            truncate_chars(resname, 5),
+            truncate_chars(name, 5),
+            (serial + 1) % 100_000,
+            atoms.x[i] / 10.0,
+            atoms.y[i] / 10.0,
+            atoms.z[i] / 10.0,
+        )?;
+    }
+
+    write_box_line(atoms, writer)?;
+    Ok(())
+}
+
+/// Write the trailing box-vector line: 3 values for an orthorhombic box, the
+/// full 9-value triclinic form when any off-diagonal component is non-zero,
+/// or an all-zero line when the structure carries no periodic box.
+fn write_box_line<W: Write>(atoms: &Atoms, writer: &mut W) -> Result<()> {
+    if let Some(pbox) = &atoms.periodic_box {
+        let m = pbox.matrix;
+        let to_nm = |v: f32| v / 10.0;
+        let (v1x, v1y, v1z) = (to_nm(m[0][0]), to_nm(m[1][0]), to_nm(m[2][0]));
+        let (v2x, v2y, v2z) = (to_nm(m[0][1]), to_nm(m[1][1]), to_nm(m[2][1]));
+        let (v3x, v3y, v3z) = (to_nm(m[0][2]), to_nm(m[1][2]), to_nm(m[2][2]));
+
+        let triclinic = v1y.abs() > 1e-6 || v1z.abs() > 1e-6 || v2x.abs() > 1e-6
+            || v2z.abs() > 1e-6 || v3x.abs() > 1e-6 || v3y.abs() > 1e-6;
+
+        if triclinic {
+            writeln!(
+                writer,
+                "{:>10.5}{:>10.5}{:>10.5}{:>10.5}{:>10.5}{:>10.5}{:>10.5}{:>10.5}{:>10.5}",
+                v1x, v2y, v3z, v1y, v1z, v2x, v2z, v3x, v3y
+            )?;
+        } else {
+            writeln!(writer, "{:>10.5}{:>10.5}{:>10.5}", v1x, v2y, v3z)?;
+        }
+    } else {
+        writeln!(writer, "{:>10.5}{:>10.5}{:>10.5}", 0.0, 0.0, 0.0)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::gro::parse_gro_reader;
+    use std::io::BufReader;
+
+    fn water() -> Atoms {
+        let mut atoms = Atoms::new();
+        atoms.push(1.26, 1.26, 1.26, 8);
+        atoms.push(1.90, 1.26, 1.26, 1);
+        atoms.push(0.62, 1.26, 1.26, 1);
+        atoms.residue_names = Some(vec!["WAT".to_string(); 3]);
+        atoms.residue_indices = Some(vec![1; 3]);
+        atoms.atom_names = Some(vec!["OW".to_string(), "HW1".to_string(), "HW2".to_string()]);
+        atoms
+    }
+
+    #[test]
+    fn test_write_gro_round_trips_through_parser() {
+        let atoms = water();
+        let mut buf = Vec::new();
+        write_gro_writer(&atoms, None, &mut buf).unwrap();
+
+        let parsed = parse_gro_reader(BufReader::new(buf.as_slice())).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed.position(0), Some([1.26, 1.26, 1.26]));
+        assert_eq!(parsed.element(0), Some(8));
+    }
+
+    #[test]
+    fn test_write_gro_box_line_from_periodic_box() {
+        let mut atoms = water();
+        atoms.set_periodic_box([[20.0, 0.0, 0.0], [0.0, 20.0, 0.0], [0.0, 0.0, 20.0]]);
+
+        let mut buf = Vec::new();
+        write_gro_writer(&atoms, None, &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        let box_line = text.lines().last().unwrap();
+        let values: Vec<f32> = box_line.split_whitespace().map(|v| v.parse().unwrap()).collect();
+        assert_eq!(values, vec![2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_write_gro_respects_selection() {
+        let atoms = water();
+        let mut buf = Vec::new();
+        write_gro_writer(&atoms, Some(&[0]), &mut buf).unwrap();
+
+        let parsed = parse_gro_reader(BufReader::new(buf.as_slice())).unwrap();
+        assert_eq!(parsed.len(), 1);
+    }
+
+    #[test]
+    fn test_write_gro_truncates_multibyte_names_without_panicking() {
+        let mut atoms = water();
+        // "abcd世" is 5 chars but 7 bytes - byte-index slicing at [..5]
+        // would previously land mid multi-byte character and panic.
+        atoms.residue_names = Some(vec!["abcd世".to_string(); 3]);
+        atoms.atom_names = Some(vec!["abcd世".to_string(); 3]);
+
+        let mut buf = Vec::new();
+        write_gro_writer(&atoms, None, &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        assert!(text.lines().nth(2).unwrap().contains("abcd世"));
+    }
+}
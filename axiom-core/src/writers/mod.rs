@@ -0,0 +1,37 @@
+// Structure/coordinate writers - the inverse of `parsers`
+//
+// Every writer takes `&Atoms`, an optional slice of selected atom indices
+// (typically from `evaluate_selection`; `None` means "write everything"),
+// and a destination. This makes read -> select -> write round trips
+// straightforward, e.g. parse a CIF, run a selection, and export the
+// sub-system as a PDB or GRO file.
+
+pub mod gro;
+pub mod pdb;
+pub mod g96;
+
+pub use gro::write_gro;
+pub use pdb::write_pdb;
+pub use g96::write_g96;
+
+/// Resolve an optional selection into the list of atom indices to write, in
+/// ascending order, defaulting to every atom when no selection is given.
+fn resolve_indices(atoms: &crate::atoms::Atoms, indices: Option<&[usize]>) -> Vec<usize> {
+    match indices {
+        Some(selected) => {
+            let mut selected = selected.to_vec();
+            selected.sort_unstable();
+            selected
+        }
+        None => (0..atoms.len()).collect(),
+    }
+}
+
+/// Truncate `s` to at most `max_chars` characters. Fixed-width formats cap
+/// atom/residue names by byte-slicing (`&s[..n]`), which panics the moment
+/// `n` lands in the middle of a multi-byte UTF-8 character - names aren't
+/// guaranteed ASCII, so column widths need to be enforced per-character
+/// instead.
+fn truncate_chars(s: &str, max_chars: usize) -> String {
+    s.chars().take(max_chars).collect()
+}
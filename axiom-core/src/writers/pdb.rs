@@ -0,0 +1,202 @@
+// PDB file writer - the inverse of `parsers::pdb`
+//
+// Writes fixed-width ATOM/HETATM records matching the columns documented in
+// `parsers::pdb` (1-6 record name, 7-11 serial, 13-16 atom name, 18-20
+// resName, 22 chainID, 23-26 resSeq, 31-38/39-46/47-54 coordinates,
+// 77-78 element), plus a leading `CRYST1` record when the structure carries
+// a periodic box.
+
+use crate::atoms::Atoms;
+use crate::errors::{AxiomError, Result};
+use crate::parsers::pdb::atomic_number_to_symbol;
+use crate::writers::{resolve_indices, truncate_chars};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Standard protein residues, used only to decide between `ATOM` and
+/// `HETATM` records - mirrors `SelectionAST::Protein`'s residue list.
+const PROTEIN_RESIDUES: [&str; 24] = [
+    "ALA", "ARG", "ASN", "ASP", "CYS", "GLN", "GLU", "GLY", "HIS", "ILE", "LEU", "LYS", "MET",
+    "PHE", "PRO", "SER", "THR", "TRP", "TYR", "VAL", "HSD", "HSE", "HSP", "CYX",
+];
+
+/// Write `atoms` (optionally restricted to `indices`) to a PDB file at `path`.
+pub fn write_pdb<P: AsRef<Path>>(atoms: &Atoms, indices: Option<&[usize]>, path: P) -> Result<()> {
+    let file = File::create(path.as_ref())
+        .map_err(|_| AxiomError::FileNotFound(path.as_ref().display().to_string()))?;
+    let mut writer = BufWriter::new(file);
+    write_pdb_writer(atoms, indices, &mut writer)
+}
+
+/// Write `atoms` (optionally restricted to `indices`) as PDB to any `Write`.
+pub fn write_pdb_writer<W: Write>(atoms: &Atoms, indices: Option<&[usize]>, writer: &mut W) -> Result<()> {
+    if atoms.is_empty() {
+        return Err(AxiomError::EmptyStructure);
+    }
+
+    let selected = resolve_indices(atoms, indices);
+
+    if let Some(pbox) = &atoms.periodic_box {
+        let (a, b, c, alpha, beta, gamma) = pbox.lengths_angles();
+        writeln!(
+            writer,
+            "CRYST1{:>9.3}{:>9.3}{:>9.3}{:>7.2}{:>7.2}{:>7.2} P 1           1",
+            a, b, c, alpha, beta, gamma
+        )?;
+    }
+
+    for (serial, &i) in selected.iter().enumerate() {
+        let resname = atoms
+            .residue_names
+            .as_ref()
+            .map(|r| r[i].as_str())
+            .unwrap_or("UNK");
+        let record = if PROTEIN_RESIDUES.iter().any(|&p| resname.eq_ignore_ascii_case(p)) {
+            "ATOM"
+        } else {
+            "HETATM"
+        };
+
+        let name = atoms
+            .atom_names
+            .as_ref()
+            .map(|n| n[i].as_str())
+            .unwrap_or_else(|| atomic_number_to_symbol(atoms.elements[i]));
+        let chain = atoms.chain_ids.as_ref().map(|c| c[i].as_str()).unwrap_or("A");
+        let resid = atoms.residue_indices.as_ref().map(|r| r[i]).unwrap_or(1);
+        let element = atomic_number_to_symbol(atoms.elements[i]);
+
+        writeln!(
+            writer,
+            "{:<6}{:>5} {} {:<3} {:1}{:>4}    {:>8.3}{:>8.3}{:>8.3}{:>6.2}{:>6.2}          {:>2}",
+            record,
+            (serial + 1) % 100_000,
+            format_atom_name(name),
+            truncate_chars(resname, 3),
+            chain,
+            resid % 10_000,
+            atoms.x[i],
+            atoms.y[i],
+            atoms.z[i],
+            1.00,
+            0.00,
+            element,
+        )?;
+    }
+
+    writeln!(writer, "END")?;
+    Ok(())
+}
+
+/// Lay an atom name out in the 4-character field (columns 13-16): names of
+/// 4+ characters start at column 13, shorter names (the common case for
+/// single/double-letter elements) are shifted one column right.
+fn format_atom_name(name: &str) -> String {
+    if name.chars().count() >= 4 {
+        format!("{:<4}", truncate_chars(name, 4))
+    } else {
+        format!(" {:<3}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::pdb::parse_pdb_reader;
+    use std::io::BufReader;
+
+    fn water() -> Atoms {
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 8);
+        atoms.push(0.757, 0.586, 0.0, 1);
+        atoms.push(-0.757, 0.586, 0.0, 1);
+        atoms.residue_names = Some(vec!["WAT".to_string(); 3]);
+        atoms.chain_ids = Some(vec!["A".to_string(); 3]);
+        atoms.residue_indices = Some(vec![1; 3]);
+        atoms.atom_names = Some(vec!["O".to_string(), "H1".to_string(), "H2".to_string()]);
+        atoms
+    }
+
+    #[test]
+    fn test_write_pdb_round_trips_through_parser() {
+        let atoms = water();
+        let mut buf = Vec::new();
+        write_pdb_writer(&atoms, None, &mut buf).unwrap();
+
+        let parsed = parse_pdb_reader(BufReader::new(buf.as_slice())).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed.element(0), Some(8));
+        assert_eq!(parsed.element(1), Some(1));
+        assert_eq!(parsed.position(0), Some([0.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_write_pdb_uses_hetatm_for_water() {
+        let atoms = water();
+        let mut buf = Vec::new();
+        write_pdb_writer(&atoms, None, &mut buf).unwrap();
+
+        let text = String::from_utf8(buf).unwrap();
+        assert!(text.lines().next().unwrap().starts_with("HETATM"));
+    }
+
+    #[test]
+    fn test_write_pdb_respects_selection() {
+        let atoms = water();
+        let mut buf = Vec::new();
+        write_pdb_writer(&atoms, Some(&[0]), &mut buf).unwrap();
+
+        let parsed = parse_pdb_reader(BufReader::new(buf.as_slice())).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed.element(0), Some(8));
+    }
+
+    #[test]
+    fn test_write_pdb_empty_structure_errors() {
+        let atoms = Atoms::new();
+        let mut buf = Vec::new();
+        assert!(write_pdb_writer(&atoms, None, &mut buf).is_err());
+    }
+
+    #[test]
+    fn test_write_pdb_wraps_resid_instead_of_widening_columns() {
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 8);
+        atoms.push(1.0, 0.0, 0.0, 8);
+        atoms.residue_indices = Some(vec![9999, 10000]);
+
+        let mut buf = Vec::new();
+        write_pdb_writer(&atoms, None, &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        // serial (columns 7-11, 1-indexed -> [6..11)) sits right-aligned in
+        // its 5-wide field.
+        assert_eq!(&lines[0][6..11], "    1");
+        assert_eq!(&lines[1][6..11], "    2");
+        // resid (columns 23-26, 1-indexed -> [22..26)) wraps at 10000 rather
+        // than shifting every fixed-column field after it.
+        assert_eq!(&lines[0][22..26], "9999");
+        assert_eq!(&lines[1][22..26], "   0");
+        // Every record line keeps the same fixed total width, confirming
+        // nothing downstream shifted.
+        assert_eq!(lines[0].len(), lines[1].len());
+    }
+
+    #[test]
+    fn test_write_pdb_truncates_multibyte_names_without_panicking() {
+        let mut atoms = Atoms::new();
+        atoms.push(0.0, 0.0, 0.0, 8);
+        // "ab世" is 3 chars but 5 bytes, and "abc世" is 4 chars but 6 bytes -
+        // byte-index slicing at [..3]/[..4] would previously land mid
+        // multi-byte character and panic.
+        atoms.residue_names = Some(vec!["ab世".to_string()]);
+        atoms.atom_names = Some(vec!["abc世".to_string()]);
+
+        let mut buf = Vec::new();
+        write_pdb_writer(&atoms, None, &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        assert!(text.lines().next().unwrap().contains("ab世"));
+    }
+}
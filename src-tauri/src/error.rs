@@ -0,0 +1,57 @@
+// Structured, serializable errors for the Tauri command boundary.
+//
+// axiom_core::AxiomError is Display-only (it doesn't derive Serialize); it isn't meant
+// to cross the IPC boundary as-is. CommandError is the IPC-facing counterpart:
+// it derives `Serialize` with an internally tagged representation so the
+// frontend can match on `kind` (and read structured fields like `format` or
+// `index`) instead of string-matching an opaque message.
+
+use serde::Serialize;
+use thiserror::Error;
+
+#[derive(Error, Debug, Serialize)]
+#[serde(tag = "kind")]
+pub enum CommandError {
+    #[error("No structure loaded")]
+    NoStructureLoaded,
+
+    #[error("No renderer initialized")]
+    NoRendererInitialized,
+
+    #[error("Unsupported format: {format}")]
+    UnsupportedFormat { format: String },
+
+    #[error("Failed to parse {format}: {message}")]
+    ParseError { format: String, message: String },
+
+    #[error("Atom index {index} out of bounds (total: {total})")]
+    IndexOutOfBounds { index: usize, total: usize },
+
+    #[error("Rendering failed: {message}")]
+    RenderFailed { message: String },
+
+    #[error("Selection parse error: {message}")]
+    SelectionParse { message: String },
+
+    #[error("I/O error: {message}")]
+    Io { message: String },
+}
+
+impl From<std::io::Error> for CommandError {
+    fn from(err: std::io::Error) -> Self {
+        CommandError::Io { message: err.to_string() }
+    }
+}
+
+impl From<axiom_core::AxiomError> for CommandError {
+    fn from(err: axiom_core::AxiomError) -> Self {
+        match err {
+            axiom_core::AxiomError::RenderError(message) => CommandError::RenderFailed { message },
+            axiom_core::AxiomError::SelectionError(message) => CommandError::SelectionParse { message },
+            axiom_core::AxiomError::SelectionSyntaxError(diagnostic) => {
+                CommandError::SelectionParse { message: diagnostic.to_string() }
+            }
+            other => CommandError::ParseError { format: "structure".to_string(), message: other.to_string() },
+        }
+    }
+}
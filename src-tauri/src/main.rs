@@ -6,8 +6,11 @@ use std::path::PathBuf;
 use std::sync::Mutex;
 use tauri::State;
 
+mod error;
+use error::CommandError;
+
 // Import axiom-core types (use correct API)
-use axiom_core::{Atoms, Renderer, RendererConfig, BackgroundColor, compute_bonds};
+use axiom_core::{Atoms, Renderer, RendererConfig, BackgroundColor, Projection, ReconstructionFilter, compute_bonds, compute_bonds_valence};
 use axiom_core::parsers;
 
 /// Application state (shared across commands)
@@ -54,7 +57,7 @@ struct CameraState {
 }
 
 /// Result type for Tauri commands
-type CommandResult<T> = Result<T, String>;
+type CommandResult<T> = Result<T, CommandError>;
 
 /// Load structure from file
 #[tauri::command]
@@ -76,14 +79,14 @@ async fn load_structure(
     // Load atoms based on format
     let atoms = match format.as_str() {
         "pdb" => parsers::pdb::parse_pdb(&path)
-            .map_err(|e| format!("Failed to parse PDB: {}", e))?,
+            .map_err(|e| CommandError::ParseError { format: "PDB".to_string(), message: e.to_string() })?,
         "xyz" => parsers::xyz::parse_xyz(&path)
-            .map_err(|e| format!("Failed to parse XYZ: {}", e))?,
+            .map_err(|e| CommandError::ParseError { format: "XYZ".to_string(), message: e.to_string() })?,
         "gro" => parsers::gro::parse_gro(&path)
-            .map_err(|e| format!("Failed to parse GRO: {}", e))?,
+            .map_err(|e| CommandError::ParseError { format: "GRO".to_string(), message: e.to_string() })?,
         "lammpstrj" | "lammps" => parsers::lammps::parse_lammps(&path)
-            .map_err(|e| format!("Failed to parse LAMMPS: {}", e))?,
-        _ => return Err(format!("Unsupported format: {}", format)),
+            .map_err(|e| CommandError::ParseError { format: "LAMMPS".to_string(), message: e.to_string() })?,
+        _ => return Err(CommandError::UnsupportedFormat { format }),
     };
 
     // Calculate bounding box manually from Atoms SoA
@@ -149,7 +152,7 @@ async fn render_structure(
     let atoms_guard = state.atoms.lock().unwrap();
     let atoms = atoms_guard
         .as_ref()
-        .ok_or("No structure loaded")?;
+        .ok_or(CommandError::NoStructureLoaded)?;
 
     // Create renderer
     let background = match config.background.as_str() {
@@ -161,14 +164,27 @@ async fn render_structure(
     let renderer_config = RendererConfig {
         width: config.width,
         height: config.height,
+        projection: Projection::default(),
+        lights: vec![axiom_core::Light::Directional {
+            direction: [0.5, 0.5, 1.0],
+            color: [1.0, 1.0, 1.0],
+            intensity: 1.0,
+        }],
         ssaa_factor: config.ssaa as u32,
-        ao_enabled: config.enable_ao,
-        ao_samples: config.ao_samples as u32,
+        reconstruction_filter: ReconstructionFilter::default(),
+        dithering: true,
+        ssao_enabled: config.enable_ao,
+        ssao_samples: config.ao_samples as u32,
         background,
         specular_enabled: true,
         specular_power: 50.0,
-        ao_radius: 2.0,
-        ao_strength: 0.5,
+        ssao_radius: 2.0,
+        ssao_bias: 0.05,
+        raytrace_passes: 8,
+        raytrace_shadow_samples: 4,
+        raytrace_shadow_light_radius: 0.15,
+        raytrace_ao_samples: 12,
+        raytrace_ao_radius: 2.0,
         // Performance optimizations (Phase 6)
         enable_frustum_culling: true,
         enable_lod: true,
@@ -176,10 +192,11 @@ async fn render_structure(
         enable_octree: true,
         octree_max_depth: 8,
         octree_max_atoms_per_node: 32,
+        debug_flags: axiom_core::DebugFlags::NONE,
     };
 
     let mut renderer = Renderer::new(renderer_config)
-        .map_err(|e| format!("Renderer creation failed: {}", e))?;
+        .map_err(|e| CommandError::RenderFailed { message: e.to_string() })?;
 
     // Set camera if provided, otherwise use auto camera
     if let Some(cam) = camera {
@@ -222,7 +239,7 @@ async fn render_structure(
 
     // Render - returns PNG bytes directly
     let png_bytes = renderer.render(atoms)
-        .map_err(|e| format!("Rendering failed: {}", e))?;
+        .map_err(|e| CommandError::RenderFailed { message: e.to_string() })?;
 
     Ok(png_bytes)
 }
@@ -236,10 +253,10 @@ async fn select_atoms(
     let atoms_guard = state.atoms.lock().unwrap();
     let atoms = atoms_guard
         .as_ref()
-        .ok_or("No structure loaded")?;
+        .ok_or(CommandError::NoStructureLoaded)?;
 
     let selection = axiom_core::select(atoms, &query)
-        .map_err(|e| format!("Selection parse error: {}", e))?;
+        .map_err(|e| CommandError::SelectionParse { message: e.to_string() })?;
 
     Ok(selection)
 }
@@ -248,7 +265,7 @@ async fn select_atoms(
 #[tauri::command]
 async fn save_image(path: String, image_data: Vec<u8>) -> CommandResult<()> {
     std::fs::write(&path, &image_data)
-        .map_err(|e| format!("Failed to write file: {}", e))?;
+        .map_err(CommandError::from)?;
     Ok(())
 }
 
@@ -258,7 +275,7 @@ async fn get_statistics(state: State<'_, AppState>) -> CommandResult<StructureSt
     let atoms_guard = state.atoms.lock().unwrap();
     let atoms = atoms_guard
         .as_ref()
-        .ok_or("No structure loaded")?;
+        .ok_or(CommandError::NoStructureLoaded)?;
 
     let mut element_counts = std::collections::HashMap::new();
     for &elem in &atoms.elements {
@@ -288,15 +305,22 @@ struct StructureStats {
 async fn compute_bonds_cmd(
     tolerance: f32,
     max_distance: f32,
+    enforce_valence: bool,
     state: State<'_, AppState>
 ) -> CommandResult<usize> {
     let atoms_guard = state.atoms.lock().unwrap();
     let atoms = atoms_guard
         .as_ref()
-        .ok_or("No structure loaded")?;
+        .ok_or(CommandError::NoStructureLoaded)?;
 
-    // Use axiom_core::compute_bonds free function (3 args: atoms, tolerance, max_distance)
-    let bonds = compute_bonds(atoms, tolerance, max_distance);
+    // `enforce_valence` selects the valence-limited pass (drops a hypervalent
+    // atom's longest candidate bonds); off by default behavior (distance-only,
+    // permissive) is still reachable by passing false.
+    let bonds = if enforce_valence {
+        compute_bonds_valence(atoms, tolerance, max_distance)
+    } else {
+        compute_bonds(atoms, tolerance, max_distance)
+    };
     let bond_count = bonds.atom1.len();
 
     // Note: Bonds are returned but not stored in Atoms (Atoms struct doesn't have bonds field)
@@ -322,10 +346,10 @@ async fn get_atom_details(
     let atoms_guard = state.atoms.lock().unwrap();
     let atoms = atoms_guard
         .as_ref()
-        .ok_or("No structure loaded")?;
+        .ok_or(CommandError::NoStructureLoaded)?;
 
     if index >= atoms.len() {
-        return Err(format!("Atom index {} out of bounds (total: {})", index, atoms.len()));
+        return Err(CommandError::IndexOutOfBounds { index, total: atoms.len() });
     }
 
     let details = AtomDetails {
@@ -342,26 +366,42 @@ async fn get_atom_details(
 }
 
 /// Pick atom at screen coordinates (returns closest atom to click)
-/// This is a simplified version - for production, would use GPU picking or ray-casting
+///
+/// Builds a renderer sized to the viewport, applies the caller's camera, and
+/// reuses `Renderer::pick_atom`'s octree-accelerated ray cast - the same
+/// picking path the CPU renderer already uses internally.
 #[tauri::command]
 async fn pick_atom_at_screen(
-    _screen_x: f32,
-    _screen_y: f32,
-    _width: u32,
-    _height: u32,
+    screen_x: f32,
+    screen_y: f32,
+    width: u32,
+    height: u32,
+    camera: CameraState,
     state: State<'_, AppState>,
 ) -> CommandResult<Option<AtomDetails>> {
-    let _atoms_guard = state.atoms.lock().unwrap();
-    let _atoms = _atoms_guard
+    let atoms_guard = state.atoms.lock().unwrap();
+    let atoms = atoms_guard
         .as_ref()
-        .ok_or("No structure loaded")?;
+        .ok_or(CommandError::NoStructureLoaded)?;
+
+    let renderer_config = RendererConfig {
+        width,
+        height,
+        projection: Projection::Perspective { fov_y: camera.fov },
+        ..RendererConfig::default()
+    };
+
+    let mut renderer = Renderer::new(renderer_config)
+        .map_err(|e| CommandError::RenderFailed { message: e.to_string() })?;
+    renderer.set_camera(camera.position, camera.target, camera.up);
 
-    // For now, return None - this requires camera projection matrix
-    // In production, this would project atoms to screen space and find closest
-    // For Phase 4, we'll implement a simpler approach on frontend using canvas coordinates
+    let hit = renderer.pick_atom(atoms, screen_x, screen_y);
 
-    // Placeholder: Could implement basic ray-casting here with camera info
-    Ok(None)
+    Ok(hit.map(|index| AtomDetails {
+        index,
+        element: atoms.elements[index],
+        position: [atoms.x[index], atoms.y[index], atoms.z[index]],
+    }))
 }
 
 /// Export structure to file (PDB, XYZ, or CIF)
@@ -374,17 +414,17 @@ async fn export_structure(
     let atoms_guard = state.atoms.lock().unwrap();
     let atoms = atoms_guard
         .as_ref()
-        .ok_or("No structure loaded")?;
+        .ok_or(CommandError::NoStructureLoaded)?;
 
     let content = match format.as_str() {
         "pdb" => export_to_pdb(atoms),
         "xyz" => export_to_xyz(atoms),
         "cif" => export_to_cif(atoms),
-        _ => return Err(format!("Unsupported export format: {}", format)),
+        _ => return Err(CommandError::UnsupportedFormat { format }),
     };
 
     std::fs::write(&path, content)
-        .map_err(|e| format!("Failed to write file: {}", e))?;
+        .map_err(CommandError::from)?;
 
     Ok(())
 }
@@ -560,7 +600,7 @@ async fn export_measurements(
     }
 
     std::fs::write(&path, output)
-        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+        .map_err(CommandError::from)?;
 
     Ok(())
 }
@@ -614,8 +654,8 @@ struct OctreeStatsData {
 
 /// Get current performance metrics from renderer
 #[tauri::command]
-fn get_performance_metrics(state: State<AppState>) -> Result<PerformanceMetrics, String> {
-    let renderer_lock = state.renderer.lock().map_err(|e| format!("Lock error: {}", e))?;
+fn get_performance_metrics(state: State<AppState>) -> CommandResult<PerformanceMetrics> {
+    let renderer_lock = state.renderer.lock().map_err(|e| CommandError::RenderFailed { message: e.to_string() })?;
 
     if let Some(renderer) = renderer_lock.as_ref() {
         let summary = renderer.get_performance_summary();
@@ -632,14 +672,14 @@ fn get_performance_metrics(state: State<AppState>) -> Result<PerformanceMetrics,
             sample_count: summary.sample_count,
         })
     } else {
-        Err("No renderer initialized".to_string())
+        Err(CommandError::NoRendererInitialized)
     }
 }
 
 /// Get octree statistics (if built)
 #[tauri::command]
-fn get_octree_stats(state: State<AppState>) -> Result<Option<OctreeStatsData>, String> {
-    let renderer_lock = state.renderer.lock().map_err(|e| format!("Lock error: {}", e))?;
+fn get_octree_stats(state: State<AppState>) -> CommandResult<Option<OctreeStatsData>> {
+    let renderer_lock = state.renderer.lock().map_err(|e| CommandError::RenderFailed { message: e.to_string() })?;
 
     if let Some(renderer) = renderer_lock.as_ref() {
         if let Some(stats) = renderer.get_octree_stats() {
@@ -652,10 +692,51 @@ fn get_octree_stats(state: State<AppState>) -> Result<Option<OctreeStatsData>, S
             Ok(None)
         }
     } else {
-        Err("No renderer initialized".to_string())
+        Err(CommandError::NoRendererInitialized)
     }
 }
 
+/// wgpu validation/OOM diagnostics for the GPU renderer backend
+#[derive(Debug, Clone, Serialize)]
+struct GpuDiagnosticsData {
+    adapter_name: String,
+    backend: String,
+    validation_errors: Vec<String>,
+    oom_events: usize,
+    device_lost: bool,
+}
+
+/// Get wgpu validation/out-of-memory diagnostics for the GPU renderer backend.
+///
+/// The app's interactive rendering path uses the CPU (software) renderer, so
+/// this spins up a throwaway headless GPU renderer purely to capture adapter
+/// info and any validation/OOM errors along the way - including adapter
+/// lookup failure itself, which is expected on GPU-less or driver-less
+/// machines and is exactly the kind of thing this command should explain.
+#[tauri::command]
+async fn get_gpu_diagnostics() -> CommandResult<GpuDiagnosticsData> {
+    use axiom_core::renderer::{Renderer as GpuRenderer, RendererConfig as GpuRendererConfig};
+
+    let diagnostics = match GpuRenderer::new_blocking(GpuRendererConfig::default()) {
+        Ok(renderer) => renderer.gpu_diagnostics(),
+        Err(e) => axiom_core::GpuDiagnostics {
+            adapter_name: "none".to_string(),
+            backend: "none".to_string(),
+            validation_errors: vec![e.to_string()],
+            oom_events: 0,
+            device_lost: false,
+        },
+    };
+
+    Ok(GpuDiagnosticsData {
+        adapter_name: diagnostics.adapter_name,
+        backend: diagnostics.backend,
+        validation_errors: diagnostics.validation_errors,
+        oom_events: diagnostics.oom_events,
+        device_lost: diagnostics.device_lost,
+    })
+}
+
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
@@ -677,6 +758,7 @@ fn main() {
             export_measurements,
             get_performance_metrics,
             get_octree_stats,
+            get_gpu_diagnostics,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");